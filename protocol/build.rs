@@ -0,0 +1,545 @@
+//! Generates packet id tables and registry enums from the vanilla data generator reports in
+//! `data/reports`.
+//!
+//! Every directory in there holds the reports of one version, next to the `version.json` of the
+//! server jar. Packet ids are read from all of them, registries and blocks from the newest
+//! version which has them. The registries and blocks of older versions are turned into tables
+//! which map their ids to the ones of the newest version and back.
+//!
+//! `entities.json` and `block_light.json` are not generated by the server. They hold the hitbox
+//! dimensions and category of every entity type, and the light emission and opacity of the
+//! default state of every block, which are only available in the game code.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+const REPORTS: &str = "data/reports";
+
+/// The registries turned into enums, with the name of the enum.
+const REGISTRIES: &[(&str, &str)] = &[
+    ("minecraft:block", "Block"),
+    ("minecraft:block_entity_type", "BlockEntityType"),
+    ("minecraft:cat_variant", "CatVariant"),
+    ("minecraft:enchantment", "Enchantment"),
+    ("minecraft:entity_type", "EntityType"),
+    ("minecraft:frog_variant", "FrogVariant"),
+    ("minecraft:item", "Item"),
+    ("minecraft:menu", "Menu"),
+    ("minecraft:mob_effect", "MobEffect"),
+    ("minecraft:painting_variant", "PaintingVariant"),
+    ("minecraft:particle_type", "ParticleType"),
+    ("minecraft:sound_event", "SoundEvent"),
+    ("minecraft:villager_profession", "VillagerProfession"),
+    ("minecraft:villager_type", "VillagerType"),
+];
+
+/// Registries which were renamed, with their name in older versions.
+const RENAMED: &[(&str, &str)] = &[("minecraft:painting_variant", "minecraft:motive")];
+
+/// The ids of an older version by the ids of the newest one, and the other way around.
+type IdTables = (Vec<i32>, Vec<i32>);
+
+struct Report {
+    protocol: i32,
+    dir: PathBuf,
+}
+
+fn block_light(registry: &Map<String, Value>, light: &Map<String, Value>) -> String {
+    let rows: Vec<_> = sorted(registry)
+        .into_iter()
+        .map(|(id, _)| {
+            let block = &light[id];
+
+            format!("({}, {})", block["emission"], block["opacity"])
+        })
+        .collect();
+
+    format!("const BLOCK_LIGHT: &[(u8, u8)] = &[{}];\n", rows.join(", "))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={REPORTS}");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let mut reports: Vec<_> = fs::read_dir(REPORTS)
+        .unwrap()
+        .map(|entry| {
+            let dir = entry.unwrap().path();
+            let version = read(&dir.join("version.json")).unwrap();
+
+            println!("cargo:rerun-if-changed={}", dir.display());
+
+            Report {
+                protocol: version["protocol_version"].as_i64().unwrap() as i32,
+                dir,
+            }
+        })
+        .collect();
+
+    reports.sort_by_key(|report| report.protocol);
+
+    fs::write(out.join("packet_ids.rs"), packet_ids(&reports)).unwrap();
+
+    let newest = |file| {
+        reports
+            .iter()
+            .rev()
+            .find_map(|report| read(&report.dir.join(file)))
+            .unwrap_or_else(|| panic!("no report contains {file}"))
+    };
+
+    let registries = newest("registries.json");
+    let entries = |registry: &str| registries[registry]["entries"].as_object().unwrap();
+
+    let blocks = newest("blocks.json");
+    let older: Vec<_> = reports
+        .iter()
+        .filter_map(|report| {
+            let registries = read(&report.dir.join("registries.json"))?;
+            let blocks = read(&report.dir.join("blocks.json"))?;
+
+            Some((report.protocol, registries, blocks))
+        })
+        .collect();
+
+    let mut code = format!(
+        "const REGISTRY_VERSIONS: &[ProtocolVersion] = &[{}];\n",
+        older
+            .iter()
+            .map(|(protocol, ..)| format!("ProtocolVersion({protocol})"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    for (registry, name) in REGISTRIES {
+        code += &registry_enum(registry, name, entries(registry));
+
+        let tables = older.iter().map(|(protocol, registries, _)| {
+            let empty = Map::new();
+            let name = RENAMED
+                .iter()
+                .find(|(new, _)| new == registry && registries.get(new).is_none())
+                .map_or(*registry, |(_, old)| *old);
+            let old = registries[name]["entries"].as_object().unwrap_or(&empty);
+
+            (*protocol, registry_ids(entries(registry), old))
+        });
+
+        code += &version_ids(&const_name(registry), tables);
+    }
+
+    code += &block_states(entries("minecraft:block"), blocks.as_object().unwrap());
+
+    let tables = older.iter().map(|(protocol, _, old)| {
+        (
+            *protocol,
+            state_ids(blocks.as_object().unwrap(), old.as_object().unwrap()),
+        )
+    });
+
+    code += &version_ids("BLOCK_STATE", tables);
+    code += &entity_info(
+        entries("minecraft:entity_type"),
+        newest("entities.json").as_object().unwrap(),
+    );
+
+    code += &block_light(
+        entries("minecraft:block"),
+        newest("block_light.json").as_object().unwrap(),
+    );
+
+    fs::write(out.join("registries.rs"), code).unwrap();
+}
+
+fn read(path: &Path) -> Option<Value> {
+    let json = fs::read_to_string(path).ok()?;
+
+    Some(serde_json::from_str(&json).unwrap())
+}
+
+/// `minecraft:oak_stairs` to `OakStairs`.
+fn variant_name(id: &str) -> String {
+    let path = id.strip_prefix("minecraft:").unwrap_or(id);
+
+    path.split(['_', '/', '.'])
+        .map(|word| {
+            let mut chars = word.chars();
+
+            chars.next().map_or(String::new(), |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+/// `minecraft:move_player_pos` to `MOVE_PLAYER_POS`.
+fn const_name(id: &str) -> String {
+    let path = id.strip_prefix("minecraft:").unwrap_or(id);
+
+    path.replace(['/', '.'], "_").to_ascii_uppercase()
+}
+
+/// Merges the `packets.json` of all versions into one table per packet, keyed by the version in
+/// which the id changed.
+///
+/// Ids are only known up to the newest report, so every table ends with `None` after it.
+fn packet_ids(reports: &[Report]) -> String {
+    type Table = BTreeMap<i32, Option<i32>>;
+
+    let mut states: BTreeMap<String, BTreeMap<String, BTreeMap<String, Table>>> = BTreeMap::new();
+    let mut versions = vec![];
+
+    for report in reports {
+        let Some(packets) = read(&report.dir.join("packets.json")) else {
+            continue;
+        };
+
+        versions.push(report.protocol);
+
+        for (state, bounds) in packets.as_object().unwrap() {
+            for (bound, packets) in bounds.as_object().unwrap() {
+                let tables = states
+                    .entry(state.clone())
+                    .or_default()
+                    .entry(bound.clone())
+                    .or_default();
+
+                for (name, packet) in packets.as_object().unwrap() {
+                    let id = packet["protocol_id"].as_i64().unwrap() as i32;
+
+                    tables
+                        .entry(name.clone())
+                        .or_default()
+                        .insert(report.protocol, Some(id));
+                }
+            }
+        }
+    }
+
+    let until = versions.last().map_or(0, |newest| newest + 1);
+    let mut code = String::new();
+
+    for (state, bounds) in states {
+        writeln!(code, "pub mod {state} {{").unwrap();
+
+        for (bound, tables) in bounds {
+            writeln!(code, "pub mod {bound} {{").unwrap();
+            writeln!(code, "use crate::version::ProtocolVersion;").unwrap();
+
+            for (name, table) in tables {
+                let mut entries = vec![];
+                let mut last = None;
+
+                for version in versions.iter().chain([&until]) {
+                    let id = table.get(version).copied().flatten();
+
+                    if id != last && (last.is_some() || id.is_some()) {
+                        entries.push(match id {
+                            Some(id) => format!("(ProtocolVersion({version}), Some({id:#04x}))"),
+                            None => format!("(ProtocolVersion({version}), None)"),
+                        });
+                    }
+
+                    last = id;
+                }
+
+                writeln!(code, "/// `{name}`").unwrap();
+                writeln!(
+                    code,
+                    "pub const {}: &[(ProtocolVersion, Option<i32>)] = &[{}];",
+                    const_name(&name),
+                    entries.join(", ")
+                )
+                .unwrap();
+            }
+
+            writeln!(code, "}}").unwrap();
+        }
+
+        writeln!(code, "}}").unwrap();
+    }
+
+    code
+}
+
+/// Sorts the entries of a registry by protocol id, which must be contiguous from 0.
+fn sorted(entries: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = entries.iter().collect();
+
+    entries.sort_by_key(|(_, entry)| entry["protocol_id"].as_i64().unwrap());
+
+    for (index, (id, entry)) in entries.iter().enumerate() {
+        assert_eq!(
+            entry["protocol_id"].as_i64(),
+            Some(index as i64),
+            "protocol ids are not contiguous at {id}"
+        );
+    }
+
+    entries
+}
+
+fn registry_enum(registry: &str, name: &str, entries: &Map<String, Value>) -> String {
+    let mut names = BTreeMap::new();
+    let mut variants = vec![];
+
+    for (index, (id, _)) in sorted(entries).into_iter().enumerate() {
+        let variant = variant_name(id);
+        let path = id.strip_prefix("minecraft:").unwrap();
+
+        if let Some(other) = names.insert(variant.clone(), id) {
+            panic!("{id} and {other} are both named {variant}");
+        }
+
+        variants.push(format!("{variant} = {index} => {path:?}"));
+    }
+
+    format!(
+        "registry! {{ {name}({registry:?}, {}_IDS) {{ {} }} }}\n",
+        const_name(registry),
+        variants.join(", ")
+    )
+}
+
+/// The ids of the entries of a registry in an older version, by the ids of the newest one, and
+/// the other way around. Entries missing on the other side are `-1`.
+fn registry_ids(newest: &Map<String, Value>, old: &Map<String, Value>) -> IdTables {
+    let to = sorted(newest)
+        .into_iter()
+        .map(|(id, _)| old.get(id).map_or(-1, protocol_id))
+        .collect();
+    let from = sorted(old)
+        .into_iter()
+        .map(|(id, _)| newest.get(id).map_or(-1, protocol_id))
+        .collect();
+
+    (to, from)
+}
+
+fn protocol_id(entry: &Value) -> i32 {
+    entry["protocol_id"].as_i64().unwrap() as i32
+}
+
+/// The ids of the block states of an older version, by the ids of the newest one, and the other
+/// way around. States of blocks missing on the other side are `-1`.
+///
+/// Properties one side lacks are dropped, and properties the other side lacks take their default
+/// value, like for leaves, which can only be waterlogged since 1.19.
+fn state_ids(newest: &Map<String, Value>, old: &Map<String, Value>) -> IdTables {
+    (map_states(newest, old), map_states(old, newest))
+}
+
+fn map_states(from: &Map<String, Value>, to: &Map<String, Value>) -> Vec<i32> {
+    let mut ids = vec![];
+
+    let mut blocks: Vec<_> = from.iter().collect();
+
+    blocks.sort_by_key(|(_, block)| block["states"][0]["id"].as_i64().unwrap());
+
+    for (id, block) in blocks {
+        for state in block["states"].as_array().unwrap() {
+            assert_eq!(
+                state["id"].as_i64(),
+                Some(ids.len() as i64),
+                "unordered states of {id}"
+            );
+
+            let Some(target) = to.get(id) else {
+                ids.push(-1);
+                continue;
+            };
+
+            let states = target["states"].as_array().unwrap();
+            let default = states
+                .iter()
+                .find(|state| state["default"].as_bool() == Some(true))
+                .unwrap_or(&states[0]);
+            let names = target["properties"]
+                .as_object()
+                .map_or(vec![], |properties| properties.iter().collect());
+            let wanted: Map<_, _> = names
+                .iter()
+                .map(|(name, values)| {
+                    let value = state["properties"]
+                        .get(name.as_str())
+                        .filter(|value| values.as_array().unwrap().contains(value))
+                        .unwrap_or(&default["properties"][name.as_str()]);
+
+                    (name.to_string(), value.clone())
+                })
+                .collect();
+            let found = states
+                .iter()
+                .find(|state| state["properties"].as_object().unwrap_or(&Map::new()) == &wanted)
+                .unwrap();
+
+            ids.push(found["id"].as_i64().unwrap() as i32);
+        }
+    }
+
+    ids
+}
+
+/// The id tables of the versions whose ids differ from the newest one, as `{name}_IDS`.
+///
+/// Versions with the same ids share their tables.
+fn version_ids(name: &str, tables: impl Iterator<Item = (i32, IdTables)>) -> String {
+    let mut code = String::new();
+    let mut entries = vec![];
+    let mut shared: Vec<(IdTables, i32)> = vec![];
+
+    for (protocol, (to, from)) in tables {
+        let identity =
+            to.len() == from.len() && to.iter().enumerate().all(|(i, id)| *id == i as i32);
+
+        if identity {
+            continue;
+        }
+
+        let tables = (to, from);
+        let first = match shared.iter().find(|(other, _)| *other == tables) {
+            Some((_, first)) => *first,
+            None => {
+                let join = |ids: &[i32]| {
+                    ids.iter()
+                        .map(i32::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+
+                writeln!(
+                    code,
+                    "const {name}_TO_{protocol}: &[i32] = &[{}];",
+                    join(&tables.0)
+                )
+                .unwrap();
+                writeln!(
+                    code,
+                    "const {name}_FROM_{protocol}: &[i32] = &[{}];",
+                    join(&tables.1)
+                )
+                .unwrap();
+                shared.push((tables, protocol));
+                protocol
+            }
+        };
+
+        entries.push(format!(
+            "(ProtocolVersion({protocol}), {name}_TO_{first}, {name}_FROM_{first})"
+        ));
+    }
+
+    writeln!(
+        code,
+        "const {name}_IDS: VersionIds = &[{}];",
+        entries.join(", ")
+    )
+    .unwrap();
+
+    code
+}
+
+/// The range of global palette ids, the default state and the properties of every block.
+///
+/// The states of a block must be numbered like the game does: every combination of property
+/// values in order, with the last property changing fastest.
+fn block_states(registry: &Map<String, Value>, blocks: &Map<String, Value>) -> String {
+    let mut rows = vec![];
+    let mut properties = vec![];
+
+    for (id, _) in sorted(registry) {
+        let block = &blocks[id];
+        let states = block["states"].as_array().unwrap();
+        let state_id = |state: &Value| state["id"].as_i64().unwrap();
+
+        let min = states.iter().map(state_id).min().unwrap();
+        let max = states.iter().map(state_id).max().unwrap();
+        let default = states
+            .iter()
+            .find(|state| state["default"].as_bool() == Some(true))
+            .map_or(min, state_id);
+
+        let names: Vec<_> = block["properties"]
+            .as_object()
+            .map_or(vec![], |properties| properties.iter().collect());
+        let mut expected = vec![Map::new()];
+
+        for (name, values) in &names {
+            expected = expected
+                .into_iter()
+                .flat_map(|state| {
+                    values.as_array().unwrap().iter().map(move |value| {
+                        let mut state = state.clone();
+                        state.insert(name.to_string(), value.clone());
+                        state
+                    })
+                })
+                .collect();
+        }
+
+        for (offset, (state, expected)) in states.iter().zip(&expected).enumerate() {
+            assert_eq!(
+                state_id(state),
+                min + offset as i64,
+                "unordered states of {id}"
+            );
+            assert_eq!(
+                state["properties"].as_object().unwrap_or(&Map::new()),
+                expected,
+                "unordered states of {id}"
+            );
+        }
+
+        assert_eq!(states.len(), expected.len(), "missing states of {id}");
+
+        rows.push(format!(
+            "(Block::{}, {min}, {max}, {default})",
+            variant_name(id)
+        ));
+        properties.push(format!(
+            "&[{}]",
+            names
+                .iter()
+                .map(|(name, values)| format!("({name:?}, &{values})"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    format!(
+        "const BLOCK_STATES: &[(Block, i32, i32, i32)] = &[{}];\n\
+        const BLOCK_PROPERTIES: &[&[(&str, &[&str])]] = &[{}];\n",
+        rows.join(", "),
+        properties.join(", ")
+    )
+}
+
+/// The hitbox dimensions and category of every entity type, in protocol id order.
+fn entity_info(registry: &Map<String, Value>, entities: &Map<String, Value>) -> String {
+    let rows: Vec<_> = sorted(registry)
+        .into_iter()
+        .map(|(id, _)| {
+            let entity = &entities[id];
+
+            format!(
+                "({:?}, {:?}, EntityCategory::{})",
+                entity["width"].as_f64().unwrap() as f32,
+                entity["height"].as_f64().unwrap() as f32,
+                variant_name(entity["category"].as_str().unwrap())
+            )
+        })
+        .collect();
+
+    format!(
+        "const ENTITY_INFO: &[(f32, f32, EntityCategory)] = &[{}];\n",
+        rows.join(", ")
+    )
+}
@@ -7,6 +7,7 @@ use arrow_protocol::{
     error::DeserializeError,
     handshake::{Handshake, NextState},
     status::{Status, StatusRequest},
+    version::ProtocolVersion,
     Bound, Protocol, State,
 };
 use bytes::BytesMut;
@@ -23,9 +24,10 @@ struct Args {
     #[clap(short, value_parser, default_value_t = 25565)]
     port: u16,
 
-    /// The protocol version to use in the Handshake packet.
-    #[clap(short = 'v', long, value_parser, default_value_t = 759)]
-    protocol_version: i32,
+    /// The protocol version to use in the Handshake packet, either as a number like `759` or as a
+    /// version name like `1.19`.
+    #[clap(short = 'v', long, value_parser, default_value = "1.19")]
+    protocol_version: ProtocolVersion,
 }
 
 fn main() {
@@ -35,20 +37,23 @@ fn main() {
         Ok(mut tcp_stream) => {
             tcp_stream
                 .write_all(
-                    &Protocol::Handshake(Handshake {
-                        version: args.protocol_version.into(),
-                        address: args.address,
-                        port: args.port,
-                        next_state: NextState::Status,
-                    })
-                    .serialize(Default::default(), None)
+                    &Protocol::Handshake(
+                        Handshake {
+                            version: args.protocol_version.into(),
+                            address: args.address,
+                            port: args.port,
+                            next_state: NextState::Status,
+                        }
+                        .into(),
+                    )
+                    .serialize_with_version(args.protocol_version, Default::default(), None)
                     .unwrap(),
                 )
                 .unwrap();
             tcp_stream
                 .write_all(
                     &Protocol::Status(Status::StatusRequest(StatusRequest))
-                        .serialize(Default::default(), None)
+                        .serialize_with_version(args.protocol_version, Default::default(), None)
                         .unwrap(),
                 )
                 .unwrap();
@@ -65,9 +70,10 @@ fn main() {
 
                 packet.extend_from_slice(&buf[..len]);
 
-                let response = Protocol::deserialize(
+                let response = Protocol::deserialize_with_version(
                     Bound::Clientbound,
                     State::Status,
+                    args.protocol_version,
                     Default::default(),
                     None,
                     &mut packet.clone(),
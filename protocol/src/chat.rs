@@ -1,8 +1,14 @@
+use std::ops::Deref;
+
+use bytes::{Buf, BufMut};
 use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Value};
 
 use crate::{
     error::{DeRes, DeserializeError, SerRes},
-    types::Serialize as Ser,
+    nbt::{Compound, Tag},
+    types::{Context, Decode, Encode},
+    version::ProtocolVersion,
 };
 
 #[serde_with::skip_serializing_none]
@@ -25,21 +31,148 @@ pub struct Chat {
     pub extra: Option<Vec<Chat>>,
 }
 
-impl Ser for Chat {
-    fn serialize(&self, buf: &mut bytes::BytesMut) -> SerRes<()> {
+impl Encode for Chat {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         let s = serde_json::to_string(&self).unwrap();
 
-        Ser::serialize(&s, buf)
+        s.encode(buf)
     }
 
-    fn deserialize(buf: &mut bytes::BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        let s: String = Ser::deserialize(buf)?;
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if !is_nbt(cx) {
+            return self.encode(buf);
+        }
+
+        let value = serde_json::to_value(self).unwrap();
+
+        to_tag(&value)
+            .unwrap_or_else(|| Compound::new().into())
+            .write_nameless(buf)
+    }
+}
+
+impl Decode for Chat {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let s = String::decode(buf)?;
 
         serde_json::from_str(&s).map_err(DeserializeError::JsonError)
     }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if !is_nbt(cx) {
+            return Self::decode(buf);
+        }
+
+        let value = match from_tag(Tag::read_nameless(buf)?) {
+            Value::String(text) => json!({ "text": text }),
+            value => value,
+        };
+
+        serde_json::from_value(value).map_err(DeserializeError::JsonError)
+    }
+}
+
+/// A [`Chat`] which is always sent as JSON, like the login disconnect reason.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct JsonChat(pub Chat);
+
+impl From<Chat> for JsonChat {
+    fn from(chat: Chat) -> Self {
+        Self(chat)
+    }
+}
+
+impl Deref for JsonChat {
+    type Target = Chat;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Encode for JsonChat {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.0.encode(buf)
+    }
+}
+
+impl Decode for JsonChat {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Chat::decode(buf).map(Self)
+    }
+}
+
+/// Chat components are sent as nameless NBT since 1.20.3.
+fn is_nbt(cx: &Context) -> bool {
+    cx.version.since(ProtocolVersion::V1_20_3)
+}
+
+/// Converts JSON into the tags vanilla writes for it, leaving out `null`.
+fn to_tag(value: &Value) -> Option<Tag> {
+    let tag = match value {
+        Value::Null => return None,
+        Value::Bool(b) => Tag::Byte(*b as i8),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i32::try_from(i).map_or(Tag::Long(i), Tag::Int),
+            None => Tag::Double(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => Tag::String(s.clone()),
+        Value::Array(values) => {
+            let tags: Vec<_> = values.iter().filter_map(to_tag).collect();
+
+            // NBT lists hold a single tag type, so mixed lists wrap their elements in compounds.
+            if tags.windows(2).all(|pair| pair[0].id() == pair[1].id()) {
+                Tag::List(tags)
+            } else {
+                Tag::List(
+                    tags.into_iter()
+                        .map(|tag| match tag {
+                            Tag::Compound(_) => tag,
+                            tag => Tag::Compound([(String::new(), tag)].into_iter().collect()),
+                        })
+                        .collect(),
+                )
+            }
+        }
+        Value::Object(map) => Tag::Compound(
+            map.iter()
+                .filter_map(|(key, value)| Some((key.clone(), to_tag(value)?)))
+                .collect(),
+        ),
+    };
+
+    Some(tag)
+}
+
+fn from_tag(tag: Tag) -> Value {
+    match tag {
+        // Chat components only use bytes for booleans.
+        Tag::Byte(b) => Value::Bool(b != 0),
+        Tag::Short(i) => i.into(),
+        Tag::Int(i) => i.into(),
+        Tag::Long(i) => i.into(),
+        Tag::Float(f) => f.into(),
+        Tag::Double(f) => f.into(),
+        Tag::ByteArray(bytes) => bytes.into(),
+        Tag::String(s) => Value::String(s),
+        Tag::List(list) => list
+            .into_iter()
+            .map(|tag| match tag {
+                Tag::Compound(mut compound) if compound.len() == 1 && compound.contains_key("") => {
+                    from_tag(compound.remove("").unwrap())
+                }
+                tag => from_tag(tag),
+            })
+            .collect(),
+        Tag::Compound(compound) => Value::Object(
+            compound
+                .into_iter()
+                .map(|(key, tag)| (key, from_tag(tag)))
+                .collect(),
+        ),
+        Tag::IntArray(ints) => ints.into(),
+        Tag::LongArray(longs) => longs.into(),
+    }
 }
 
 impl Chat {
@@ -3,6 +3,7 @@ use tokio_util::codec::{Decoder, Encoder};
 
 use crate::{
     error::{DeserializeError, SerializeError},
+    version::ProtocolVersion,
     Bound, Decryptor, Encryptor, PacketCompression, Protocol, State,
 };
 
@@ -10,6 +11,7 @@ use crate::{
 pub struct Codec {
     bound: Bound,
     state: State,
+    version: ProtocolVersion,
     compression: PacketCompression,
     encryptor: Option<Encryptor>,
     decryptor: Option<Decryptor>,
@@ -20,6 +22,7 @@ impl Codec {
         Self {
             bound,
             state,
+            version: Default::default(),
             compression: Default::default(),
             encryptor: None,
             decryptor: None,
@@ -36,9 +39,38 @@ impl Codec {
         self.decryptor = Some(Decryptor::new(&key.into(), &key.into()));
     }
 
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Sets the state packets are decoded in.
+    ///
+    /// Packets which switch the state, like [`Handshake`](crate::handshake::Handshake) or
+    /// `LoginAcknowledged`, do this automatically when they pass through the codec.
     pub fn set_state(&mut self, state: State) {
         self.state = state;
     }
+
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// Sets the protocol version used for packet ids and layouts.
+    ///
+    /// This is done automatically when a handshake passes through the codec.
+    pub fn set_version(&mut self, version: ProtocolVersion) {
+        self.version = version;
+    }
+
+    fn update(&mut self, packet: &Protocol) {
+        if let Protocol::Handshake(handshake) = packet {
+            self.version = handshake.protocol_version();
+        }
+
+        if let Some(state) = packet.next_state(self.version) {
+            self.state = state;
+        }
+    }
 }
 
 impl Decoder for Codec {
@@ -47,14 +79,19 @@ impl Decoder for Codec {
     type Error = DeserializeError;
 
     fn decode(&mut self, src: &mut bytes::BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        match Protocol::deserialize(
+        match Protocol::deserialize_with_version(
             self.bound,
             self.state,
+            self.version,
             self.compression,
             self.decryptor.as_mut(),
             src,
         ) {
-            Ok(packet) => Ok(Some(packet)),
+            Ok(packet) => {
+                self.update(&packet);
+
+                Ok(Some(packet))
+            }
             Err(DeserializeError::UnexpectedEof) => Ok(None),
             Err(e) => Err(e),
         }
@@ -66,10 +103,12 @@ impl Encoder<Protocol> for Codec {
 
     fn encode(&mut self, item: Protocol, dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
         dst.extend_from_slice(
-            item.serialize(self.compression, self.encryptor.as_mut())?
+            item.serialize_with_version(self.version, self.compression, self.encryptor.as_mut())?
                 .as_slice(),
         );
 
+        self.update(&item);
+
         Ok(())
     }
 }
@@ -0,0 +1,169 @@
+use uuid::Uuid;
+
+use crate::{
+    chat::Chat,
+    data, packets,
+    play::{ChatMode, MainHand, ParticleStatus, SkinParts},
+    state,
+    types::{
+        identifier::Identifier, raw_nbt::RawNbt, varint::VarInt, ByteArray, InferredLenByteArray,
+    },
+    varint_enum,
+};
+
+state! {
+    Configuration(V1_20_2..=V1_21_7);
+    serverbound {
+        ClientInformation { V1_20_2 = 0x00 },
+        CookieResponse { V1_20_5 = 0x01 },
+        ServerboundPluginMessage { V1_20_2 = 0x01, V1_20_5 = 0x02 },
+        AcknowledgeFinishConfiguration { V1_20_2 = 0x02, V1_20_5 = 0x03 },
+        ServerboundKeepAlive { V1_20_2 = 0x03, V1_20_5 = 0x04 },
+        Pong { V1_20_2 = 0x04, V1_20_5 = 0x05 },
+        ResourcePackResponse { V1_20_2 = 0x05, V1_20_5 = 0x06 },
+        ServerboundKnownPacks { V1_20_5 = 0x07 }
+    };
+    clientbound {
+        CookieRequest { V1_20_5 = 0x00 },
+        ClientboundPluginMessage { V1_20_2 = 0x00, V1_20_5 = 0x01 },
+        ConfigurationDisconnect { V1_20_2 = 0x01, V1_20_5 = 0x02 },
+        FinishConfiguration { V1_20_2 = 0x02, V1_20_5 = 0x03 },
+        ClientboundKeepAlive { V1_20_2 = 0x03, V1_20_5 = 0x04 },
+        Ping { V1_20_2 = 0x04, V1_20_5 = 0x05 },
+        ResetChat { V1_20_5 = 0x06 },
+        RegistryCodec { V1_20_2 = 0x05, V1_20_5 = _ },
+        RegistryData { V1_20_5 = 0x07 },
+        RemoveResourcePack { V1_20_3 = 0x06, V1_20_5 = 0x08 },
+        AddResourcePack { V1_20_2 = 0x06, V1_20_3 = 0x07, V1_20_5 = 0x09 },
+        StoreCookie { V1_20_5 = 0x0a },
+        Transfer { V1_20_5 = 0x0b },
+        FeatureFlags { V1_20_2 = 0x07, V1_20_3 = 0x08, V1_20_5 = 0x0c },
+        UpdateTags { V1_20_2 = 0x08, V1_20_3 = 0x09, V1_20_5 = 0x0d },
+        ClientboundKnownPacks { V1_20_5 = 0x0e }
+    }
+}
+
+packets! {
+    ClientInformation {
+        locale: String,
+        view_distance: u8,
+        chat_mode: ChatMode,
+        chat_colors: bool,
+        displayed_skin_parts: SkinParts,
+        main_hand: MainHand,
+        enable_text_filtering: bool,
+        allow_server_listings: bool,
+        #[since(V1_21_2)]
+        particle_status: ParticleStatus
+    };
+    CookieResponse {
+        key: Identifier,
+        payload: Option<ByteArray>
+    };
+    ServerboundPluginMessage {
+        channel: Identifier,
+        data: InferredLenByteArray
+    };
+    AcknowledgeFinishConfiguration;
+    ServerboundKeepAlive {
+        id: i64
+    };
+    Pong {
+        id: i32
+    };
+    ResourcePackResponse {
+        #[since(V1_20_3)]
+        uuid: Uuid,
+        result: ResourcePackResult
+    };
+    ServerboundKnownPacks {
+        packs: Vec<KnownPack>
+    };
+
+    CookieRequest {
+        key: Identifier
+    };
+    ClientboundPluginMessage {
+        channel: Identifier,
+        data: InferredLenByteArray
+    };
+    ConfigurationDisconnect {
+        reason: Chat
+    };
+    FinishConfiguration;
+    ClientboundKeepAlive {
+        id: i64
+    };
+    Ping {
+        id: i32
+    };
+    ResetChat;
+    RegistryCodec {
+        codec: RawNbt
+    };
+    RegistryData {
+        registry: Identifier,
+        entries: Vec<RegistryEntry>
+    };
+    RemoveResourcePack {
+        uuid: Option<Uuid>
+    };
+    AddResourcePack {
+        #[since(V1_20_3)]
+        uuid: Uuid,
+        url: String,
+        hash: String,
+        forced: bool,
+        prompt: Option<Chat>
+    };
+    StoreCookie {
+        key: Identifier,
+        payload: ByteArray
+    };
+    Transfer {
+        host: String,
+        port: VarInt
+    };
+    FeatureFlags {
+        flags: Vec<Identifier>
+    };
+    UpdateTags {
+        registries: Vec<RegistryTags>
+    };
+    ClientboundKnownPacks {
+        packs: Vec<KnownPack>
+    }
+}
+
+data! {
+    KnownPack {
+        namespace: String,
+        id: String,
+        version: String
+    };
+    RegistryEntry {
+        id: Identifier,
+        data: Option<RawNbt>
+    };
+    RegistryTags {
+        registry: Identifier,
+        tags: Vec<Tag>
+    };
+    Tag {
+        name: Identifier,
+        entries: Vec<VarInt>
+    }
+}
+
+varint_enum! {
+    ResourcePackResult {
+        SuccessfullyLoaded = 0,
+        Declined = 1,
+        FailedDownload = 2,
+        Accepted = 3,
+        Downloaded = 4,
+        InvalidUrl = 5,
+        FailedToReload = 6,
+        Discarded = 7
+    }
+}
@@ -1,12 +1,13 @@
 use std::{
-    io,
+    fmt, io,
+    str::Utf8Error,
     string::{FromUtf16Error, FromUtf8Error},
 };
 
 use rsa::{errors::Error as RsaError, pkcs8::spki::Error as SpkiError};
 use thiserror::Error;
 
-use crate::{Bound, State};
+use crate::{version::ProtocolVersion, Bound, State};
 
 pub(crate) type SerRes<T> = Result<T, SerializeError>;
 pub(crate) type DeRes<T> = Result<T, DeserializeError>;
@@ -17,19 +18,35 @@ pub enum DeserializeError {
     UnexpectedEof,
     #[error("VarInt too long")]
     VarIntTooLong,
+    #[error("Invalid length {0}")]
+    InvalidLength(i64),
+    #[error("Invalid palette index {0}")]
+    InvalidPaletteIndex(u64),
     #[error("Invalid enum variant {1} for enum {0}")]
     InvalidEnumVariant(&'static str, isize),
     #[error("{0}")]
     FromUtf8Error(#[from] FromUtf8Error),
     #[error("{0}")]
     FromUtf16Error(#[from] FromUtf16Error),
+    #[error("{0}")]
+    Utf8Error(#[from] Utf8Error),
     #[error("Unknown {0} {1} packet with id `0x{2:02x}`")]
     UnknownPacketId(Bound, State, i32),
+    #[error("The {0} state is not supported in protocol version {1}")]
+    UnsupportedVersion(State, ProtocolVersion),
+    #[error("{0} cannot be decoded in protocol version {1}")]
+    UnsupportedValue(&'static str, ProtocolVersion),
     #[error("Broken packet")]
     BrokenPacket,
+    #[error("Invalid command graph: {0}")]
+    InvalidCommandGraph(&'static str),
     #[error("{0}")]
     JsonError(serde_json::Error),
     #[error("{0}")]
+    InvalidIdentifier(#[from] IdentifierError),
+    #[error("{0}")]
+    UnknownEntry(#[from] UnknownEntryError),
+    #[error("{0}")]
     SpkiError(#[from] SpkiError),
     #[error("Invalid shared secret length")]
     InvalidSharedSecretLength,
@@ -38,13 +55,25 @@ pub enum DeserializeError {
     #[error("{0}")]
     ZlibError(String),
     #[error("{0}")]
-    NbtError(#[from] nbt::Error),
+    NbtError(#[from] NbtError),
     #[error("{0}")]
     IoError(#[from] io::Error),
 }
 
 #[derive(Error, Debug)]
 pub enum SerializeError {
+    #[error("Length {0} does not fit into the length prefix")]
+    LengthTooLarge(usize),
+    #[error("Packet {0} does not exist in protocol version {1}")]
+    UnsupportedPacket(&'static str, ProtocolVersion),
+    #[error("{0} cannot be encoded in protocol version {1}")]
+    UnsupportedValue(&'static str, ProtocolVersion),
+    #[error("Packet {0} is not part of the schema")]
+    UnknownPacket(String),
+    #[error("Expected a value of type {0}")]
+    TypeMismatch(&'static str),
+    #[error("Missing field `{0}`")]
+    MissingField(String),
     #[error("Expected RSA public key to have a size of 1024 bits, got {0} bits")]
     UnexpectedPublicKeySize(usize),
     #[error("Failed to encode RSA public key {0}")]
@@ -52,7 +81,162 @@ pub enum SerializeError {
     #[error("{0}")]
     RsaError(#[from] RsaError),
     #[error("{0}")]
-    NbtError(#[from] nbt::Error),
+    NbtError(#[from] NbtError),
     #[error("{0}")]
     IoError(#[from] io::Error),
 }
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NbtError {
+    #[error("{0}")]
+    Custom(String),
+    #[error("Invalid NBT tag type {0}")]
+    InvalidTag(u8),
+    #[error("Unexpected TAG_End")]
+    UnexpectedEnd,
+    #[error("Expected a compound tag")]
+    ExpectedCompound,
+    #[error("NBT list elements must all have the same type")]
+    MixedList,
+    #[error("NBT is nested deeper than {0} levels")]
+    TooDeep(usize),
+    #[error("NBT takes up more than {0} bytes")]
+    TooLarge(usize),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{0} at position {1}")]
+pub struct SnbtError(pub &'static str, pub usize);
+
+impl serde::ser::Error for NbtError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl serde::de::Error for NbtError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierError {
+    #[error("Invalid identifier namespace `{0}`")]
+    InvalidNamespace(String),
+    #[error("Invalid identifier path `{0}`")]
+    InvalidPath(String),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown {0} entry `{1}`")]
+pub struct UnknownEntryError(pub &'static str, pub String);
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BlockStateError {
+    #[error("Invalid block state `{0}`")]
+    Invalid(String),
+    #[error("{0}")]
+    UnknownBlock(#[from] UnknownEntryError),
+    #[error("Block {0} has no property `{1}`")]
+    UnknownProperty(String, String),
+    #[error("Invalid value `{1}` for property `{0}`")]
+    InvalidValue(String, String),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown Minecraft version `{0}`")]
+pub struct UnknownVersionError(pub String);
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    #[error("integer")]
+    Integer,
+    #[error("long")]
+    Long,
+    #[error("float")]
+    Float,
+    #[error("double")]
+    Double,
+}
+
+impl NumberKind {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Integer => "Integer",
+            Self::Long => "Long",
+            Self::Float => "Float",
+            Self::Double => "Double",
+        }
+    }
+}
+
+/// Why a command failed to parse, with the English messages of vanilla.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("Unknown or incomplete command, see below for error")]
+    UnknownCommand,
+    #[error("Incorrect argument for command")]
+    UnknownArgument,
+    #[error("Expected whitespace to end one argument, but found trailing data")]
+    ExpectedSeparator,
+    #[error("Expected boolean")]
+    ExpectedBool,
+    #[error("Invalid boolean, expected 'true' or 'false' but found '{0}'")]
+    InvalidBool(String),
+    #[error("Expected {0}")]
+    ExpectedNumber(NumberKind),
+    #[error("Invalid {0} '{1}'")]
+    InvalidNumber(NumberKind, String),
+    #[error("{} must not be less than {1}, found {2}", .0.title())]
+    NumberTooLow(NumberKind, String, String),
+    #[error("{} must not be more than {1}, found {2}", .0.title())]
+    NumberTooHigh(NumberKind, String, String),
+    #[error("Expected quote to start a string")]
+    ExpectedStartOfQuote,
+    #[error("Unclosed quoted string")]
+    ExpectedEndOfQuote,
+    #[error("Invalid escape sequence '\\{0}' in quoted string")]
+    InvalidEscape(char),
+    #[error("Expected a coordinate")]
+    ExpectedCoordinate,
+    #[error("Expected a block position")]
+    ExpectedBlockPosition,
+    #[error("Incomplete (expected {0} coordinates)")]
+    IncompleteCoordinates(usize),
+    #[error("Cannot mix world & local coordinates (everything must either use ^ or not)")]
+    MixedCoordinates,
+    #[error("Invalid name or UUID")]
+    InvalidEntity,
+    #[error("Missing selector type")]
+    MissingSelector,
+    #[error("Unknown selector type '{0}'")]
+    UnknownSelector(String),
+    #[error("Expected end of options")]
+    UnterminatedOptions,
+    #[error("Unknown option '{0}'")]
+    UnknownOption(String),
+    #[error("Expected value for option '{0}'")]
+    ValuelessOption(String),
+    #[error("Only one entity is allowed, but the provided selector allows more than one")]
+    TooManyEntities,
+    #[error("Only one player is allowed, but the provided selector allows more than one")]
+    TooManyPlayers,
+    #[error(
+        "Only players may be affected by this command, but the provided selector includes entities"
+    )]
+    OnlyPlayers,
+    #[error("Invalid ID")]
+    InvalidId,
+    #[error("Invalid UUID")]
+    InvalidUuid,
+}
+
+/// A [`CommandError`] along with where in the input it happened, as a byte offset.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{error} at position {}: {}", self.position(), self.context())]
+pub struct CommandSyntaxError {
+    pub error: CommandError,
+    pub input: String,
+    pub cursor: usize,
+}
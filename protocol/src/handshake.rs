@@ -1,7 +1,7 @@
-use crate::{packet, types::varint::VarInt, varint_enum};
+use crate::{packet, types::varint::VarInt, varint_enum, version::ProtocolVersion};
 
 packet! {
-    Handshake(0x00) {
+    Handshake {
         version: VarInt,
         address: String,
         port: u16,
@@ -9,9 +9,16 @@ packet! {
     }
 }
 
+impl Handshake {
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.version.into()
+    }
+}
+
 varint_enum! {
     NextState {
         Status = 1,
-        Login = 2
+        Login = 2,
+        Transfer = 3
     }
 }
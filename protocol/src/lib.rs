@@ -3,13 +3,20 @@ pub(crate) use macros::*;
 
 pub mod chat;
 pub mod codec;
+pub mod configuration;
 pub mod error;
 pub mod handshake;
 pub mod legacy;
+pub mod light;
 pub mod login;
+pub mod nbt;
+pub mod packet_ids;
 pub mod play;
+pub mod registry;
+pub mod schema;
 pub mod status;
 pub mod types;
+pub mod version;
 
 use std::{
     fmt,
@@ -17,26 +24,36 @@ use std::{
 };
 
 use aes::cipher::{BlockDecryptMut, BlockEncryptMut};
-use bytes::{Buf, BufMut, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use configuration::{AcknowledgeFinishConfiguration, Configuration};
 use error::{DeRes, DeserializeError, SerRes};
 use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
-use handshake::Handshake;
+use handshake::{Handshake, NextState};
 use login::Login;
 use play::Play;
+use serde::Deserialize;
 use status::Status;
 use types::{
     varint::{read_encrypted_varint, VarInt},
-    Serialize,
+    Decode, Serialize,
 };
+use version::ProtocolVersion;
 
 pub enum Protocol {
-    Handshake(Handshake),
+    /// Boxed, so that it is built from a [`Handshake`] with `into` like the packets of the other
+    /// states.
+    Handshake(Box<Handshake>),
     Status(Status),
     Login(Login),
+    Configuration(Configuration),
     Play(Play),
 }
 
+/// The compression set up by the Set Compression packet.
+///
+/// More settings may be added, so start from [`PacketCompression::default`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct PacketCompression {
     pub enabled: bool,
     pub threshold: usize,
@@ -46,16 +63,46 @@ pub type Encryptor = cfb8::Encryptor<aes::Aes128>;
 pub type Decryptor = cfb8::Decryptor<aes::Aes128>;
 
 impl Protocol {
+    /// Returns the state the connection switches to once this packet has been sent, or `None`
+    /// if it stays in the current one.
+    pub fn next_state(&self, version: ProtocolVersion) -> Option<State> {
+        match self {
+            Protocol::Handshake(handshake) => Some(match handshake.next_state {
+                NextState::Status => State::Status,
+                NextState::Login | NextState::Transfer => State::Login,
+            }),
+            Protocol::Login(Login::LoginSuccess(_)) if version.until(ProtocolVersion::V1_20_2) => {
+                Some(State::Play)
+            }
+            Protocol::Login(Login::LoginAcknowledged(_)) => Some(State::Configuration),
+            Protocol::Configuration(Configuration::AcknowledgeFinishConfiguration(
+                AcknowledgeFinishConfiguration,
+            )) => Some(State::Play),
+            Protocol::Play(Play::ConfigurationAcknowledged(_)) => Some(State::Configuration),
+            _ => None,
+        }
+    }
+
     pub fn serialize(
         &self,
         compression: PacketCompression,
         encryptor: Option<&mut Encryptor>,
+    ) -> SerRes<Vec<u8>> {
+        self.serialize_with_version(ProtocolVersion::default(), compression, encryptor)
+    }
+
+    pub fn serialize_with_version(
+        &self,
+        version: ProtocolVersion,
+        compression: PacketCompression,
+        encryptor: Option<&mut Encryptor>,
     ) -> SerRes<Vec<u8>> {
         let (id, data) = match self {
-            Protocol::Handshake(handshake) => handshake.serialize()?,
-            Protocol::Status(status) => status.serialize()?,
-            Protocol::Login(login) => login.serialize()?,
-            Protocol::Play(play) => play.serialize()?,
+            Protocol::Handshake(handshake) => (0x00, handshake.serialize(version)?),
+            Protocol::Status(status) => status.serialize(version)?,
+            Protocol::Login(login) => login.serialize(version)?,
+            Protocol::Configuration(configuration) => configuration.serialize(version)?,
+            Protocol::Play(play) => play.serialize(version)?,
         };
 
         let mut packet = BytesMut::new();
@@ -101,6 +148,24 @@ impl Protocol {
         compression: PacketCompression,
         decryptor: Option<&mut Decryptor>,
         packet: &mut BytesMut,
+    ) -> DeRes<Self> {
+        Self::deserialize_with_version(
+            bound,
+            state,
+            ProtocolVersion::default(),
+            compression,
+            decryptor,
+            packet,
+        )
+    }
+
+    pub fn deserialize_with_version(
+        bound: Bound,
+        state: State,
+        version: ProtocolVersion,
+        compression: PacketCompression,
+        decryptor: Option<&mut Decryptor>,
+        packet: &mut BytesMut,
     ) -> DeRes<Self> {
         let mut bytes = if let Some(decryptor) = decryptor {
             let len = read_encrypted_varint(packet, decryptor)? as usize;
@@ -116,7 +181,7 @@ impl Protocol {
 
             decryptor.decrypt_blocks_mut(&mut buf);
 
-            BytesMut::from_iter(buf.into_iter().flatten())
+            buf.into_iter().flatten().collect::<Vec<u8>>().into()
         } else {
             let len = VarInt::deserialize(packet)?.0 as usize;
 
@@ -125,11 +190,11 @@ impl Protocol {
                 return Err(DeserializeError::UnexpectedEof);
             }
 
-            packet.split_to(len)
+            packet.split_to(len).freeze()
         };
 
         if compression.enabled {
-            let data_len = VarInt::deserialize(&mut bytes)?.0 as usize;
+            let data_len = VarInt::decode(&mut bytes)?.0 as usize;
 
             if data_len != 0 {
                 let compressed = &bytes[..];
@@ -148,25 +213,30 @@ impl Protocol {
                     }
                 }
 
-                bytes = BytesMut::from(vec.as_slice());
+                bytes = Bytes::from(vec);
             }
         }
 
-        let id = VarInt::deserialize(&mut bytes)?.0;
+        let id = VarInt::decode(&mut bytes)?.0;
 
         match state {
             State::Handshake => match (bound, id) {
-                (Bound::Serverbound, 0) => Ok(Self::Handshake(Handshake::deserialize(&mut bytes)?)),
+                (Bound::Serverbound, 0x00) => Handshake::deserialize(version, &mut bytes)
+                    .map(|handshake| Self::Handshake(handshake.into())),
                 (_, _) => Err(DeserializeError::UnknownPacketId(bound, state, id)),
             },
-            State::Status => Status::deserialize(bound, id, &mut bytes).map(Self::Status),
-            State::Login => Login::deserialize(bound, id, &mut bytes).map(Self::Login),
-            State::Play => Play::deserialize(bound, id, &mut bytes).map(Self::Play),
+            State::Status => Status::deserialize(bound, version, id, &mut bytes).map(Self::Status),
+            State::Login => Login::deserialize(bound, version, id, &mut bytes).map(Self::Login),
+            State::Configuration => {
+                Configuration::deserialize(bound, version, id, &mut bytes).map(Self::Configuration)
+            }
+            State::Play => Play::deserialize(bound, version, id, &mut bytes).map(Self::Play),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Bound {
     Serverbound,
     Clientbound,
@@ -181,11 +251,13 @@ impl fmt::Display for Bound {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum State {
     Handshake,
     Status,
     Login,
+    Configuration,
     Play,
 }
 
@@ -195,6 +267,7 @@ impl fmt::Display for State {
             State::Handshake => "handshake",
             State::Status => "status",
             State::Login => "login",
+            State::Configuration => "configuration",
             State::Play => "play",
         };
 
@@ -0,0 +1,421 @@
+//! Sky and block light of chunk sections, and an engine which computes it.
+
+use std::collections::{HashMap, VecDeque};
+
+use bytes::{Buf, BufMut};
+
+use crate::{
+    error::{DeRes, DeserializeError, SerRes},
+    play::chunk::Section,
+    types::{
+        bit_set::BitSet, length_prefixed::LengthPrefix, varint::VarInt, Context, Decode, Encode,
+        PaletteKind,
+    },
+    version::ProtocolVersion,
+};
+
+/// The light levels of a section, two to a byte with the lower nibble first, in the same order
+/// as block states.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NibbleArray(Vec<u8>);
+
+impl Default for NibbleArray {
+    fn default() -> Self {
+        Self::filled(0)
+    }
+}
+
+impl NibbleArray {
+    /// The number of bytes in an array.
+    pub const LEN: usize = 2048;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filled(level: u8) -> Self {
+        Self(vec![(level & 15) * 0x11; Self::LEN])
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        (bytes.len() == Self::LEN).then_some(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> u8 {
+        let index = PaletteKind::BlockStates.index(x, y, z);
+
+        self.0[index / 2] >> (index % 2 * 4) & 15
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, level: u8) {
+        let index = PaletteKind::BlockStates.index(x, y, z);
+        let shift = index % 2 * 4;
+        let byte = &mut self.0[index / 2];
+
+        *byte = *byte & !(15 << shift) | (level & 15) << shift;
+    }
+
+    /// Whether every level is 0.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|byte| *byte == 0)
+    }
+}
+
+impl Encode for NibbleArray {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        VarInt::from_len(Self::LEN)?.encode(buf)?;
+        buf.put_slice(&self.0);
+
+        Ok(())
+    }
+}
+
+impl Decode for NibbleArray {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let len = VarInt::decode(buf)?.to_len()?;
+
+        if len != Self::LEN {
+            return Err(DeserializeError::InvalidLength(len as i64));
+        }
+
+        if buf.remaining() < len {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+
+        let mut bytes = vec![0; len];
+        buf.copy_to_slice(&mut bytes);
+
+        Ok(Self(bytes))
+    }
+}
+
+/// The light of a chunk column, shared by the Chunk Data and Update Light packets.
+///
+/// Light sections start one section below the world, so bit `i` of a mask stands for section
+/// `i - 1`. There is an array for each section set in the matching mask, in ascending order,
+/// while sections in the empty masks are dark.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LightData {
+    /// Not sent since 1.20, where edges are always trusted.
+    pub trust_edges: bool,
+    pub sky_light_mask: BitSet,
+    pub block_light_mask: BitSet,
+    pub empty_sky_light_mask: BitSet,
+    pub empty_block_light_mask: BitSet,
+    pub sky_light: Vec<NibbleArray>,
+    pub block_light: Vec<NibbleArray>,
+}
+
+impl LightData {
+    /// Builds the masks from the sky and block light of every light section, from the one below
+    /// the world up.
+    pub fn from_sections(sky: &[NibbleArray], block: &[NibbleArray]) -> Self {
+        let mut light = Self {
+            trust_edges: true,
+            ..Default::default()
+        };
+
+        for (i, array) in sky.iter().enumerate() {
+            if array.is_empty() {
+                light.empty_sky_light_mask.set(i, true);
+            } else {
+                light.sky_light_mask.set(i, true);
+                light.sky_light.push(array.clone());
+            }
+        }
+
+        for (i, array) in block.iter().enumerate() {
+            if array.is_empty() {
+                light.empty_block_light_mask.set(i, true);
+            } else {
+                light.block_light_mask.set(i, true);
+                light.block_light.push(array.clone());
+            }
+        }
+
+        light
+    }
+}
+
+impl Encode for LightData {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if cx.version.until(ProtocolVersion::V1_20) {
+            self.trust_edges.encode(buf)?;
+        }
+
+        self.sky_light_mask.encode_with(buf, cx)?;
+        self.block_light_mask.encode_with(buf, cx)?;
+        self.empty_sky_light_mask.encode_with(buf, cx)?;
+        self.empty_block_light_mask.encode_with(buf, cx)?;
+        self.sky_light.encode_with(buf, cx)?;
+        self.block_light.encode_with(buf, cx)
+    }
+}
+
+impl Decode for LightData {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let trust_edges = cx.version.since(ProtocolVersion::V1_20) || bool::decode(buf)?;
+
+        Ok(Self {
+            trust_edges,
+            sky_light_mask: BitSet::decode_with(buf, cx)?,
+            block_light_mask: BitSet::decode_with(buf, cx)?,
+            empty_sky_light_mask: BitSet::decode_with(buf, cx)?,
+            empty_block_light_mask: BitSet::decode_with(buf, cx)?,
+            sky_light: Vec::decode_with(buf, cx)?,
+            block_light: Vec::decode_with(buf, cx)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LightKind {
+    Sky,
+    Block,
+}
+
+const DIRECTIONS: [(i32, i32, i32); 6] = [
+    (0, -1, 0),
+    (0, 1, 0),
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// The chunk coordinates of a column, the index of a section in it and the coordinates in the
+/// section.
+type Location = ((i32, i32), usize, usize, usize, usize);
+
+#[derive(Debug, Clone)]
+struct Column {
+    sections: Vec<Section>,
+    sky: Vec<NibbleArray>,
+    block: Vec<NibbleArray>,
+}
+
+/// Computes the sky and block light of loaded chunk columns.
+///
+/// Block light spreads from emitters and sky light falls from above the world, losing at least
+/// one level per block and more through translucent blocks, until it reaches an opaque block.
+/// Light crosses into neighbouring columns only if they are loaded too. Emission and opacity are
+/// those of the default state of each block.
+///
+/// Coordinates are in blocks, with `y` counted from the bottom of the world.
+#[derive(Debug, Clone, Default)]
+pub struct LightEngine {
+    height: usize,
+    columns: HashMap<(i32, i32), Column>,
+}
+
+impl LightEngine {
+    /// Creates an engine for a world `sections` sections high.
+    pub fn new(sections: usize) -> Self {
+        Self {
+            height: sections * 16,
+            columns: HashMap::new(),
+        }
+    }
+
+    /// Loads the column at chunk coordinates `x` and `z`, replacing the one there.
+    ///
+    /// Its light is dark until [`LightEngine::compute`] is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of sections does not match the height of the world.
+    pub fn insert(&mut self, x: i32, z: i32, sections: Vec<Section>) {
+        assert_eq!(sections.len() * 16, self.height, "wrong number of sections");
+
+        let len = sections.len();
+
+        self.columns.insert(
+            (x, z),
+            Column {
+                sections,
+                sky: vec![NibbleArray::new(); len],
+                block: vec![NibbleArray::new(); len],
+            },
+        );
+    }
+
+    pub fn remove(&mut self, x: i32, z: i32) -> Option<Vec<Section>> {
+        self.columns.remove(&(x, z)).map(|column| column.sections)
+    }
+
+    /// Recomputes the light of all loaded columns.
+    pub fn compute(&mut self) {
+        let mut sky = VecDeque::new();
+        let mut block = VecDeque::new();
+
+        for (&(cx, cz), column) in &mut self.columns {
+            column.sky.fill(NibbleArray::new());
+            column.block.fill(NibbleArray::new());
+
+            for (i, section) in column.sections.iter().enumerate() {
+                for (x, y, z) in section_positions() {
+                    let emission = section.block(x, y, z).block().light_emission();
+
+                    if emission > 0 {
+                        column.block[i].set(x, y, z, emission);
+                        block.push_back(world_position(cx, cz, i, x, y, z));
+                    }
+                }
+            }
+
+            // Full sky light falls straight down until it hits a block which dims it.
+            for (x, z) in (0..16).flat_map(|x| (0..16).map(move |z| (x, z))) {
+                for y in (0..self.height).rev() {
+                    let (i, ly) = (y / 16, y % 16);
+                    let opacity = column.sections[i].block(x, ly, z).block().light_opacity();
+                    let level = 15u8.saturating_sub(opacity);
+
+                    column.sky[i].set(x, ly, z, level);
+
+                    if level > 0 {
+                        sky.push_back(world_position(cx, cz, i, x, ly, z));
+                    }
+
+                    if level < 15 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.spread(LightKind::Block, block);
+        self.spread(LightKind::Sky, sky);
+    }
+
+    /// The sky light at a position, which is full above the world and dark in unloaded columns.
+    pub fn sky_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        if y >= self.height as i32 {
+            return 15;
+        }
+
+        self.level(LightKind::Sky, x, y, z).unwrap_or(0)
+    }
+
+    pub fn block_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.level(LightKind::Block, x, y, z).unwrap_or(0)
+    }
+
+    /// The light of a loaded column, for the sections below and above the world too.
+    pub fn light_data(&self, x: i32, z: i32) -> Option<LightData> {
+        let column = self.columns.get(&(x, z))?;
+
+        let mut sky = vec![NibbleArray::new()];
+        sky.extend(column.sky.iter().cloned());
+        sky.push(NibbleArray::filled(15));
+
+        let mut block = vec![NibbleArray::new()];
+        block.extend(column.block.iter().cloned());
+        block.push(NibbleArray::new());
+
+        Some(LightData::from_sections(&sky, &block))
+    }
+
+    fn spread(&mut self, kind: LightKind, mut queue: VecDeque<(i32, i32, i32)>) {
+        while let Some((x, y, z)) = queue.pop_front() {
+            let Some(level) = self.level(kind, x, y, z) else {
+                continue;
+            };
+
+            for (dx, dy, dz) in DIRECTIONS {
+                let (nx, ny, nz) = (x + dx, y + dy, z + dz);
+
+                let Some(opacity) = self.opacity(nx, ny, nz) else {
+                    continue;
+                };
+
+                let spread = level.saturating_sub(opacity.max(1));
+
+                if spread > self.level(kind, nx, ny, nz).unwrap_or(15) {
+                    self.set_level(kind, nx, ny, nz, spread);
+                    queue.push_back((nx, ny, nz));
+                }
+            }
+        }
+    }
+
+    /// Splits a position into its column, section index and section-relative coordinates.
+    fn locate(&self, x: i32, y: i32, z: i32) -> Option<Location> {
+        if y < 0 || y >= self.height as i32 {
+            return None;
+        }
+
+        Some((
+            (x.div_euclid(16), z.div_euclid(16)),
+            y as usize / 16,
+            x.rem_euclid(16) as usize,
+            y as usize % 16,
+            z.rem_euclid(16) as usize,
+        ))
+    }
+
+    fn opacity(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        let (key, i, x, y, z) = self.locate(x, y, z)?;
+        let column = self.columns.get(&key)?;
+
+        Some(column.sections[i].block(x, y, z).block().light_opacity())
+    }
+
+    fn level(&self, kind: LightKind, x: i32, y: i32, z: i32) -> Option<u8> {
+        let (key, i, x, y, z) = self.locate(x, y, z)?;
+        let column = self.columns.get(&key)?;
+
+        let arrays = match kind {
+            LightKind::Sky => &column.sky,
+            LightKind::Block => &column.block,
+        };
+
+        Some(arrays[i].get(x, y, z))
+    }
+
+    fn set_level(&mut self, kind: LightKind, x: i32, y: i32, z: i32, level: u8) {
+        let Some((key, i, x, y, z)) = self.locate(x, y, z) else {
+            return;
+        };
+        let Some(column) = self.columns.get_mut(&key) else {
+            return;
+        };
+
+        let arrays = match kind {
+            LightKind::Sky => &mut column.sky,
+            LightKind::Block => &mut column.block,
+        };
+
+        arrays[i].set(x, y, z, level);
+    }
+}
+
+fn section_positions() -> impl Iterator<Item = (usize, usize, usize)> {
+    (0..16).flat_map(|y| (0..16).flat_map(move |z| (0..16).map(move |x| (x, y, z))))
+}
+
+fn world_position(
+    cx: i32,
+    cz: i32,
+    section: usize,
+    x: usize,
+    y: usize,
+    z: usize,
+) -> (i32, i32, i32) {
+    (
+        cx * 16 + x as i32,
+        (section * 16 + y) as i32,
+        cz * 16 + z as i32,
+    )
+}
@@ -1,86 +1,110 @@
+use bytes::{Buf, BufMut};
 use rsa::{PaddingScheme, PublicKey, RsaPrivateKey, RsaPublicKey};
 use uuid::Uuid;
 
 use crate::{
-    chat::Chat,
+    chat::JsonChat,
     data,
-    error::{DeRes, DeserializeError, SerRes},
+    error::{DeRes, DeserializeError, SerRes, SerializeError},
     packets, state,
-    types::{varint::VarInt, Either},
+    types::{identifier::Identifier, varint::VarInt, ByteArray, Context, Decode, Encode},
+    version::ProtocolVersion,
 };
 
 state! {
-    Login;
+    // Left open so that clients newer than the reports can still be sent a disconnect.
+    Login(V1_18..);
     serverbound {
-        0x00 => LoginStart,
-        0x01 => EncryptionResponse,
-        0x02 => LoginPluginResponse
+        LoginStart { V1_18 = 0x00 },
+        EncryptionResponse { V1_18 = 0x01 },
+        LoginPluginResponse { V1_18 = 0x02 },
+        LoginAcknowledged { V1_20_2 = 0x03 },
+        LoginCookieResponse { V1_20_5 = 0x04 }
     };
     clientbound {
-        0x00 => LoginDisconnect,
-        0x01 => EncryptionRequest,
-        0x02 => LoginSuccess,
-        0x03 => SetCompression,
-        0x04 => LoginPluginRequest
+        LoginDisconnect { V1_18 = 0x00 },
+        EncryptionRequest { V1_18 = 0x01 },
+        LoginSuccess { V1_18 = 0x02 },
+        SetCompression { V1_18 = 0x03 },
+        LoginPluginRequest { V1_18 = 0x04 },
+        LoginCookieRequest { V1_20_5 = 0x05 }
     }
 }
 
 packets! {
-    LoginStart(0x00) {
+    LoginStart {
         name: String,
-        sig_data: Option<SigData>
+        #[since(V1_19)]
+        #[until(V1_19_3)]
+        sig_data: Option<SigData>,
+        #[since(V1_19_1)]
+        uuid: LoginUuid
     };
-    EncryptionResponse(0x01) {
+    EncryptionResponse {
         shared_secret: SharedSecret,
-        verify: Either<EncryptedVerifyToken, SaltSignature>
+        verify: EncryptionVerify
     };
-    LoginPluginResponse(0x02) {
+    LoginPluginResponse {
         message_id: VarInt,
-        data: Option<Vec<u8>>
+        data: Option<ByteArray>
+    };
+    LoginAcknowledged;
+    LoginCookieResponse {
+        key: Identifier,
+        payload: Option<ByteArray>
     };
 
-    LoginDisconnect(0x00) {
-        reason: Chat
+    LoginDisconnect {
+        reason: JsonChat
     };
-    EncryptionRequest(0x01) {
+    EncryptionRequest {
         server_id: String,
         public_key: RsaPublicKey,
-        verify_token: VerifyToken
+        verify_token: VerifyToken,
+        #[since(V1_20_5)]
+        should_authenticate: bool
     };
-    LoginSuccess(0x02) {
+    LoginSuccess {
         uuid: Uuid,
         username: String,
-        properties: Vec<LoginSuccessProperty>
+        #[since(V1_19)]
+        properties: Vec<LoginSuccessProperty>,
+        #[since(V1_20_5)]
+        #[until(V1_21_2)]
+        strict_error_handling: bool
     };
-    SetCompression(0x03) {
+    SetCompression {
         threshold: VarInt
     };
-    LoginPluginRequest(0x04) {
+    LoginPluginRequest {
         message_id: VarInt,
-        channel: String,
-        data: Vec<u8>
+        channel: Identifier,
+        data: ByteArray
+    };
+    LoginCookieRequest {
+        key: Identifier
     }
 }
 
 data! {
     SigData {
         timestamp: i64,
-        public_key: Vec<u8>,
-        signature: Vec<u8>
+        public_key: ByteArray,
+        signature: ByteArray
     };
     SharedSecret {
-        encrypted_secret: Vec<u8>
+        encrypted_secret: ByteArray
     };
     EncryptedVerifyToken {
-        encrypted_token: Vec<u8>
+        encrypted_token: ByteArray
     };
     SaltSignature {
         salt: i64,
-        signature: Vec<u8>
+        signature: ByteArray
     };
 
     VerifyToken {
-        verify_token: Vec<u8>
+        verify_token: ByteArray
     };
     LoginSuccessProperty {
         name: String,
@@ -89,6 +113,113 @@ data! {
     }
 }
 
+/// The uuid of the player logging in.
+///
+/// It is optional until 1.20.2, which always sends it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoginUuid(pub Option<Uuid>);
+
+impl From<Option<Uuid>> for LoginUuid {
+    fn from(uuid: Option<Uuid>) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<Uuid> for LoginUuid {
+    fn from(uuid: Uuid) -> Self {
+        Self(Some(uuid))
+    }
+}
+
+impl Encode for LoginUuid {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if cx.version.until(ProtocolVersion::V1_20_2) {
+            return self.0.encode(buf);
+        }
+
+        self.0
+            .ok_or(SerializeError::UnsupportedValue(
+                "Missing login uuid",
+                cx.version,
+            ))?
+            .encode(buf)
+    }
+}
+
+impl Decode for LoginUuid {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if cx.version.until(ProtocolVersion::V1_20_2) {
+            Option::decode(buf).map(Self)
+        } else {
+            Uuid::decode(buf).map(|uuid| Self(Some(uuid)))
+        }
+    }
+}
+
+/// Proves to the server that the client could decrypt the verify token.
+///
+/// From 1.19 to 1.19.2 clients with a chat signing key send a signature instead of the token.
+#[derive(Debug, Clone)]
+pub enum EncryptionVerify {
+    Token(EncryptedVerifyToken),
+    Signature(SaltSignature),
+}
+
+fn has_signature(cx: &Context) -> bool {
+    cx.version.since(ProtocolVersion::V1_19) && cx.version.until(ProtocolVersion::V1_19_3)
+}
+
+impl Encode for EncryptionVerify {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match self {
+            EncryptionVerify::Token(token) => {
+                if has_signature(cx) {
+                    true.encode(buf)?;
+                }
+
+                token.encode(buf)
+            }
+            EncryptionVerify::Signature(signature) => {
+                if !has_signature(cx) {
+                    return Err(SerializeError::UnsupportedValue(
+                        "SaltSignature",
+                        cx.version,
+                    ));
+                }
+
+                false.encode(buf)?;
+                signature.encode(buf)
+            }
+        }
+    }
+}
+
+impl Decode for EncryptionVerify {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if !has_signature(cx) || bool::decode(buf)? {
+            EncryptedVerifyToken::decode(buf).map(Self::Token)
+        } else {
+            SaltSignature::decode(buf).map(Self::Signature)
+        }
+    }
+}
+
 impl SharedSecret {
     pub fn encrypt(secret: &[u8; 16], public_key: RsaPublicKey) -> SerRes<Self> {
         let mut rng = rand::thread_rng();
@@ -96,7 +227,9 @@ impl SharedSecret {
         let encrypted_secret =
             public_key.encrypt(&mut rng, PaddingScheme::PKCS1v15Encrypt, secret)?;
 
-        Ok(Self { encrypted_secret })
+        Ok(Self {
+            encrypted_secret: encrypted_secret.into(),
+        })
     }
 
     pub fn decrypt(&self, private_key: RsaPrivateKey) -> DeRes<[u8; 16]> {
@@ -115,7 +248,9 @@ impl EncryptedVerifyToken {
         let encrypted_token =
             public_key.encrypt(&mut rng, PaddingScheme::PKCS1v15Encrypt, verify_token)?;
 
-        Ok(Self { encrypted_token })
+        Ok(Self {
+            encrypted_token: encrypted_token.into(),
+        })
     }
 
     pub fn decrypt(&self, private_key: RsaPrivateKey) -> DeRes<Vec<u8>> {
@@ -1,33 +1,108 @@
 macro_rules! state {
-    ($name:ident; serverbound { $($sbid:literal => $sbpacket:ident),* }; clientbound { $($cbid:literal => $cbpacket:ident),* } ) => {
+    (
+        $name:ident($min:ident $(..=$last:ident)? $(..)?);
+        serverbound { $($sbpacket:ident $sbids:tt),* };
+        clientbound { $($cbpacket:ident $cbids:tt),* }
+    ) => {
         pub enum $name {
             $($sbpacket($sbpacket),)*
             $($cbpacket($cbpacket),)*
         }
 
+        $(impl $sbpacket {
+            /// The packet ids of this packet, keyed by the version they were introduced in.
+            pub const IDS: &'static [($crate::version::ProtocolVersion, Option<i32>)] =
+                $crate::packet_ids!($sbids);
+
+            /// Returns the id of this packet in `version`, or `None` if it can't be sent there.
+            pub fn packet_id(version: $crate::version::ProtocolVersion) -> Option<i32> {
+                if !$crate::packet_versions!($sbids, version) {
+                    return None;
+                }
+
+                $crate::version::packet_id(Self::IDS, version)
+            }
+        })*
+        $(impl $cbpacket {
+            /// The packet ids of this packet, keyed by the version they were introduced in.
+            pub const IDS: &'static [($crate::version::ProtocolVersion, Option<i32>)] =
+                $crate::packet_ids!($cbids);
+
+            /// Returns the id of this packet in `version`, or `None` if it can't be sent there.
+            pub fn packet_id(version: $crate::version::ProtocolVersion) -> Option<i32> {
+                if !$crate::packet_versions!($cbids, version) {
+                    return None;
+                }
+
+                $crate::version::packet_id(Self::IDS, version)
+            }
+        })*
+
         impl $name {
-            pub fn serialize(&self) -> $crate::error::SerRes<(i32, Vec<u8>)> {
+            /// Returns `true` if packets of this state can be encoded and decoded for `version`.
+            pub fn supports(version: $crate::version::ProtocolVersion) -> bool {
+                version.since($crate::version::ProtocolVersion::$min)
+                    $(&& version <= $crate::version::ProtocolVersion::$last)?
+            }
+
+            pub fn name(&self) -> &'static str {
                 match self {
-                    $(Self::$sbpacket(variant) => variant.serialize(),)*
-                    $(Self::$cbpacket(variant) => variant.serialize(),)*
+                    $(Self::$sbpacket(_) => stringify!($sbpacket),)*
+                    $(Self::$cbpacket(_) => stringify!($cbpacket),)*
                 }
             }
 
-            pub fn deserialize(bound: $crate::Bound, id: i32, bytes: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
+            /// Returns the id of this packet in `version`, or `None` if it does not exist there.
+            pub fn packet_id(&self, version: $crate::version::ProtocolVersion) -> Option<i32> {
+                if !Self::supports(version) {
+                    return None;
+                }
+
+                match self {
+                    $(Self::$sbpacket(_) => $sbpacket::packet_id(version),)*
+                    $(Self::$cbpacket(_) => $cbpacket::packet_id(version),)*
+                }
+            }
+
+            pub fn serialize(&self, version: $crate::version::ProtocolVersion) -> $crate::error::SerRes<(i32, Vec<u8>)> {
+                let id = self
+                    .packet_id(version)
+                    .ok_or_else(|| $crate::error::SerializeError::UnsupportedPacket(self.name(), version))?;
+
+                let data = match self {
+                    $(Self::$sbpacket(variant) => variant.serialize(version)?,)*
+                    $(Self::$cbpacket(variant) => variant.serialize(version)?,)*
+                };
+
+                Ok((id, data))
+            }
+
+            pub fn deserialize<B: bytes::Buf>(
+                bound: $crate::Bound,
+                version: $crate::version::ProtocolVersion,
+                id: i32,
+                bytes: &mut B,
+            ) -> $crate::error::DeRes<Self> {
+                use $crate::{error::DeserializeError, Bound, State};
+
+                if !Self::supports(version) {
+                    return Err(DeserializeError::UnsupportedVersion(State::$name, version));
+                }
+
                 match bound {
-                    $crate::Bound::Serverbound => {
-                        match id {
-                            $($sbid => Ok(Self::$sbpacket(<$sbpacket>::deserialize(bytes)?)),)*
-                            _ => Err($crate::error::DeserializeError::UnknownPacketId(bound, $crate::State::$name, id))
-                        }
+                    Bound::Serverbound => {
+                        $(if $sbpacket::packet_id(version) == Some(id) {
+                            return <$sbpacket>::deserialize(version, bytes).map(Self::$sbpacket);
+                        })*
                     }
-                    $crate::Bound::Clientbound => {
-                        match id {
-                            $($cbid => Ok(Self::$cbpacket(<$cbpacket>::deserialize(bytes)?)),)*
-                            _ => Err($crate::error::DeserializeError::UnknownPacketId(bound, $crate::State::$name, id))
-                        }
+                    Bound::Clientbound => {
+                        $(if $cbpacket::packet_id(version) == Some(id) {
+                            return <$cbpacket>::deserialize(version, bytes).map(Self::$cbpacket);
+                        })*
                     }
                 }
+
+                Err(DeserializeError::UnknownPacketId(bound, State::$name, id))
             }
         }
 
@@ -44,52 +119,89 @@ macro_rules! state {
     }
 }
 
+/// The ids of a packet in `state!`, either listed as `{ V1_18 = 0x00, V1_19 = _ }` or taken from
+/// a generated table in [`packet_ids`](crate::packet_ids) as `(packet_ids::play::serverbound::CHAT)`.
+///
+/// A generated table can be followed by the range of versions whose layout the packet models, as
+/// in `(packet_ids::play::serverbound::CHAT, ..V1_19_3)`.
+macro_rules! packet_ids {
+    ({ $($ver:ident = $id:tt),+ }) => {
+        &[$(($crate::version::ProtocolVersion::$ver, $crate::packet_id!($id))),+]
+    };
+    (($ids:path $(, $($min:ident)? .. $($max:ident)?)?)) => {
+        $ids
+    };
+}
+
+/// Whether a packet in `state!` can be sent in `version`, going by the range after its ids.
+macro_rules! packet_versions {
+    ({ $($ver:ident = $id:tt),+ }, $version:ident) => {
+        true
+    };
+    (($ids:path $(, $($min:ident)? .. $($max:ident)?)?), $version:ident) => {
+        true $($(&& $version.since($crate::version::ProtocolVersion::$min))?
+            $(&& $version.until($crate::version::ProtocolVersion::$max))?)?
+    };
+}
+
+macro_rules! packet_id {
+    (_) => {
+        None
+    };
+    ($id:literal) => {
+        Some($id)
+    };
+}
+
 macro_rules! packets {
-    ($($name:ident($id:literal) $({ $($field:ident: $ty:ty),+ })?);*) => {
+    ($($name:ident $({ $($(#[$gate:ident($ver:ident)])* $field:ident: $ty:ty),+ })?);*) => {
         $(
-            $crate::packet! { $name($id) $({ $($field: $ty),+ })? }
+            $crate::packet! { $name $({ $($(#[$gate($ver)])* $field: $ty),+ })? }
         )*
     }
 }
 
 macro_rules! packet {
-    ($name:ident($id:literal)) => {
+    ($name:ident) => {
         #[derive(Debug, Clone)]
         pub struct $name;
 
         impl $name {
-            pub fn serialize(&self) -> $crate::error::SerRes<(i32, Vec<u8>)> {
-                Ok(($id, vec![]))
+            pub fn serialize(&self, _: $crate::version::ProtocolVersion) -> $crate::error::SerRes<Vec<u8>> {
+                Ok(vec![])
             }
 
-            pub fn deserialize(_: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
+            pub fn deserialize<B: bytes::Buf>(_: $crate::version::ProtocolVersion, _: &mut B) -> $crate::error::DeRes<Self> {
                 Ok(Self)
             }
 
         }
     };
 
-    ($name:ident($id:literal) { $($field:ident: $ty:ty),+ }) => {
+    ($name:ident { $($(#[$gate:ident($ver:ident)])* $field:ident: $ty:ty),+ }) => {
         #[derive(Debug, Clone)]
         pub struct $name {
             $(pub $field: $ty),*
         }
 
         impl $name {
-            pub fn serialize(&self) -> $crate::error::SerRes<(i32, Vec<u8>)> {
-                use $crate::types::Serialize;
+            pub fn serialize(&self, version: $crate::version::ProtocolVersion) -> $crate::error::SerRes<Vec<u8>> {
+                use $crate::types::Encode;
 
-                let mut data = bytes::BytesMut::new();
+                let cx = $crate::types::Context::new(version);
+                let mut data = Vec::new();
 
-                $(self.$field.serialize(&mut data)?;)*
+                $(if $crate::field_present!(cx $(, $gate($ver))*) {
+                    self.$field.encode_with(&mut data, &cx)?;
+                })*
 
-                Ok(($id, data.to_vec()))
+                Ok(data)
             }
 
-            pub fn deserialize(buf: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
-                use $crate::types::Serialize;
+            pub fn deserialize<B: bytes::Buf>(version: $crate::version::ProtocolVersion, buf: &mut B) -> $crate::error::DeRes<Self> {
+                let cx = $crate::types::Context::new(version);
 
-                $(let $field: $ty = Serialize::deserialize(buf)?;)*
+                $(let $field: $ty = $crate::decode_field!(buf, cx $(, $gate($ver))*);)*
 
                 Ok(Self {
                     $($field),*
@@ -99,30 +211,55 @@ macro_rules! packet {
     };
 }
 
+/// Checks the `#[since(..)]` and `#[until(..)]` gates of a packet field against the version in
+/// `cx`.
+macro_rules! field_present {
+    ($cx:ident $(, $gate:ident($ver:ident))*) => {
+        true $(&& $cx.version.$gate($crate::version::ProtocolVersion::$ver))*
+    };
+}
+
+/// Decodes a packet field, or falls back to its default if it is gated out by the version in `cx`.
+macro_rules! decode_field {
+    ($buf:ident, $cx:ident) => {
+        $crate::types::Decode::decode_with($buf, &$cx)?
+    };
+    ($buf:ident, $cx:ident $(, $gate:ident($ver:ident))+) => {
+        if $crate::field_present!($cx $(, $gate($ver))+) {
+            $crate::types::Decode::decode_with($buf, &$cx)?
+        } else {
+            Default::default()
+        }
+    };
+}
+
 macro_rules! varint_enum {
-    ($($name:ident { $($variant:ident = $value:literal),* });*) => {
+    ($($(#[$meta:meta])* $name:ident { $($(#[$vmeta:meta])* $variant:ident = $value:literal),* });*) => {
         $(
             #[repr(i32)]
             #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            $(#[$meta])*
             pub enum $name {
-                $($variant = $value),*
+                $($(#[$vmeta])* $variant = $value),*
             }
 
-            impl $crate::types::Serialize for $name {
-                fn serialize(&self, buf: &mut bytes::BytesMut) -> $crate::error::SerRes<()> {
+            impl $crate::types::Encode for $name {
+                fn encode<B: bytes::BufMut>(&self, buf: &mut B) -> $crate::error::SerRes<()> {
                     use $crate::types::varint::VarInt;
 
                     let val = *self as i32;
 
                     let varint = VarInt(val);
 
-                    varint.serialize(buf)
+                    varint.encode(buf)
                 }
+            }
 
-                fn deserialize(buf: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
+            impl $crate::types::Decode for $name {
+                fn decode<B: bytes::Buf>(buf: &mut B) -> $crate::error::DeRes<Self> {
                     use $crate::{error::DeserializeError, types::varint::VarInt};
 
-                    let varint = VarInt::deserialize(buf)?;
+                    let varint = VarInt::decode(buf)?;
 
                     match varint.0 {
                         $($value => Ok(Self::$variant),)*
@@ -143,17 +280,19 @@ macro_rules! int_enum {
                 $($variant = $value),*
             }
 
-            impl $crate::types::Serialize for $name {
-                fn serialize(&self, buf: &mut bytes::BytesMut) -> $crate::error::SerRes<()> {
+            impl $crate::types::Encode for $name {
+                fn encode<B: bytes::BufMut>(&self, buf: &mut B) -> $crate::error::SerRes<()> {
                     let val = *self as $int;
 
-                    val.serialize(buf)
+                    val.encode(buf)
                 }
+            }
 
-                fn deserialize(buf: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
+            impl $crate::types::Decode for $name {
+                fn decode<B: bytes::Buf>(buf: &mut B) -> $crate::error::DeRes<Self> {
                     use $crate::error::DeserializeError;
 
-                    let val = <$int>::deserialize(buf)?;
+                    let val = <$int>::decode(buf)?;
 
                     match val {
                         $($value => Ok(Self::$variant),)*
@@ -165,24 +304,139 @@ macro_rules! int_enum {
     }
 }
 
+macro_rules! registry {
+    ($($name:ident($key:literal, $ids:ident) { $($variant:ident = $id:literal => $path:literal),* })*) => {
+        $(
+            #[repr(i32)]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            pub enum $name {
+                $($variant = $id),*
+            }
+
+            impl $crate::registry::Registry for $name {
+                const KEY: &'static str = $key;
+                const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+                fn id(self) -> i32 {
+                    self as i32
+                }
+
+                fn path(self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $path),*
+                    }
+                }
+
+                fn from_path(path: &str) -> Option<Self> {
+                    match path {
+                        $($path => Some(Self::$variant),)*
+                        _ => None,
+                    }
+                }
+
+                fn id_in(self, version: $crate::version::ProtocolVersion) -> Option<i32> {
+                    $crate::registry::id_in($ids, self as i32, version)
+                }
+
+                fn from_id_in(id: i32, version: $crate::version::ProtocolVersion) -> Option<Self> {
+                    $crate::registry::from_id_in($ids, id, version).and_then(Self::from_id)
+                }
+            }
+
+            impl $crate::types::Encode for $name {
+                fn encode<B: bytes::BufMut>(&self, buf: &mut B) -> $crate::error::SerRes<()> {
+                    self.encode_with(buf, &$crate::types::Context::default())
+                }
+
+                fn encode_with<B: bytes::BufMut>(
+                    &self,
+                    buf: &mut B,
+                    cx: &$crate::types::Context,
+                ) -> $crate::error::SerRes<()> {
+                    use $crate::{error::SerializeError, registry::Registry, types::varint::VarInt};
+
+                    let id = self
+                        .id_in(cx.version)
+                        .ok_or(SerializeError::UnsupportedValue(stringify!($name), cx.version))?;
+
+                    VarInt(id).encode(buf)
+                }
+            }
+
+            impl $crate::types::Decode for $name {
+                fn decode<B: bytes::Buf>(buf: &mut B) -> $crate::error::DeRes<Self> {
+                    Self::decode_with(buf, &$crate::types::Context::default())
+                }
+
+                fn decode_with<B: bytes::Buf>(
+                    buf: &mut B,
+                    cx: &$crate::types::Context,
+                ) -> $crate::error::DeRes<Self> {
+                    use $crate::{error::DeserializeError, registry::Registry, types::varint::VarInt};
+
+                    let id = VarInt::decode(buf)?.0;
+
+                    if !$crate::registry::supports(cx.version) {
+                        return Err(DeserializeError::UnsupportedValue(stringify!($name), cx.version));
+                    }
+
+                    Self::from_id_in(id, cx.version)
+                        .ok_or(DeserializeError::InvalidEnumVariant(stringify!($name), id as isize))
+                }
+            }
+
+            impl std::fmt::Display for $name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    use $crate::registry::Registry;
+
+                    write!(f, "minecraft:{}", self.path())
+                }
+            }
+
+            impl std::str::FromStr for $name {
+                type Err = $crate::error::UnknownEntryError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    use $crate::registry::Registry;
+
+                    s.parse()
+                        .ok()
+                        .and_then(|identifier| Self::from_identifier(&identifier))
+                        .ok_or_else(|| $crate::error::UnknownEntryError($key, s.to_owned()))
+                }
+            }
+        )*
+    };
+}
+
 macro_rules! data {
     ($($name:ident { $($field:ident: $ty:ty),* });*) => {
         $(
-            #[derive(Debug, Clone)]
+            #[derive(Debug, Clone, PartialEq)]
             pub struct $name {
                 $(pub $field: $ty),*
             }
 
-            impl $crate::types::Serialize for $name {
-                fn serialize(&self, buf: &mut bytes::BytesMut) -> $crate::error::SerRes<()> {
-                    $(self.$field.serialize(buf)?;)*
+            impl $crate::types::Encode for $name {
+                fn encode<B: bytes::BufMut>(&self, buf: &mut B) -> $crate::error::SerRes<()> {
+                    self.encode_with(buf, &Default::default())
+                }
+
+                fn encode_with<B: bytes::BufMut>(&self, buf: &mut B, cx: &$crate::types::Context) -> $crate::error::SerRes<()> {
+                    $(self.$field.encode_with(buf, cx)?;)*
                     Ok(())
                 }
+            }
+
+            impl $crate::types::Decode for $name {
+                fn decode<B: bytes::Buf>(buf: &mut B) -> $crate::error::DeRes<Self> {
+                    Self::decode_with(buf, &Default::default())
+                }
 
-                fn deserialize(buf: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
-                    use $crate::types::Serialize;
+                fn decode_with<B: bytes::Buf>(buf: &mut B, cx: &$crate::types::Context) -> $crate::error::DeRes<Self> {
+                    use $crate::types::Decode;
 
-                    $(let $field: $ty = Serialize::deserialize(buf)?;)*
+                    $(let $field: $ty = Decode::decode_with(buf, cx)?;)*
 
                     Ok(Self {
                         $($field),*
@@ -193,21 +447,45 @@ macro_rules! data {
     }
 }
 
+/// Like `data!`, but for compounds, which are converted with serde. Fields may have serde
+/// attributes like `#[serde(rename = "...")]`.
 macro_rules! nbt_data {
-    ($($name:ident { $($field:ident: $ty:ty),* });*) => {
+    ($($name:ident { $($(#[$attr:meta])* $field:ident: $ty:ty),* });*) => {
         $(
-            #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+            #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
             pub struct $name {
-                $(pub $field: $ty),*
+                $($(#[$attr])* pub $field: $ty),*
+            }
+
+            impl $crate::types::Encode for $name {
+                fn encode<B: bytes::BufMut>(&self, buf: &mut B) -> $crate::error::SerRes<()> {
+                    self.encode_with(buf, &Default::default())
+                }
+
+                fn encode_with<B: bytes::BufMut>(
+                    &self,
+                    buf: &mut B,
+                    cx: &$crate::types::Context,
+                ) -> $crate::error::SerRes<()> {
+                    let compound = $crate::nbt::to_compound(self)?;
+
+                    $crate::types::Encode::encode_with(&compound, buf, cx)
+                }
             }
 
-            impl $crate::types::Serialize for $name {
-                fn serialize(&self, buf: &mut bytes::BytesMut) -> $crate::error::SerRes<()> {
-                    nbt::to_writer(&mut bytes::BufMut::writer(buf), &self, None).map_err(Into::into)
+            impl $crate::types::Decode for $name {
+                fn decode<B: bytes::Buf>(buf: &mut B) -> $crate::error::DeRes<Self> {
+                    Self::decode_with(buf, &Default::default())
                 }
 
-                fn deserialize(buf: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
-                    nbt::from_reader(&mut bytes::Buf::reader(buf)).map_err(Into::into)
+                fn decode_with<B: bytes::Buf>(
+                    buf: &mut B,
+                    cx: &$crate::types::Context,
+                ) -> $crate::error::DeRes<Self> {
+                    let compound: $crate::nbt::Compound =
+                        $crate::types::Decode::decode_with(buf, cx)?;
+
+                    Ok($crate::nbt::from_compound(compound)?)
                 }
             }
         )*
@@ -229,21 +507,27 @@ macro_rules! bitflags {
                 }
             }
 
-            impl $crate::types::Serialize for $name {
-                fn serialize(&self, buf: &mut bytes::BytesMut) -> $crate::error::SerRes<()> {
-                    self.bits().serialize(buf)
+            impl $crate::types::Encode for $name {
+                fn encode<B: bytes::BufMut>(&self, buf: &mut B) -> $crate::error::SerRes<()> {
+                    self.bits().encode(buf)
                 }
+            }
 
-                fn deserialize(buf: &mut bytes::BytesMut) -> $crate::error::DeRes<Self> {
-                    Ok(Self::from_bits_truncate(<$ty>::deserialize(buf)?))
+            impl $crate::types::Decode for $name {
+                fn decode<B: bytes::Buf>(buf: &mut B) -> $crate::error::DeRes<Self> {
+                    Ok(Self::from_bits_truncate(<$ty>::decode(buf)?))
                 }
             }
         )+
     };
 }
 
-pub(crate) use {bitflags, data, int_enum, nbt_data, packet, packets, state, varint_enum};
+pub(crate) use {
+    bitflags, data, decode_field, field_present, int_enum, nbt_data, packet, packet_id, packet_ids,
+    packet_versions, packets, registry, state, varint_enum,
+};
 
+#[cfg(test)]
 mod test {
     #[test]
     fn nbt_data() {
@@ -0,0 +1,578 @@
+//! Named Binary Tag, the format structured data like item tags and registries is sent in.
+//!
+//! Values are held in a [`Tag`] tree, usually with a [`Compound`] as the root. Packets send the
+//! root with an empty name before 1.20.2 and without one since, which [`Encode`] and [`Decode`]
+//! pick from the protocol version. [`Nbt`] additionally allows the root to be absent, which is
+//! sent as a lone `TAG_End`.
+//!
+//! Serde types are converted with [`to_tag`] and [`from_tag`]. Reading is limited to
+//! [`MAX_DEPTH`] levels of nesting and [`MAX_SIZE`] bytes.
+//!
+//! Tags are displayed as SNBT and parsed from it, see [`snbt`].
+
+mod de;
+mod ser;
+pub mod snbt;
+
+pub use de::{from_compound, from_tag};
+pub use ser::{byte_array, int_array, long_array, to_compound, to_tag};
+
+use std::ops::{Deref, DerefMut};
+
+use bytes::{Buf, BufMut};
+use indexmap::IndexMap;
+
+use crate::{
+    error::{DeRes, DeserializeError, NbtError, SerRes, SerializeError},
+    types::{mutf8, raw_nbt::is_nameless, Context, Decode, Encode},
+};
+
+pub const MAX_DEPTH: usize = 512;
+/// The most bytes a tag read from the network may take up, like vanilla allows.
+pub const MAX_SIZE: usize = 2 * 1024 * 1024;
+
+const TAG_END: u8 = 0;
+const TAG_COMPOUND: u8 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    /// A list whose elements all have the same type.
+    List(Vec<Tag>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+/// A map of named tags which keeps the order they were inserted in.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Compound(IndexMap<String, Tag>);
+
+/// A root compound which may be absent, like the tag of an item.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Nbt(pub Option<Compound>);
+
+impl Tag {
+    /// The type id written in front of the tag.
+    pub fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(_) => 9,
+            Tag::Compound(_) => TAG_COMPOUND,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+
+    /// Returns the value of any integer tag.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Tag::Byte(i) => Some(i.into()),
+            Tag::Short(i) => Some(i.into()),
+            Tag::Int(i) => Some(i.into()),
+            Tag::Long(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of any number tag.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Tag::Float(f) => Some(f.into()),
+            Tag::Double(f) => Some(f),
+            _ => self.as_i64().map(|i| i as f64),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Tag]> {
+        match self {
+            Tag::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self {
+            Tag::Compound(compound) => Some(compound),
+            _ => None,
+        }
+    }
+
+    /// Writes the tag with a root name, as stored in files.
+    pub fn write_named<B: BufMut>(&self, name: &str, buf: &mut B) -> SerRes<()> {
+        buf.put_u8(self.id());
+        write_string(name, buf)?;
+        self.write_payload(buf)
+    }
+
+    pub fn read_named<B: Buf>(buf: &mut B) -> DeRes<(String, Self)> {
+        let mut reader = Reader::new(buf);
+        let id = reader.root_id()?;
+        let name = reader.string()?;
+
+        Ok((name, reader.payload(id, 0)?))
+    }
+
+    /// Writes the tag without a root name, as sent since 1.20.2.
+    pub fn write_nameless<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        buf.put_u8(self.id());
+        self.write_payload(buf)
+    }
+
+    pub fn read_nameless<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let mut reader = Reader::new(buf);
+        let id = reader.root_id()?;
+
+        reader.payload(id, 0)
+    }
+
+    fn write_payload<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        match self {
+            Tag::Byte(i) => buf.put_i8(*i),
+            Tag::Short(i) => buf.put_i16(*i),
+            Tag::Int(i) => buf.put_i32(*i),
+            Tag::Long(i) => buf.put_i64(*i),
+            Tag::Float(f) => buf.put_f32(*f),
+            Tag::Double(f) => buf.put_f64(*f),
+            Tag::ByteArray(bytes) => {
+                write_len(bytes.len(), buf)?;
+                bytes.iter().for_each(|i| buf.put_i8(*i));
+            }
+            Tag::String(s) => write_string(s, buf)?,
+            Tag::List(list) => {
+                let id = list.first().map_or(TAG_END, Tag::id);
+
+                if list.iter().any(|tag| tag.id() != id) {
+                    return Err(NbtError::MixedList.into());
+                }
+
+                buf.put_u8(id);
+                write_len(list.len(), buf)?;
+
+                for tag in list {
+                    tag.write_payload(buf)?;
+                }
+            }
+            Tag::Compound(compound) => compound.write_payload(buf)?,
+            Tag::IntArray(ints) => {
+                write_len(ints.len(), buf)?;
+                ints.iter().for_each(|i| buf.put_i32(*i));
+            }
+            Tag::LongArray(longs) => {
+                write_len(longs.len(), buf)?;
+                longs.iter().for_each(|i| buf.put_i64(*i));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Compound {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the compound with a root name, as stored in files.
+    pub fn write_named<B: BufMut>(&self, name: &str, buf: &mut B) -> SerRes<()> {
+        buf.put_u8(TAG_COMPOUND);
+        write_string(name, buf)?;
+        self.write_payload(buf)
+    }
+
+    pub fn read_named<B: Buf>(buf: &mut B) -> DeRes<(String, Self)> {
+        let (name, tag) = Tag::read_named(buf)?;
+
+        Ok((name, tag.try_into()?))
+    }
+
+    /// Writes the compound without a root name, as sent since 1.20.2.
+    pub fn write_nameless<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        buf.put_u8(TAG_COMPOUND);
+        self.write_payload(buf)
+    }
+
+    pub fn read_nameless<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Ok(Tag::read_nameless(buf)?.try_into()?)
+    }
+
+    fn write_payload<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        for (name, tag) in &self.0 {
+            tag.write_named(name, buf)?;
+        }
+
+        buf.put_u8(TAG_END);
+
+        Ok(())
+    }
+}
+
+impl Deref for Compound {
+    type Target = IndexMap<String, Tag>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Compound {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(String, Tag)> for Compound {
+    fn from_iter<T: IntoIterator<Item = (String, Tag)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Compound {
+    type Item = (String, Tag);
+    type IntoIter = indexmap::map::IntoIter<String, Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl TryFrom<Tag> for Compound {
+    type Error = NbtError;
+
+    fn try_from(tag: Tag) -> Result<Self, Self::Error> {
+        match tag {
+            Tag::Compound(compound) => Ok(compound),
+            _ => Err(NbtError::ExpectedCompound),
+        }
+    }
+}
+
+macro_rules! tag_from {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl From<$ty> for Tag {
+                fn from(value: $ty) -> Self {
+                    Tag::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+tag_from! {
+    bool => Byte,
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    String => String,
+    &str => String,
+    Vec<Tag> => List,
+    Compound => Compound
+}
+
+impl From<Compound> for Nbt {
+    fn from(compound: Compound) -> Self {
+        Self(Some(compound))
+    }
+}
+
+impl From<Option<Compound>> for Nbt {
+    fn from(compound: Option<Compound>) -> Self {
+        Self(compound)
+    }
+}
+
+impl Encode for Tag {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match is_nameless(cx) {
+            true => self.write_nameless(buf),
+            false => self.write_named("", buf),
+        }
+    }
+}
+
+impl Decode for Tag {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        match is_nameless(cx) {
+            true => Self::read_nameless(buf),
+            false => Self::read_named(buf).map(|(_, tag)| tag),
+        }
+    }
+}
+
+impl Encode for Compound {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match is_nameless(cx) {
+            true => self.write_nameless(buf),
+            false => self.write_named("", buf),
+        }
+    }
+}
+
+impl Decode for Compound {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        Ok(Tag::decode_with(buf, cx)?.try_into()?)
+    }
+}
+
+impl Encode for Nbt {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match &self.0 {
+            Some(compound) => compound.encode_with(buf, cx),
+            None => {
+                buf.put_u8(TAG_END);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Decode for Nbt {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if buf.chunk().first() == Some(&TAG_END) {
+            buf.advance(1);
+            return Ok(Self(None));
+        }
+
+        Compound::decode_with(buf, cx).map(|compound| Self(Some(compound)))
+    }
+}
+
+fn write_string<B: BufMut>(s: &str, buf: &mut B) -> SerRes<()> {
+    let bytes = mutf8::encode(s);
+    let len =
+        u16::try_from(bytes.len()).map_err(|_| SerializeError::LengthTooLarge(bytes.len()))?;
+
+    buf.put_u16(len);
+    buf.put_slice(&bytes);
+
+    Ok(())
+}
+
+/// The fewest bytes the payload of a tag with this id takes up.
+fn min_payload_size(id: u8) -> usize {
+    match id {
+        2 | 8 => 2,
+        3 | 5 | 7 | 11 | 12 => 4,
+        4 | 6 => 8,
+        9 => 5,
+        _ => 1,
+    }
+}
+
+fn write_len<B: BufMut>(len: usize, buf: &mut B) -> SerRes<()> {
+    let len = i32::try_from(len).map_err(|_| SerializeError::LengthTooLarge(len))?;
+
+    buf.put_i32(len);
+
+    Ok(())
+}
+
+/// Reads tags while keeping track of how many bytes they take up.
+struct Reader<'a, B> {
+    buf: &'a mut B,
+    budget: usize,
+}
+
+impl<'a, B: Buf> Reader<'a, B> {
+    fn new(buf: &'a mut B) -> Self {
+        Self {
+            buf,
+            budget: MAX_SIZE,
+        }
+    }
+
+    /// Takes `len` bytes from the budget and checks that they are available.
+    fn take(&mut self, len: usize) -> DeRes<()> {
+        self.budget = self
+            .budget
+            .checked_sub(len)
+            .ok_or(NbtError::TooLarge(MAX_SIZE))?;
+
+        if self.buf.remaining() < len {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+
+        Ok(())
+    }
+
+    fn root_id(&mut self) -> DeRes<u8> {
+        match self.u8()? {
+            TAG_END => Err(NbtError::UnexpectedEnd.into()),
+            id => Ok(id),
+        }
+    }
+
+    fn u8(&mut self) -> DeRes<u8> {
+        self.take(1)?;
+        Ok(self.buf.get_u8())
+    }
+
+    fn len(&mut self, size: usize) -> DeRes<usize> {
+        self.take(4)?;
+
+        let len = self.buf.get_i32();
+        let len = usize::try_from(len).map_err(|_| DeserializeError::InvalidLength(len as i64))?;
+
+        self.take(len.saturating_mul(size))?;
+
+        Ok(len)
+    }
+
+    fn string(&mut self) -> DeRes<String> {
+        self.take(2)?;
+
+        let len = self.buf.get_u16() as usize;
+        self.take(len)?;
+
+        let mut bytes = vec![0; len];
+        self.buf.copy_to_slice(&mut bytes);
+
+        mutf8::decode(&bytes)
+    }
+
+    fn payload(&mut self, id: u8, depth: usize) -> DeRes<Tag> {
+        if depth > MAX_DEPTH {
+            return Err(NbtError::TooDeep(MAX_DEPTH).into());
+        }
+
+        // Lists and compounds recurse in their own functions, which keeps the stack frame of
+        // every level small.
+        match id {
+            9 => self.list(depth).map(Tag::List),
+            TAG_COMPOUND => self.compound(depth).map(Tag::Compound),
+            _ => self.value(id),
+        }
+    }
+
+    fn list(&mut self, depth: usize) -> DeRes<Vec<Tag>> {
+        let id = self.u8()?;
+        // Every element is charged a byte on top of its payload, so lists of empty compounds
+        // still use up the budget.
+        let len = self.len(1)?;
+
+        if id == TAG_END && len != 0 {
+            return Err(NbtError::UnexpectedEnd.into());
+        }
+
+        // The elements still have to fit in what is left, which bounds the allocation.
+        let mut list = Vec::with_capacity(len.min(self.budget / min_payload_size(id)));
+
+        for _ in 0..len {
+            list.push(self.payload(id, depth + 1)?);
+        }
+
+        Ok(list)
+    }
+
+    fn compound(&mut self, depth: usize) -> DeRes<Compound> {
+        let mut compound = Compound::new();
+
+        loop {
+            let id = self.u8()?;
+
+            if id == TAG_END {
+                return Ok(compound);
+            }
+
+            let name = self.string()?;
+            let tag = self.payload(id, depth + 1)?;
+
+            compound.insert(name, tag);
+        }
+    }
+
+    #[inline(never)]
+    fn value(&mut self, id: u8) -> DeRes<Tag> {
+        let tag = match id {
+            1 => {
+                self.take(1)?;
+                Tag::Byte(self.buf.get_i8())
+            }
+            2 => {
+                self.take(2)?;
+                Tag::Short(self.buf.get_i16())
+            }
+            3 => {
+                self.take(4)?;
+                Tag::Int(self.buf.get_i32())
+            }
+            4 => {
+                self.take(8)?;
+                Tag::Long(self.buf.get_i64())
+            }
+            5 => {
+                self.take(4)?;
+                Tag::Float(self.buf.get_f32())
+            }
+            6 => {
+                self.take(8)?;
+                Tag::Double(self.buf.get_f64())
+            }
+            7 => {
+                let len = self.len(1)?;
+                Tag::ByteArray((0..len).map(|_| self.buf.get_i8()).collect())
+            }
+            8 => Tag::String(self.string()?),
+            11 => {
+                let len = self.len(4)?;
+                Tag::IntArray((0..len).map(|_| self.buf.get_i32()).collect())
+            }
+            12 => {
+                let len = self.len(8)?;
+                Tag::LongArray((0..len).map(|_| self.buf.get_i64()).collect())
+            }
+            _ => return Err(NbtError::InvalidTag(id).into()),
+        };
+
+        Ok(tag)
+    }
+}
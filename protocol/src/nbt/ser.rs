@@ -0,0 +1,459 @@
+use serde::{ser, Serialize};
+
+use crate::error::NbtError;
+
+use super::{Compound, Tag};
+
+pub(super) const BYTE_ARRAY: &str = "__nbt_byte_array";
+pub(super) const INT_ARRAY: &str = "__nbt_int_array";
+pub(super) const LONG_ARRAY: &str = "__nbt_long_array";
+
+/// Converts a value into a tag.
+///
+/// `None` and unit values are left out of compounds. Unsigned integers are stored in the signed
+/// tag of the same size, like Java does.
+pub fn to_tag<T: Serialize + ?Sized>(value: &T) -> Result<Tag, NbtError> {
+    value
+        .serialize(Serializer)?
+        .ok_or_else(|| NbtError::Custom("the value is absent".to_string()))
+}
+
+/// Converts a struct or map into a compound.
+pub fn to_compound<T: Serialize + ?Sized>(value: &T) -> Result<Compound, NbtError> {
+    to_tag(value)?.try_into()
+}
+
+/// Serializes a sequence of integers as a `TAG_Byte_Array`, for use with
+/// `#[serde(serialize_with = "...")]`.
+pub fn byte_array<T: Serialize, S: ser::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(BYTE_ARRAY, value)
+}
+
+/// Serializes a sequence of integers as a `TAG_Int_Array`.
+pub fn int_array<T: Serialize, S: ser::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(INT_ARRAY, value)
+}
+
+/// Serializes a sequence of integers as a `TAG_Long_Array`.
+pub fn long_array<T: Serialize, S: ser::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct(LONG_ARRAY, value)
+}
+
+fn array<T: TryFrom<i64>>(tag: Tag) -> Result<Vec<T>, NbtError> {
+    let invalid = || NbtError::Custom("arrays can only hold integers of their size".to_string());
+
+    match tag {
+        Tag::List(list) => list
+            .iter()
+            .map(|tag| T::try_from(tag.as_i64().ok_or_else(invalid)?).map_err(|_| invalid()))
+            .collect(),
+        Tag::ByteArray(bytes) => bytes
+            .into_iter()
+            .map(|i| T::try_from(i.into()).map_err(|_| invalid()))
+            .collect(),
+        Tag::IntArray(ints) => ints
+            .into_iter()
+            .map(|i| T::try_from(i.into()).map_err(|_| invalid()))
+            .collect(),
+        Tag::LongArray(longs) => longs
+            .into_iter()
+            .map(|i| T::try_from(i).map_err(|_| invalid()))
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Serializes into `None` for values which are left out.
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeList;
+    type SerializeMap = SerializeCompound;
+    type SerializeStruct = SerializeCompound;
+    type SerializeStructVariant = SerializeCompound;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Byte(v as i8)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Byte(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Short(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Int(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Long(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i8(v as i8)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i16(v as i16)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Float(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::String(v.to_string())))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::String(v.to_owned())))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Tag::ByteArray(v.iter().map(|b| *b as i8).collect())))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let tag = to_tag(value)?;
+
+        let tag = match name {
+            BYTE_ARRAY => Tag::ByteArray(array(tag)?),
+            INT_ARRAY => Tag::IntArray(array(tag)?),
+            LONG_ARRAY => Tag::LongArray(array(tag)?),
+            _ => tag,
+        };
+
+        Ok(Some(tag))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let mut compound = Compound::new();
+
+        if let Some(tag) = value.serialize(Serializer)? {
+            compound.insert(variant.to_owned(), tag);
+        }
+
+        Ok(Some(Tag::Compound(compound)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeList {
+            variant: None,
+            list: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(SerializeList {
+            variant: Some(variant),
+            list: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(SerializeCompound {
+            variant: None,
+            compound: Compound::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(SerializeCompound {
+            variant: Some(variant),
+            compound: Compound::new(),
+            key: None,
+        })
+    }
+}
+
+/// Wraps the tag of an enum variant in a compound with the name of the variant.
+fn wrap(variant: Option<&'static str>, tag: Tag) -> Option<Tag> {
+    let tag = match variant {
+        Some(variant) => Tag::Compound([(variant.to_owned(), tag)].into_iter().collect()),
+        None => tag,
+    };
+
+    Some(tag)
+}
+
+struct SerializeList {
+    variant: Option<&'static str>,
+    list: Vec<Tag>,
+}
+
+impl SerializeList {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        let tag = value
+            .serialize(Serializer)?
+            .ok_or_else(|| NbtError::Custom("lists cannot hold absent values".to_string()))?;
+
+        if self
+            .list
+            .first()
+            .is_some_and(|first| first.id() != tag.id())
+        {
+            return Err(NbtError::MixedList);
+        }
+
+        self.list.push(tag);
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<Tag>, NbtError> {
+        Ok(wrap(self.variant, Tag::List(self.list)))
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeList {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+struct SerializeCompound {
+    variant: Option<&'static str>,
+    compound: Compound,
+    key: Option<String>,
+}
+
+impl SerializeCompound {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), NbtError> {
+        if let Some(tag) = value.serialize(Serializer)? {
+            self.compound.insert(key, tag);
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<Tag>, NbtError> {
+        Ok(wrap(self.variant, Tag::Compound(self.compound)))
+    }
+}
+
+impl ser::SerializeMap for SerializeCompound {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        match to_tag(key)? {
+            Tag::String(key) => self.key = Some(key),
+            _ => return Err(NbtError::Custom("map keys must be strings".to_string())),
+        }
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self.key.take().unwrap_or_default();
+
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeCompound {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeCompound {
+    type Ok = Option<Tag>;
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Tag::Byte(i) => serializer.serialize_i8(*i),
+            Tag::Short(i) => serializer.serialize_i16(*i),
+            Tag::Int(i) => serializer.serialize_i32(*i),
+            Tag::Long(i) => serializer.serialize_i64(*i),
+            Tag::Float(f) => serializer.serialize_f32(*f),
+            Tag::Double(f) => serializer.serialize_f64(*f),
+            Tag::ByteArray(bytes) => byte_array(bytes, serializer),
+            Tag::String(s) => serializer.serialize_str(s),
+            Tag::List(list) => list.serialize(serializer),
+            Tag::Compound(compound) => compound.serialize(serializer),
+            Tag::IntArray(ints) => int_array(ints, serializer),
+            Tag::LongArray(longs) => long_array(longs, serializer),
+        }
+    }
+}
+
+impl Serialize for Compound {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
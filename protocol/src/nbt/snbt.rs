@@ -0,0 +1,468 @@
+//! Stringified NBT, as used in commands: `{display:{Name:'"x"'},Count:1b}`.
+//!
+//! [`to_string`] writes tags the way vanilla does, with type suffixes on every number, so its
+//! output parses back into the same tag.
+
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+use crate::error::SnbtError;
+
+use super::{Compound, Tag, MAX_DEPTH};
+
+pub fn from_str(s: &str) -> Result<Tag, SnbtError> {
+    let mut parser = Parser { input: s, pos: 0 };
+    let tag = parser.value(0)?;
+
+    parser.skip_whitespace();
+
+    if parser.pos != s.len() {
+        return Err(parser.error("Trailing data"));
+    }
+
+    Ok(tag)
+}
+
+pub fn to_string(tag: &Tag) -> String {
+    let mut s = String::new();
+    write(tag, &mut s, None).unwrap();
+    s
+}
+
+/// Formats a tag over multiple lines, indented by four spaces.
+pub fn to_string_pretty(tag: &Tag) -> String {
+    let mut s = String::new();
+    write(tag, &mut s, Some(0)).unwrap();
+    s
+}
+
+fn is_unquoted(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+/// Quotes with `"` unless the string contains one before any `'`.
+fn write_quoted<W: Write>(s: &str, w: &mut W) -> fmt::Result {
+    let quote = match s.chars().find(|c| matches!(c, '"' | '\'')) {
+        Some('"') => '\'',
+        _ => '"',
+    };
+
+    w.write_char(quote)?;
+
+    for c in s.chars() {
+        if c == '\\' || c == quote {
+            w.write_char('\\')?;
+        }
+
+        w.write_char(c)?;
+    }
+
+    w.write_char(quote)
+}
+
+fn write_key<W: Write>(key: &str, w: &mut W) -> fmt::Result {
+    match !key.is_empty() && key.chars().all(is_unquoted) {
+        true => w.write_str(key),
+        false => write_quoted(key, w),
+    }
+}
+
+fn write_array<T: fmt::Display, W: Write>(
+    prefix: &str,
+    values: &[T],
+    suffix: &str,
+    w: &mut W,
+    pretty: bool,
+) -> fmt::Result {
+    let separator = if pretty { ", " } else { "," };
+
+    write!(w, "[{prefix};")?;
+
+    for (i, value) in values.iter().enumerate() {
+        match i {
+            0 if pretty => w.write_char(' ')?,
+            0 => {}
+            _ => w.write_str(separator)?,
+        }
+
+        write!(w, "{value}{suffix}")?;
+    }
+
+    w.write_char(']')
+}
+
+fn newline<W: Write>(w: &mut W, indent: usize) -> fmt::Result {
+    writeln!(w)?;
+    (0..indent).try_for_each(|_| w.write_str("    "))
+}
+
+/// Writes a tag, over multiple lines at the given indent if there is one.
+fn write<W: Write>(tag: &Tag, w: &mut W, indent: Option<usize>) -> fmt::Result {
+    match tag {
+        Tag::Byte(i) => write!(w, "{i}b"),
+        Tag::Short(i) => write!(w, "{i}s"),
+        Tag::Int(i) => write!(w, "{i}"),
+        Tag::Long(i) => write!(w, "{i}L"),
+        Tag::Float(f) => write!(w, "{f:?}f"),
+        Tag::Double(f) => write!(w, "{f:?}d"),
+        Tag::ByteArray(bytes) => write_array("B", bytes, "B", w, indent.is_some()),
+        Tag::String(s) => write_quoted(s, w),
+        Tag::List(list) => {
+            // Only lists of lists and compounds are spread over multiple lines.
+            let nested = indent.filter(|_| {
+                list.first()
+                    .is_some_and(|tag| matches!(tag, Tag::List(_) | Tag::Compound(_)))
+            });
+
+            w.write_char('[')?;
+
+            for (i, tag) in list.iter().enumerate() {
+                if i != 0 {
+                    w.write_char(',')?;
+
+                    if indent.is_some() && nested.is_none() {
+                        w.write_char(' ')?;
+                    }
+                }
+
+                if let Some(indent) = nested {
+                    newline(w, indent + 1)?;
+                }
+
+                write(tag, w, nested.map(|indent| indent + 1))?;
+            }
+
+            if let Some(indent) = nested {
+                newline(w, indent)?;
+            }
+
+            w.write_char(']')
+        }
+        Tag::Compound(compound) => {
+            w.write_char('{')?;
+
+            for (i, (key, tag)) in compound.iter().enumerate() {
+                if i != 0 {
+                    w.write_char(',')?;
+                }
+
+                if let Some(indent) = indent {
+                    newline(w, indent + 1)?;
+                }
+
+                write_key(key, w)?;
+                w.write_char(':')?;
+
+                if indent.is_some() {
+                    w.write_char(' ')?;
+                }
+
+                write(tag, w, indent.map(|indent| indent + 1))?;
+            }
+
+            if let Some(indent) = indent.filter(|_| !compound.is_empty()) {
+                newline(w, indent)?;
+            }
+
+            w.write_char('}')
+        }
+        Tag::IntArray(ints) => write_array("I", ints, "", w, indent.is_some()),
+        Tag::LongArray(longs) => write_array("L", longs, "L", w, indent.is_some()),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &'static str) -> SnbtError {
+        SnbtError(message, self.pos)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char, message: &'static str) -> Result<(), SnbtError> {
+        self.skip_whitespace();
+
+        match self.peek() == Some(expected) {
+            true => {
+                self.bump();
+                Ok(())
+            }
+            false => Err(self.error(message)),
+        }
+    }
+
+    /// Skips a `,` and tells whether there was one.
+    fn separator(&mut self) -> bool {
+        self.skip_whitespace();
+
+        if self.peek() != Some(',') {
+            return false;
+        }
+
+        self.bump();
+        self.skip_whitespace();
+
+        true
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Tag, SnbtError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("Too deeply nested"));
+        }
+
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('{') => self.compound(depth).map(Tag::Compound),
+            Some('[') => self.list(depth),
+            Some('"' | '\'') => self.quoted().map(Tag::String),
+            _ => match self.unquoted() {
+                "" => Err(self.error("Expected value")),
+                token => Ok(typed(token)),
+            },
+        }
+    }
+
+    fn compound(&mut self, depth: usize) -> Result<Compound, SnbtError> {
+        let mut compound = Compound::new();
+
+        self.expect('{', "Expected '{'")?;
+        self.skip_whitespace();
+
+        while self.peek() != Some('}') {
+            let key = match self.peek() {
+                Some('"' | '\'') => self.quoted()?,
+                _ => match self.unquoted() {
+                    "" => return Err(self.error("Expected key")),
+                    key => key.to_owned(),
+                },
+            };
+
+            self.expect(':', "Expected ':'")?;
+            compound.insert(key, self.value(depth + 1)?);
+
+            if !self.separator() {
+                break;
+            }
+        }
+
+        self.expect('}', "Expected '}'")?;
+
+        Ok(compound)
+    }
+
+    fn list(&mut self, depth: usize) -> Result<Tag, SnbtError> {
+        self.expect('[', "Expected '['")?;
+
+        let rest = &self.input.as_bytes()[self.pos..];
+
+        if let [kind @ (b'B' | b'I' | b'L'), b';', ..] = rest {
+            self.pos += 2;
+            return self.array(*kind, depth);
+        }
+
+        let mut list: Vec<Tag> = Vec::new();
+
+        self.skip_whitespace();
+
+        while self.peek() != Some(']') {
+            let start = self.pos;
+            let tag = self.value(depth + 1)?;
+
+            if list.first().is_some_and(|first| first.id() != tag.id()) {
+                self.pos = start;
+                return Err(self.error("Mixed list element types"));
+            }
+
+            list.push(tag);
+
+            if !self.separator() {
+                break;
+            }
+        }
+
+        self.expect(']', "Expected ']'")?;
+
+        Ok(Tag::List(list))
+    }
+
+    fn array(&mut self, kind: u8, depth: usize) -> Result<Tag, SnbtError> {
+        let mut tag = match kind {
+            b'B' => Tag::ByteArray(vec![]),
+            b'I' => Tag::IntArray(vec![]),
+            _ => Tag::LongArray(vec![]),
+        };
+
+        self.skip_whitespace();
+
+        while self.peek() != Some(']') {
+            let start = self.pos;
+
+            match (&mut tag, self.value(depth + 1)?) {
+                (Tag::ByteArray(bytes), Tag::Byte(i)) => bytes.push(i),
+                (Tag::IntArray(ints), Tag::Int(i)) => ints.push(i),
+                (Tag::LongArray(longs), Tag::Long(i)) => longs.push(i),
+                _ => {
+                    self.pos = start;
+                    return Err(self.error("Invalid array element type"));
+                }
+            }
+
+            if !self.separator() {
+                break;
+            }
+        }
+
+        self.expect(']', "Expected ']'")?;
+
+        Ok(tag)
+    }
+
+    fn unquoted(&mut self) -> &str {
+        let start = self.pos;
+
+        while self.peek().is_some_and(is_unquoted) {
+            self.bump();
+        }
+
+        &self.input[start..self.pos]
+    }
+
+    fn quoted(&mut self) -> Result<String, SnbtError> {
+        let quote = self.bump().unwrap();
+        let mut s = String::new();
+
+        loop {
+            match self.bump() {
+                Some('\\') => match self.bump() {
+                    Some(c) if c == '\\' || c == quote => s.push(c),
+                    _ => return Err(self.error("Invalid escape sequence")),
+                },
+                Some(c) if c == quote => return Ok(s),
+                Some(c) => s.push(c),
+                None => return Err(self.error("Unclosed quoted string")),
+            }
+        }
+    }
+}
+
+/// Reads an unquoted value, which is a string unless it looks like a number or boolean.
+fn typed(token: &str) -> Tag {
+    number(token).unwrap_or_else(|| match token {
+        "true" => Tag::Byte(1),
+        "false" => Tag::Byte(0),
+        _ => Tag::String(token.to_owned()),
+    })
+}
+
+fn number(token: &str) -> Option<Tag> {
+    let suffix = token.chars().last()?.to_ascii_lowercase();
+    let body = &token[..token.len() - 1];
+
+    match suffix {
+        'b' if is_integer(body) => body.parse().ok().map(Tag::Byte),
+        's' if is_integer(body) => body.parse().ok().map(Tag::Short),
+        'l' if is_integer(body) => body.parse().ok().map(Tag::Long),
+        'f' if is_float(body, true) => body.parse().ok().map(Tag::Float),
+        'd' if is_float(body, true) => body.parse().ok().map(Tag::Double),
+        _ if is_integer(token) => token.parse().ok().map(Tag::Int),
+        _ if is_float(token, false) => token.parse().ok().map(Tag::Double),
+        _ => None,
+    }
+}
+
+fn digits(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `[-+]?(0|[1-9][0-9]*)`
+fn is_integer(s: &str) -> bool {
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+
+    !s.is_empty() && digits(s) && (s == "0" || !s.starts_with('0'))
+}
+
+/// Like `1.5`, `.5`, `1.` or `1.5e3`. Numbers without a point are only floats with a suffix.
+fn is_float(s: &str, suffixed: bool) -> bool {
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+
+    let mantissa = match mantissa.split_once('.') {
+        Some((int, frac)) => digits(int) && digits(frac) && !(int.is_empty() && frac.is_empty()),
+        None => suffixed && !mantissa.is_empty() && digits(mantissa),
+    };
+
+    let exponent = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+
+        !exponent.is_empty() && digits(exponent)
+    });
+
+    mantissa && exponent
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write(self, f, None)
+    }
+}
+
+impl fmt::Display for Compound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('{')?;
+
+        for (i, (key, tag)) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_char(',')?;
+            }
+
+            write_key(key, f)?;
+            write!(f, ":{tag}")?;
+        }
+
+        f.write_char('}')
+    }
+}
+
+impl FromStr for Tag {
+    type Err = SnbtError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_str(s)
+    }
+}
+
+impl FromStr for Compound {
+    type Err = SnbtError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match from_str(s)? {
+            Tag::Compound(compound) => Ok(compound),
+            _ => Err(SnbtError("Expected compound", 0)),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Packet id tables generated from the vanilla reports in `data/reports`.
+//!
+//! There is one table per packet, named after its vanilla id, in the format of the `IDS` of the
+//! packets in [`state!`](crate::state). Ids are only known up to the newest report, so every
+//! table maps later versions to `None`.
+
+include!(concat!(env!("OUT_DIR"), "/packet_ids.rs"));
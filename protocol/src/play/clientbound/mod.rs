@@ -1,13 +1,31 @@
+use bytes::{Buf, BufMut};
 use uuid::Uuid;
 
 use crate::{
-    macros::{int_enum, packets},
-    play::entity_type::EntityType,
-    types::{varint::VarInt, Angle},
+    chat::Chat,
+    error::{DeRes, DeserializeError, SerRes, SerializeError},
+    light::LightData,
+    macros::{data, int_enum, packets},
+    nbt::{Compound, Nbt},
+    play::{
+        chunk::ChunkBlockEntity,
+        command::GraphNode,
+        entity_type::EntityType,
+        metadata::{GlobalPosition, Metadata},
+        registry_codec::{DimensionTypeRef, RegistryCodec},
+        Face,
+    },
+    registry::{Block, BlockEntityType, BlockState},
+    types::{
+        identifier::Identifier,
+        position::{Position, SectionPosition},
+        varint::{VarInt, VarLong},
+        Angle, ByteArray, Context, Decode, Encode,
+    },
 };
 
 packets! {
-    SpawnEntity(0x00) {
+    SpawnEntity {
         entity_id: VarInt,
         uuid: Uuid,
         ty: EntityType,
@@ -17,21 +35,20 @@ packets! {
         pitch: Angle,
         yaw: Angle,
         head_yaw: Angle,
-        // TODO: Add Enum here?
-        // (see https://wiki.vg/Object_Data)
+        // See `SpawnEntity::object_data`.
         data: VarInt,
         velocity_x: i16,
         velocity_y: i16,
         velocity_z: i16
     };
-    SpawnExperienceOrb(0x01) {
+    SpawnExperienceOrb {
         entity_id: VarInt,
         x: f64,
         y: f64,
         z: f64,
         amount: i16
     };
-    SpawnPlayer(0x02) {
+    SpawnPlayer {
         entity_id: VarInt,
         uuid: Uuid,
         x: f64,
@@ -40,13 +57,238 @@ packets! {
         yaw: Angle,
         pitch: Angle
     };
-    EntityAnimation(0x03) {
+    EntityAnimation {
         entity_id: VarInt,
         animation: EntityAnimationId
+    };
+    ChunkDataAndUpdateLight {
+        x: i32,
+        z: i32,
+        heightmaps: Compound,
+        // The sections from the bottom of the world up, see `ChunkDataAndUpdateLight::sections`.
+        data: ByteArray,
+        block_entities: Vec<ChunkBlockEntity>,
+        light: LightData
+    };
+    UpdateLight {
+        x: VarInt,
+        z: VarInt,
+        light: LightData
+    };
+    BlockDestroyStage {
+        entity_id: VarInt,
+        position: Position,
+        // From 0 to 9, any other value removes the animation.
+        stage: i8
+    };
+    BlockEntityData {
+        position: Position,
+        ty: BlockEntityType,
+        data: Nbt
+    };
+    BlockAction {
+        position: Position,
+        action_id: u8,
+        action_param: u8,
+        block: Block
+    };
+    BlockUpdate {
+        position: Position,
+        block: BlockState
+    };
+    UpdateSectionBlocks {
+        section: SectionPosition,
+        #[until(V1_20)]
+        suppress_light_updates: bool,
+        blocks: Vec<SectionBlock>
+    };
+    SetEntityMetadata {
+        entity_id: VarInt,
+        metadata: Metadata
+    };
+    // Moves of less than 8 blocks, see `EntityMovement`.
+    UpdateEntityPosition {
+        entity_id: VarInt,
+        delta_x: i16,
+        delta_y: i16,
+        delta_z: i16,
+        on_ground: bool
+    };
+    UpdateEntityPositionAndRotation {
+        entity_id: VarInt,
+        delta_x: i16,
+        delta_y: i16,
+        delta_z: i16,
+        yaw: Angle,
+        pitch: Angle,
+        on_ground: bool
+    };
+    UpdateEntityRotation {
+        entity_id: VarInt,
+        yaw: Angle,
+        pitch: Angle,
+        on_ground: bool
+    };
+    SetHeadRotation {
+        entity_id: VarInt,
+        head_yaw: Angle
+    };
+    TeleportEntity {
+        entity_id: VarInt,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: Angle,
+        pitch: Angle,
+        on_ground: bool
+    };
+    SetEntityVelocity {
+        entity_id: VarInt,
+        velocity_x: i16,
+        velocity_y: i16,
+        velocity_z: i16
+    };
+    RemoveEntities {
+        entity_ids: Vec<VarInt>
+    };
+    LoginPlay {
+        entity_id: i32,
+        is_hardcore: bool,
+        game_mode: GameMode,
+        previous_game_mode: PreviousGameMode,
+        dimension_names: Vec<Identifier>,
+        registry_codec: RegistryCodec,
+        dimension_type: DimensionTypeRef,
+        dimension_name: Identifier,
+        // The first 8 bytes of the SHA-256 hash of the seed, for biome noise.
+        hashed_seed: i64,
+        // Ignored by the client.
+        max_players: VarInt,
+        view_distance: VarInt,
+        simulation_distance: VarInt,
+        reduced_debug_info: bool,
+        enable_respawn_screen: bool,
+        is_debug: bool,
+        is_flat: bool,
+        #[since(V1_19)]
+        death_location: Option<GlobalPosition>
+    };
+    Respawn {
+        dimension_type: DimensionTypeRef,
+        dimension_name: Identifier,
+        hashed_seed: i64,
+        game_mode: GameMode,
+        previous_game_mode: PreviousGameMode,
+        is_debug: bool,
+        is_flat: bool,
+        // Whether to keep the metadata and attributes of the player, like after the end credits.
+        copy_metadata: bool,
+        #[since(V1_19)]
+        death_location: Option<GlobalPosition>
+    };
+    Commands {
+        // See `Commands::new` and `Commands::tree`.
+        nodes: Vec<GraphNode>,
+        root_index: VarInt
+    };
+    CommandSuggestionsResponse {
+        id: VarInt,
+        // The part of the text the matches replace, in UTF-16 code units.
+        start: VarInt,
+        length: VarInt,
+        matches: Vec<SuggestionMatch>
+    };
+    StartConfiguration
+}
+
+impl BlockAction {
+    /// The direction a piston moves in, which is the parameter of its actions.
+    pub fn piston_direction(&self) -> Option<Face> {
+        matches!(self.block, Block::Piston | Block::StickyPiston)
+            .then(|| Face::decode(&mut [self.action_param].as_slice()).ok())
+            .flatten()
+    }
+}
+
+/// A changed block in Update Section Blocks, packed into a VarLong as
+/// `state << 12 | x << 8 | z << 4 | y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionBlock {
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+    pub state: BlockState,
+}
+
+impl SectionBlock {
+    pub fn new(x: u8, y: u8, z: u8, state: BlockState) -> Self {
+        Self {
+            x: x & 15,
+            y: y & 15,
+            z: z & 15,
+            state,
+        }
+    }
+}
+
+impl Encode for SectionBlock {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        let position = (self.x as i64 & 15) << 8 | (self.z as i64 & 15) << 4 | self.y as i64 & 15;
+        let state = self
+            .state
+            .id_in(cx.version)
+            .ok_or(SerializeError::UnsupportedValue("BlockState", cx.version))?;
+
+        VarLong((state as i64) << 12 | position).encode(buf)
+    }
+}
+
+impl Decode for SectionBlock {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let VarLong(entry) = VarLong::decode(buf)?;
+        let state = BlockState::from_id_in((entry >> 12) as i32, cx.version).ok_or(
+            DeserializeError::InvalidEnumVariant("BlockState", (entry >> 12) as isize),
+        )?;
+
+        Ok(Self {
+            x: (entry >> 8 & 15) as u8,
+            y: (entry & 15) as u8,
+            z: (entry >> 4 & 15) as u8,
+            state,
+        })
+    }
+}
+
+data! {
+    SuggestionMatch {
+        text: String,
+        tooltip: Option<Chat>
     }
 }
 
 int_enum! {
+    GameMode(u8) {
+        Survival = 0,
+        Creative = 1,
+        Adventure = 2,
+        Spectator = 3
+    };
+    // The game mode before the last change, which the F3+F4 switcher starts from.
+    PreviousGameMode(i8) {
+        None = -1,
+        Survival = 0,
+        Creative = 1,
+        Adventure = 2,
+        Spectator = 3
+    };
     EntityAnimationId(u8) {
         SwingMainArm = 0,
         TakeDamage = 1,
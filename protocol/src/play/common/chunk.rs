@@ -0,0 +1,356 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    error::{DeRes, DeserializeError, SerRes, SerializeError},
+    light::LightData,
+    nbt::{Compound, Nbt, Tag},
+    play::ChunkDataAndUpdateLight,
+    registry::{BlockEntityType, BlockState},
+    types::{
+        bit_storage::{ceil_log2, BitStorage},
+        Context, Decode, Encode, PaletteKind, PalettedContainer,
+    },
+    version::ProtocolVersion,
+};
+
+/// The number of biomes in [`RegistryCodec::vanilla`](crate::play::registry_codec::RegistryCodec::vanilla),
+/// which chunk sections are packed for unless told otherwise.
+pub const VANILLA_BIOMES: usize = 63;
+
+/// A chunk section as sent in the Chunk Data packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSection {
+    /// The number of non-air blocks, which the client uses to skip empty sections.
+    pub block_count: i16,
+    pub block_states: PalettedContainer,
+    pub biomes: PalettedContainer,
+}
+
+impl Encode for ChunkSection {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        self.block_count.encode(buf)?;
+        self.block_states.encode_with(buf, cx)?;
+        self.biomes.encode_with(buf, cx)
+    }
+}
+
+impl Decode for ChunkSection {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with_biomes(buf, VANILLA_BIOMES)
+    }
+}
+
+impl ChunkSection {
+    /// Decodes a section sent by a server whose biome registry holds `biomes` biomes.
+    pub fn decode_with_biomes<B: Buf>(buf: &mut B, biomes: usize) -> DeRes<Self> {
+        Ok(Self {
+            block_count: i16::decode(buf)?,
+            block_states: PalettedContainer::decode_kind(PaletteKind::BlockStates, buf)?,
+            biomes: PalettedContainer::decode_kind(PaletteKind::Biomes(biomes), buf)?,
+        })
+    }
+}
+
+/// A block entity in the Chunk Data packet, positioned relative to the chunk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkBlockEntity {
+    /// The section-relative x and z coordinates packed as `x << 4 | z`.
+    pub packed_xz: u8,
+    pub y: i16,
+    pub ty: BlockEntityType,
+    pub data: Nbt,
+}
+
+impl ChunkBlockEntity {
+    pub fn new(x: u8, y: i16, z: u8, ty: BlockEntityType, data: Nbt) -> Self {
+        Self {
+            packed_xz: (x & 15) << 4 | z & 15,
+            y,
+            ty,
+            data,
+        }
+    }
+
+    pub fn x(&self) -> u8 {
+        self.packed_xz >> 4
+    }
+
+    pub fn z(&self) -> u8 {
+        self.packed_xz & 15
+    }
+}
+
+impl Encode for ChunkBlockEntity {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        self.packed_xz.encode(buf)?;
+        self.y.encode(buf)?;
+        self.ty.encode_with(buf, cx)?;
+        self.data.encode_with(buf, cx)
+    }
+}
+
+impl Decode for ChunkBlockEntity {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        Ok(Self {
+            packed_xz: u8::decode(buf)?,
+            y: i16::decode(buf)?,
+            ty: BlockEntityType::decode_with(buf, cx)?,
+            data: Nbt::decode_with(buf, cx)?,
+        })
+    }
+}
+
+impl ChunkDataAndUpdateLight {
+    /// Decodes the sections in `data`, from the bottom of the world up.
+    pub fn sections(&self) -> DeRes<Vec<ChunkSection>> {
+        self.sections_with_biomes(VANILLA_BIOMES)
+    }
+
+    /// Decodes the sections in `data` of a server whose biome registry holds `biomes` biomes.
+    pub fn sections_with_biomes(&self, biomes: usize) -> DeRes<Vec<ChunkSection>> {
+        let mut data = &self.data[..];
+        let mut sections = Vec::new();
+
+        while !data.is_empty() {
+            sections.push(ChunkSection::decode_with_biomes(&mut data, biomes)?);
+        }
+
+        Ok(sections)
+    }
+}
+
+/// The blocks and biomes of a 16×16×16 chunk section.
+///
+/// Coordinates are relative to the section. Biomes are stored for 4×4×4 cells, so their
+/// coordinates range from 0 to 3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    blocks: Vec<BlockState>,
+    biomes: Vec<i32>,
+}
+
+impl Default for Section {
+    fn default() -> Self {
+        Self::filled(BlockState::default(), 0)
+    }
+}
+
+impl Section {
+    /// An empty section of air in the biome with id 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn filled(block: BlockState, biome: i32) -> Self {
+        Self {
+            blocks: vec![block; 4096],
+            biomes: vec![biome; 64],
+        }
+    }
+
+    pub fn block(&self, x: usize, y: usize, z: usize) -> BlockState {
+        self.blocks[PaletteKind::BlockStates.index(x, y, z)]
+    }
+
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, block: BlockState) {
+        self.blocks[PaletteKind::BlockStates.index(x, y, z)] = block;
+    }
+
+    pub fn biome(&self, x: usize, y: usize, z: usize) -> i32 {
+        self.biomes[PaletteKind::Biomes(VANILLA_BIOMES).index(x, y, z)]
+    }
+
+    pub fn set_biome(&mut self, x: usize, y: usize, z: usize, biome: i32) {
+        self.biomes[PaletteKind::Biomes(VANILLA_BIOMES).index(x, y, z)] = biome;
+    }
+
+    /// Counts the blocks which are not air.
+    pub fn block_count(&self) -> i16 {
+        self.blocks.iter().filter(|block| !block.is_air()).count() as i16
+    }
+}
+
+impl ChunkSection {
+    /// Packs a section with the block state ids of `version`, out of `biomes` biomes in the
+    /// biome registry.
+    pub fn from_section(
+        section: &Section,
+        version: ProtocolVersion,
+        biomes: usize,
+    ) -> SerRes<Self> {
+        let blocks = section
+            .blocks
+            .iter()
+            .map(|block| {
+                block
+                    .id_in(version)
+                    .ok_or(SerializeError::UnsupportedValue("BlockState", version))
+            })
+            .collect::<SerRes<Vec<_>>>()?;
+
+        Ok(Self {
+            block_count: section.block_count(),
+            block_states: PalettedContainer::from_values(PaletteKind::BlockStates, &blocks),
+            biomes: PalettedContainer::from_values(PaletteKind::Biomes(biomes), &section.biomes),
+        })
+    }
+}
+
+impl From<&Section> for ChunkSection {
+    /// Packs a section with the block state ids of the newest version, for the vanilla biomes.
+    fn from(section: &Section) -> Self {
+        let blocks: Vec<_> = section.blocks.iter().map(|block| block.id()).collect();
+
+        Self {
+            block_count: section.block_count(),
+            block_states: PalettedContainer::from_values(PaletteKind::BlockStates, &blocks),
+            biomes: PalettedContainer::from_values(
+                PaletteKind::Biomes(VANILLA_BIOMES),
+                &section.biomes,
+            ),
+        }
+    }
+}
+
+impl Section {
+    /// Unpacks a section whose block states have the ids of `version`.
+    pub fn from_chunk_section(section: &ChunkSection, version: ProtocolVersion) -> DeRes<Self> {
+        let blocks = section
+            .block_states
+            .values()
+            .into_iter()
+            .map(|id| {
+                BlockState::from_id_in(id, version).ok_or(DeserializeError::InvalidEnumVariant(
+                    "BlockState",
+                    id as isize,
+                ))
+            })
+            .collect::<DeRes<_>>()?;
+
+        Ok(Self {
+            blocks,
+            biomes: section.biomes.values(),
+        })
+    }
+}
+
+impl TryFrom<&ChunkSection> for Section {
+    type Error = DeserializeError;
+
+    /// Unpacks a section whose block states have the ids of the default version.
+    fn try_from(section: &ChunkSection) -> DeRes<Self> {
+        Self::from_chunk_section(section, ProtocolVersion::default())
+    }
+}
+
+/// Builds a Chunk Data and Update Light packet from sections stacked from the bottom of the
+/// world up.
+///
+/// The `MOTION_BLOCKING` and `WORLD_SURFACE` heightmaps are computed from the blocks, treating
+/// every block but air as motion blocking. Without [`ChunkBuilder::with_light`] the client
+/// receives no light and renders the chunk dark, see [`LightEngine`](crate::light::LightEngine)
+/// to compute it.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkBuilder {
+    x: i32,
+    z: i32,
+    sections: Vec<Section>,
+    block_entities: Vec<ChunkBlockEntity>,
+    light: LightData,
+    version: ProtocolVersion,
+    biomes: Option<usize>,
+}
+
+impl ChunkBuilder {
+    pub fn new(x: i32, z: i32) -> Self {
+        Self {
+            x,
+            z,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the section above the ones added so far.
+    pub fn with_section(mut self, section: Section) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    pub fn with_block_entity(mut self, block_entity: ChunkBlockEntity) -> Self {
+        self.block_entities.push(block_entity);
+        self
+    }
+
+    pub fn with_light(mut self, light: LightData) -> Self {
+        self.light = light;
+        self
+    }
+
+    /// Packs the sections with the block state ids of `version`, instead of the ones of the
+    /// default version.
+    pub fn with_version(mut self, version: ProtocolVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Packs the biomes for a biome registry of `count` biomes, instead of the
+    /// [`VANILLA_BIOMES`].
+    pub fn with_biomes(mut self, count: usize) -> Self {
+        self.biomes = Some(count);
+        self
+    }
+
+    pub fn build(&self) -> SerRes<ChunkDataAndUpdateLight> {
+        let biomes = self.biomes.unwrap_or(VANILLA_BIOMES);
+        let mut data = Vec::new();
+
+        for section in &self.sections {
+            ChunkSection::from_section(section, self.version, biomes)?.encode(&mut data)?;
+        }
+
+        Ok(ChunkDataAndUpdateLight {
+            x: self.x,
+            z: self.z,
+            heightmaps: self.heightmaps(),
+            data: data.into(),
+            block_entities: self.block_entities.clone(),
+            light: self.light.clone(),
+        })
+    }
+
+    /// Packs the height above the bottom of the world of the highest non-air block of each
+    /// column, in `z << 4 | x` order.
+    fn heightmaps(&self) -> Compound {
+        let height = self.sections.len() * 16;
+        let heights = (0..256).map(|i| {
+            let (x, z) = (i & 15, i >> 4);
+
+            (0..height)
+                .rev()
+                .find(|y| !self.sections[y / 16].block(x, y % 16, z).is_air())
+                .map_or(0, |y| y as u64 + 1)
+        });
+
+        let storage = BitStorage::from_entries(ceil_log2(height + 1), heights);
+        let heightmap = Tag::LongArray(storage.into_data());
+
+        [
+            ("MOTION_BLOCKING".to_string(), heightmap.clone()),
+            ("WORLD_SURFACE".to_string(), heightmap),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
@@ -0,0 +1,635 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bytes::{Buf, BufMut};
+
+use crate::{
+    bitflags,
+    error::{DeRes, DeserializeError, SerRes, SerializeError, UnknownEntryError},
+    macros::varint_enum,
+    play::Commands,
+    types::{identifier::Identifier, varint::VarInt, Context, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+/// How deep a command tree may be nested when it is rebuilt from the Commands packet.
+const MAX_DEPTH: usize = 512;
+
+/// A node of the command tree sent in the Commands packet, along with its children.
+///
+/// The tree doubles as a builder:
+///
+/// ```
+/// use arrow_protocol::play::command::{CommandNode, EntityArgumentFlags, Parser};
+///
+/// let root = CommandNode::root()
+///     .then(
+///         CommandNode::literal("kill").executes().then(
+///             CommandNode::argument("targets", Parser::Entity(EntityArgumentFlags::empty()))
+///                 .executes(),
+///         ),
+///     )
+///     .then(CommandNode::literal("suicide").redirect(["kill"]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandNode {
+    pub kind: NodeKind,
+    /// Whether the input up to this node is a complete command.
+    pub executable: bool,
+    /// The names of the nodes leading from the root to the node parsing continues at, which is
+    /// the root itself if empty.
+    pub redirect: Option<Vec<String>>,
+    pub children: Vec<CommandNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    Root,
+    Literal(String),
+    Argument {
+        name: String,
+        parser: Parser,
+        /// Where the client gets suggestions from instead of the parser.
+        suggestions: Option<SuggestionType>,
+    },
+}
+
+impl CommandNode {
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            executable: false,
+            redirect: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn root() -> Self {
+        Self::new(NodeKind::Root)
+    }
+
+    pub fn literal(name: impl Into<String>) -> Self {
+        Self::new(NodeKind::Literal(name.into()))
+    }
+
+    pub fn argument(name: impl Into<String>, parser: Parser) -> Self {
+        Self::new(NodeKind::Argument {
+            name: name.into(),
+            parser,
+            suggestions: None,
+        })
+    }
+
+    pub fn then(mut self, child: CommandNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn executes(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    /// Continues parsing at the node at `path`, like `execute run` does with the root.
+    pub fn redirect<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.redirect = Some(path.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if this is not an argument node.
+    pub fn suggests(mut self, ty: SuggestionType) -> Self {
+        match &mut self.kind {
+            NodeKind::Argument { suggestions, .. } => *suggestions = Some(ty),
+            _ => panic!("only argument nodes have suggestions"),
+        }
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.kind.name()
+    }
+
+    pub fn child(&self, name: &str) -> Option<&CommandNode> {
+        self.children
+            .iter()
+            .find(|child| child.name() == Some(name))
+    }
+
+    /// The node at the end of a path of names starting at this node.
+    pub fn find<S: AsRef<str>>(&self, path: &[S]) -> Option<&CommandNode> {
+        path.iter()
+            .try_fold(self, |node, name| node.child(name.as_ref()))
+    }
+}
+
+/// A node of the Commands packet, which refers to other nodes by their index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub kind: NodeKind,
+    pub executable: bool,
+    pub children: Vec<VarInt>,
+    pub redirect: Option<VarInt>,
+}
+
+bitflags! {
+    NodeFlags(u8) {
+        const LITERAL = 0x01;
+        const ARGUMENT = 0x02;
+        const EXECUTABLE = 0x04;
+        const REDIRECT = 0x08;
+        const SUGGESTIONS = 0x10;
+    }
+}
+
+impl Encode for GraphNode {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        let mut flags = match &self.kind {
+            NodeKind::Root => NodeFlags::empty(),
+            NodeKind::Literal(_) => NodeFlags::LITERAL,
+            NodeKind::Argument { suggestions, .. } => {
+                let mut flags = NodeFlags::ARGUMENT;
+                flags.set(NodeFlags::SUGGESTIONS, suggestions.is_some());
+                flags
+            }
+        };
+        flags.set(NodeFlags::EXECUTABLE, self.executable);
+        flags.set(NodeFlags::REDIRECT, self.redirect.is_some());
+
+        flags.encode(buf)?;
+        self.children.encode(buf)?;
+
+        if let Some(redirect) = self.redirect {
+            redirect.encode(buf)?;
+        }
+
+        match &self.kind {
+            NodeKind::Root => Ok(()),
+            NodeKind::Literal(name) => name.encode(buf),
+            NodeKind::Argument {
+                name,
+                parser,
+                suggestions,
+            } => {
+                name.encode(buf)?;
+                parser.encode_with(buf, cx)?;
+
+                if let Some(suggestions) = suggestions {
+                    suggestions.encode(buf)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Decode for GraphNode {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let flags = NodeFlags::decode(buf)?;
+        let children = Vec::decode(buf)?;
+        let redirect = flags
+            .contains(NodeFlags::REDIRECT)
+            .then(|| VarInt::decode(buf))
+            .transpose()?;
+
+        let kind = match flags.bits() & 0x03 {
+            0 => NodeKind::Root,
+            1 => NodeKind::Literal(String::decode(buf)?),
+            2 => NodeKind::Argument {
+                name: String::decode(buf)?,
+                parser: Parser::decode_with(buf, cx)?,
+                suggestions: flags
+                    .contains(NodeFlags::SUGGESTIONS)
+                    .then(|| SuggestionType::decode(buf))
+                    .transpose()?,
+            },
+            ty => {
+                return Err(DeserializeError::InvalidEnumVariant(
+                    "NodeType",
+                    ty as isize,
+                ))
+            }
+        };
+
+        Ok(Self {
+            kind,
+            executable: flags.contains(NodeFlags::EXECUTABLE),
+            children,
+            redirect,
+        })
+    }
+}
+
+impl Commands {
+    /// Flattens a command tree into the nodes of the packet, breadth first like vanilla.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a redirect does not lead to a node of the tree.
+    pub fn new(root: &CommandNode) -> Self {
+        let mut order = vec![(root, Vec::new())];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(index) = queue.pop_front() {
+            let (node, path) = order[index].clone();
+
+            for child in &node.children {
+                let mut path = path.clone();
+                path.extend(child.name().map(str::to_owned));
+
+                queue.push_back(order.len());
+                order.push((child, path));
+            }
+        }
+
+        let mut indices = HashMap::new();
+        for (index, (_, path)) in order.iter().enumerate() {
+            indices.entry(path.clone()).or_insert(index);
+        }
+
+        let mut next_child = 1;
+        let nodes = order
+            .iter()
+            .map(|(node, _)| {
+                let children = (next_child..next_child + node.children.len())
+                    .map(|index| VarInt(index as i32))
+                    .collect();
+                next_child += node.children.len();
+
+                let redirect = node.redirect.as_ref().map(|path| match indices.get(path) {
+                    Some(&index) => VarInt(index as i32),
+                    None => panic!("redirect to unknown command node {path:?}"),
+                });
+
+                GraphNode {
+                    kind: node.kind.clone(),
+                    executable: node.executable,
+                    children,
+                    redirect,
+                }
+            })
+            .collect();
+
+        Self {
+            nodes,
+            root_index: VarInt(0),
+        }
+    }
+
+    /// Rebuilds the command tree from the nodes of the packet. Nodes with several parents are
+    /// rejected, as copying them into each parent can grow the tree exponentially.
+    pub fn tree(&self) -> DeRes<CommandNode> {
+        let root = self.node(self.root_index)?;
+
+        if root.kind != NodeKind::Root {
+            return Err(DeserializeError::InvalidCommandGraph(
+                "the root is not a root node",
+            ));
+        }
+
+        // The path of a node is where it is first reached from the root.
+        let mut paths = HashMap::from([(self.root_index.0, Vec::new())]);
+        let mut queue = VecDeque::from([self.root_index.0]);
+
+        while let Some(index) = queue.pop_front() {
+            let path = paths[&index].clone();
+
+            for &child in &self.node(VarInt(index))?.children {
+                if paths.contains_key(&child.0) {
+                    continue;
+                }
+
+                let mut path = path.clone();
+                path.extend(self.node(child)?.kind.name().map(str::to_owned));
+
+                paths.insert(child.0, path);
+                queue.push_back(child.0);
+            }
+        }
+
+        self.build(
+            self.root_index,
+            &paths,
+            &mut Vec::new(),
+            &mut HashSet::new(),
+        )
+    }
+
+    fn node(&self, index: VarInt) -> DeRes<&GraphNode> {
+        usize::try_from(index.0)
+            .ok()
+            .and_then(|index| self.nodes.get(index))
+            .ok_or(DeserializeError::InvalidCommandGraph(
+                "node index out of bounds",
+            ))
+    }
+
+    fn build(
+        &self,
+        index: VarInt,
+        paths: &HashMap<i32, Vec<String>>,
+        parents: &mut Vec<i32>,
+        built: &mut HashSet<i32>,
+    ) -> DeRes<CommandNode> {
+        if parents.contains(&index.0) {
+            return Err(DeserializeError::InvalidCommandGraph(
+                "a node is its own child",
+            ));
+        }
+        if !built.insert(index.0) {
+            return Err(DeserializeError::InvalidCommandGraph(
+                "a node has several parents",
+            ));
+        }
+        if parents.len() == MAX_DEPTH {
+            return Err(DeserializeError::InvalidCommandGraph(
+                "nodes are nested too deep",
+            ));
+        }
+
+        let node = self.node(index)?;
+        let redirect = node
+            .redirect
+            .map(|redirect| {
+                self.node(redirect)?;
+                paths
+                    .get(&redirect.0)
+                    .cloned()
+                    .ok_or(DeserializeError::InvalidCommandGraph(
+                        "redirect to an unreachable node",
+                    ))
+            })
+            .transpose()?;
+
+        parents.push(index.0);
+        let children = node
+            .children
+            .iter()
+            .map(|&child| self.build(child, paths, parents, built))
+            .collect::<DeRes<_>>()?;
+        parents.pop();
+
+        Ok(CommandNode {
+            kind: node.kind.clone(),
+            executable: node.executable,
+            redirect,
+            children,
+        })
+    }
+}
+
+impl NodeKind {
+    /// The name of a literal or argument node.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            NodeKind::Root => None,
+            NodeKind::Literal(name) | NodeKind::Argument { name, .. } => Some(name),
+        }
+    }
+}
+
+/// Where the client asks for suggestions for an argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionType {
+    /// Sends a Command Suggestions Request to the server.
+    AskServer,
+    AllRecipes,
+    AvailableSounds,
+    AvailableBiomes,
+    SummonableEntities,
+}
+
+impl SuggestionType {
+    const ALL: [(Self, &'static str); 5] = [
+        (Self::AskServer, "ask_server"),
+        (Self::AllRecipes, "all_recipes"),
+        (Self::AvailableSounds, "available_sounds"),
+        (Self::AvailableBiomes, "available_biomes"),
+        (Self::SummonableEntities, "summonable_entities"),
+    ];
+}
+
+impl Encode for SuggestionType {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        let (_, path) = Self::ALL.iter().find(|(ty, _)| ty == self).unwrap();
+        format!("minecraft:{path}").encode(buf)
+    }
+}
+
+impl Decode for SuggestionType {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let id = Identifier::decode(buf)?;
+
+        Self::ALL
+            .iter()
+            .find(|(_, path)| id.is_default_namespace() && id.path() == *path)
+            .map(|(ty, _)| *ty)
+            .ok_or_else(|| UnknownEntryError("suggestion type", id.to_string()).into())
+    }
+}
+
+varint_enum! {
+    StringKind {
+        // A word of letters, digits and `_-.+`.
+        SingleWord = 0,
+        // A word, or a phrase in double or single quotes.
+        QuotablePhrase = 1,
+        // The rest of the input.
+        GreedyPhrase = 2
+    }
+}
+
+bitflags! {
+    EntityArgumentFlags(u8) {
+        const SINGLE = 0x01;
+        const PLAYERS_ONLY = 0x02;
+    }
+}
+
+/// The bounds of a number argument.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bounds<T> {
+    pub min: Option<T>,
+    pub max: Option<T>,
+}
+
+impl<T: Encode> Encode for Bounds<T> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        let flags = self.min.is_some() as u8 | (self.max.is_some() as u8) << 1;
+        flags.encode(buf)?;
+
+        if let Some(min) = &self.min {
+            min.encode(buf)?;
+        }
+        if let Some(max) = &self.max {
+            max.encode(buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Decode> Decode for Bounds<T> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let flags = u8::decode(buf)?;
+
+        Ok(Self {
+            min: (flags & 0x01 != 0).then(|| T::decode(buf)).transpose()?,
+            max: (flags & 0x02 != 0).then(|| T::decode(buf)).transpose()?,
+        })
+    }
+}
+
+macro_rules! parsers {
+    ($($id:literal $variant:ident $(($props:ty))? = $name:literal $(since $since:ident)?;)*) => {
+        /// How the client parses and highlights an argument, along with its properties.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum Parser {
+            $($variant $(($props))?,)*
+        }
+
+        impl Parser {
+            const NAMES: &'static [&'static str] = &[$($name),*];
+
+            /// The id of the parser since 1.19, before which parsers are sent by name.
+            pub fn id(&self) -> i32 {
+                match self {
+                    $(Self::$variant { .. } => $id,)*
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                Self::NAMES[self.id() as usize]
+            }
+
+            fn since(&self) -> ProtocolVersion {
+                match self {
+                    $($(Self::$variant { .. } => ProtocolVersion::$since,)?)*
+                    _ => ProtocolVersion::V1_18,
+                }
+            }
+
+            fn decode_properties<B: Buf>(id: i32, buf: &mut B) -> DeRes<Self> {
+                Ok(match id {
+                    $($id => Self::$variant $((<$props>::decode(buf)?))?,)*
+                    _ => return Err(DeserializeError::InvalidEnumVariant("Parser", id as isize)),
+                })
+            }
+        }
+    };
+}
+
+parsers! {
+    0 Bool = "brigadier:bool";
+    1 Float(Bounds<f32>) = "brigadier:float";
+    2 Double(Bounds<f64>) = "brigadier:double";
+    3 Integer(Bounds<i32>) = "brigadier:integer";
+    4 Long(Bounds<i64>) = "brigadier:long";
+    5 String(StringKind) = "brigadier:string";
+    6 Entity(EntityArgumentFlags) = "minecraft:entity";
+    7 GameProfile = "minecraft:game_profile";
+    8 BlockPos = "minecraft:block_pos";
+    9 ColumnPos = "minecraft:column_pos";
+    10 Vec3 = "minecraft:vec3";
+    11 Vec2 = "minecraft:vec2";
+    12 BlockState = "minecraft:block_state";
+    13 BlockPredicate = "minecraft:block_predicate";
+    14 ItemStack = "minecraft:item_stack";
+    15 ItemPredicate = "minecraft:item_predicate";
+    16 Color = "minecraft:color";
+    17 Component = "minecraft:component";
+    18 Message = "minecraft:message";
+    19 NbtCompoundTag = "minecraft:nbt_compound_tag";
+    20 NbtTag = "minecraft:nbt_tag";
+    21 NbtPath = "minecraft:nbt_path";
+    22 Objective = "minecraft:objective";
+    23 ObjectiveCriteria = "minecraft:objective_criteria";
+    24 Operation = "minecraft:operation";
+    25 Particle = "minecraft:particle";
+    26 Angle = "minecraft:angle";
+    27 Rotation = "minecraft:rotation";
+    28 ScoreboardSlot = "minecraft:scoreboard_slot";
+    // Whether multiple score holders are allowed.
+    29 ScoreHolder(bool) = "minecraft:score_holder";
+    30 Swizzle = "minecraft:swizzle";
+    31 Team = "minecraft:team";
+    32 ItemSlot = "minecraft:item_slot";
+    33 ResourceLocation = "minecraft:resource_location";
+    34 MobEffect = "minecraft:mob_effect";
+    35 Function = "minecraft:function";
+    36 EntityAnchor = "minecraft:entity_anchor";
+    37 IntRange = "minecraft:int_range";
+    38 FloatRange = "minecraft:float_range";
+    39 ItemEnchantment = "minecraft:item_enchantment";
+    40 EntitySummon = "minecraft:entity_summon";
+    41 Dimension = "minecraft:dimension";
+    42 Time = "minecraft:time";
+    // The registry of the resource or tag.
+    43 ResourceOrTag(Identifier) = "minecraft:resource_or_tag" since V1_18_2;
+    44 Resource(Identifier) = "minecraft:resource" since V1_18_2;
+    45 TemplateMirror = "minecraft:template_mirror" since V1_19;
+    46 TemplateRotation = "minecraft:template_rotation" since V1_19;
+    47 Uuid = "minecraft:uuid";
+}
+
+impl Encode for Parser {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if cx.version.until(self.since()) {
+            return Err(SerializeError::UnsupportedValue(self.name(), cx.version));
+        }
+
+        if cx.version.since(ProtocolVersion::V1_19) {
+            VarInt(self.id()).encode(buf)?;
+        } else {
+            self.name().encode(buf)?;
+        }
+
+        match self {
+            Self::Float(bounds) => bounds.encode(buf),
+            Self::Double(bounds) => bounds.encode(buf),
+            Self::Integer(bounds) => bounds.encode(buf),
+            Self::Long(bounds) => bounds.encode(buf),
+            Self::String(kind) => kind.encode(buf),
+            Self::Entity(flags) => flags.encode(buf),
+            Self::ScoreHolder(multiple) => multiple.encode(buf),
+            Self::ResourceOrTag(registry) | Self::Resource(registry) => registry.encode(buf),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Decode for Parser {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let id = if cx.version.since(ProtocolVersion::V1_19) {
+            VarInt::decode(buf)?.0
+        } else {
+            let name = Identifier::decode(buf)?.to_string();
+
+            Self::NAMES
+                .iter()
+                .position(|parser| *parser == name)
+                .ok_or(UnknownEntryError("argument parser", name))? as i32
+        };
+
+        Self::decode_properties(id, buf)
+    }
+}
@@ -0,0 +1,1152 @@
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use uuid::Uuid;
+
+use crate::{
+    chat::{Chat, ClickEvent, ClickEventAction, Color, Component, NormalColor, TextComponent},
+    error::{CommandError, CommandSyntaxError, NumberKind},
+    play::{
+        command::{Bounds, CommandNode, EntityArgumentFlags, NodeKind, Parser, StringKind},
+        ChatCommand, CommandSuggestionsResponse, Commands, SuggestionMatch,
+    },
+    types::{identifier::Identifier, position::Position, varint::VarInt, ByteArray},
+};
+
+/// An error and the byte offset it happened at.
+type Parse<T> = Result<T, (CommandError, usize)>;
+
+/// Parses commands against the command tree which is sent to clients, like Brigadier does on
+/// vanilla servers.
+#[derive(Debug, Clone)]
+pub struct CommandDispatcher {
+    root: CommandNode,
+}
+
+/// A parsed command, with the arguments it was given.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedCommand {
+    /// The names of the nodes the command went through, following redirects.
+    pub path: Vec<String>,
+    pub arguments: Vec<ParsedArgument>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedArgument {
+    pub name: String,
+    /// Where the argument is in the command, in bytes.
+    pub range: Range<usize>,
+    pub value: Argument,
+    /// The signature the client sent for a message argument.
+    pub signature: Option<ByteArray>,
+}
+
+/// The value of an argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Argument {
+    Bool(bool),
+    Float(f32),
+    Double(f64),
+    Integer(i32),
+    Long(i64),
+    String(String),
+    /// The rest of the command, which the client may sign.
+    Message(String),
+    Entity(EntitySelector),
+    /// Block positions, columns and vectors. Columns and 2D vectors have a relative y of 0.
+    Coordinates(Coordinates),
+    Rotation {
+        yaw: WorldCoordinate,
+        pitch: WorldCoordinate,
+    },
+    Angle(WorldCoordinate),
+    Identifier(Identifier),
+    Tag(Identifier),
+    Uuid(Uuid),
+    /// The text of an argument which is not parsed further, like NBT or a block state. It ends
+    /// at the first space outside of quotes and brackets.
+    Raw(String),
+}
+
+/// A type an [`Argument`] can be read as.
+pub trait ArgumentType: Sized {
+    fn from_argument(argument: &Argument) -> Option<Self>;
+}
+
+macro_rules! argument_types {
+    ($($ty:ty => $($variant:ident)|+;)*) => {
+        $(
+            impl ArgumentType for $ty {
+                fn from_argument(argument: &Argument) -> Option<Self> {
+                    match argument {
+                        $(Argument::$variant(value))|+ => Some(value.clone()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+argument_types! {
+    bool => Bool;
+    f32 => Float;
+    f64 => Double;
+    i32 => Integer;
+    i64 => Long;
+    String => String | Message | Raw;
+    EntitySelector => Entity;
+    Coordinates => Coordinates;
+    Identifier => Identifier;
+    Uuid => Uuid;
+}
+
+impl ParsedCommand {
+    /// The last argument with a name, since redirects may lead to the same argument again.
+    pub fn argument(&self, name: &str) -> Option<&ParsedArgument> {
+        self.arguments
+            .iter()
+            .rev()
+            .find(|argument| argument.name == name)
+    }
+
+    pub fn get<T: ArgumentType>(&self, name: &str) -> Option<T> {
+        T::from_argument(&self.argument(name)?.value)
+    }
+}
+
+/// Who an entity argument targets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntitySelector {
+    Name(String),
+    Uuid(Uuid),
+    Selector {
+        target: SelectorTarget,
+        /// The options in brackets, with their values as they were typed.
+        options: Vec<(String, String)>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorTarget {
+    /// `@p`
+    NearestPlayer,
+    /// `@r`
+    RandomPlayer,
+    /// `@a`
+    AllPlayers,
+    /// `@e`
+    AllEntities,
+    /// `@s`
+    This,
+}
+
+const SELECTOR_OPTIONS: &[&str] = &[
+    "name",
+    "distance",
+    "level",
+    "x",
+    "y",
+    "z",
+    "dx",
+    "dy",
+    "dz",
+    "x_rotation",
+    "y_rotation",
+    "limit",
+    "sort",
+    "gamemode",
+    "team",
+    "type",
+    "tag",
+    "nbt",
+    "scores",
+    "advancements",
+    "predicate",
+];
+
+impl EntitySelector {
+    pub fn option(&self, name: &str) -> Option<&str> {
+        match self {
+            Self::Selector { options, .. } => options
+                .iter()
+                .rev()
+                .find(|(option, _)| option == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Whether this selects one entity at most.
+    pub fn is_single(&self) -> bool {
+        let Self::Selector { target, .. } = self else {
+            return true;
+        };
+
+        match self.option("limit").map(str::parse::<i32>) {
+            Some(Ok(limit)) => limit <= 1,
+            _ => !matches!(
+                target,
+                SelectorTarget::AllPlayers | SelectorTarget::AllEntities
+            ),
+        }
+    }
+
+    /// Whether this selects players only. `@s` may select any entity.
+    pub fn is_players_only(&self) -> bool {
+        match self {
+            Self::Name(_) => true,
+            Self::Uuid(_) => false,
+            Self::Selector { target, .. } => match target {
+                SelectorTarget::AllEntities => {
+                    matches!(self.option("type"), Some("player" | "minecraft:player"))
+                }
+                SelectorTarget::This => false,
+                _ => true,
+            },
+        }
+    }
+}
+
+/// A coordinate which is either absolute or relative to the source of the command.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WorldCoordinate {
+    pub value: f64,
+    /// Prefixed with `~`.
+    pub relative: bool,
+}
+
+impl WorldCoordinate {
+    pub fn resolve(self, origin: f64) -> f64 {
+        if self.relative {
+            origin + self.value
+        } else {
+            self.value
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coordinates {
+    World([WorldCoordinate; 3]),
+    /// Prefixed with `^`, relative to where the source is facing, as `[left, up, forwards]`.
+    Local([f64; 3]),
+}
+
+impl Coordinates {
+    /// The point the coordinates refer to, from a source at `origin` with the rotation
+    /// `[yaw, pitch]` in degrees.
+    pub fn resolve(&self, origin: [f64; 3], [yaw, pitch]: [f32; 2]) -> [f64; 3] {
+        match self {
+            Self::World([x, y, z]) => [
+                x.resolve(origin[0]),
+                y.resolve(origin[1]),
+                z.resolve(origin[2]),
+            ],
+            Self::Local([left, up, forwards]) => {
+                let (yaw, up_pitch, pitch) = (
+                    (yaw + 90.0).to_radians(),
+                    (90.0 - pitch).to_radians(),
+                    (-pitch).to_radians(),
+                );
+                let forward = [
+                    yaw.cos() * pitch.cos(),
+                    pitch.sin(),
+                    yaw.sin() * pitch.cos(),
+                ]
+                .map(f64::from);
+                let upward = [
+                    yaw.cos() * up_pitch.cos(),
+                    up_pitch.sin(),
+                    yaw.sin() * up_pitch.cos(),
+                ]
+                .map(f64::from);
+                let leftward = [
+                    forward[2] * upward[1] - forward[1] * upward[2],
+                    forward[0] * upward[2] - forward[2] * upward[0],
+                    forward[1] * upward[0] - forward[0] * upward[1],
+                ];
+
+                [0, 1, 2].map(|i| {
+                    origin[i] + forward[i] * forwards + upward[i] * up + leftward[i] * left
+                })
+            }
+        }
+    }
+
+    /// The block the coordinates refer to, see [`resolve`](Self::resolve).
+    pub fn block_position(&self, origin: [f64; 3], rotation: [f32; 2]) -> Position {
+        let [x, y, z] = self.resolve(origin, rotation).map(f64::floor);
+
+        Position {
+            x: x as i32,
+            y: y as i16,
+            z: z as i32,
+        }
+    }
+}
+
+/// The suggestions for the end of a command.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Suggestions {
+    /// The text the suggestions were made for.
+    pub text: String,
+    /// The part of the text the matches replace, in bytes.
+    pub range: Range<usize>,
+    pub matches: Vec<SuggestionMatch>,
+}
+
+impl Suggestions {
+    /// The response to the request with the given id. Its range is counted in UTF-16 code
+    /// units of the text the suggestions were made for, like the client expects.
+    pub fn into_response(self, id: VarInt) -> CommandSuggestionsResponse {
+        let utf16 = |end: usize| {
+            self.text
+                .get(..end)
+                .map_or(0, |text| text.encode_utf16().count() as i32)
+        };
+        let start = utf16(self.range.start);
+
+        CommandSuggestionsResponse {
+            id,
+            start: VarInt(start),
+            length: VarInt(utf16(self.range.end) - start),
+            matches: self.matches,
+        }
+    }
+}
+
+/// A node the input went through, and the node it was a child of.
+#[derive(Clone)]
+struct Step<'a> {
+    parent: &'a CommandNode,
+    node: &'a CommandNode,
+    range: Range<usize>,
+}
+
+/// How far the input could be parsed along one branch of the tree.
+#[derive(Clone)]
+struct Attempt<'a> {
+    parsed: ParsedCommand,
+    steps: Vec<Step<'a>>,
+    cursor: usize,
+    errors: Vec<(CommandError, usize)>,
+}
+
+impl Attempt<'_> {
+    fn executable(&self) -> bool {
+        self.steps.last().is_some_and(|step| step.node.executable)
+    }
+}
+
+impl CommandDispatcher {
+    pub fn new(root: CommandNode) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &CommandNode {
+        &self.root
+    }
+
+    /// The Commands packet which tells clients about the commands.
+    pub fn commands(&self) -> Commands {
+        Commands::new(&self.root)
+    }
+
+    /// Parses a command without the leading slash.
+    pub fn parse(&self, command: &str) -> Result<ParsedCommand, CommandSyntaxError> {
+        let attempt = self.parse_children(
+            &self.root,
+            command,
+            Attempt {
+                parsed: ParsedCommand::default(),
+                steps: Vec::new(),
+                cursor: 0,
+                errors: Vec::new(),
+            },
+        );
+
+        let error = |error, cursor| CommandSyntaxError {
+            error,
+            input: command.to_owned(),
+            cursor,
+        };
+
+        if attempt.cursor < command.len() {
+            return Err(match &attempt.errors[..] {
+                [(e, cursor)] => error(e.clone(), *cursor),
+                _ if attempt.steps.is_empty() => {
+                    error(CommandError::UnknownCommand, attempt.cursor)
+                }
+                _ => error(CommandError::UnknownArgument, attempt.cursor),
+            });
+        }
+
+        if !attempt.executable() {
+            return Err(error(CommandError::UnknownCommand, attempt.cursor));
+        }
+
+        Ok(attempt.parsed)
+    }
+
+    /// Parses the command of a Chat Command packet, adding the signatures of message arguments.
+    /// Signatures for other arguments are ignored, like vanilla does.
+    pub fn parse_signed(&self, packet: &ChatCommand) -> Result<ParsedCommand, CommandSyntaxError> {
+        let mut parsed = self.parse(&packet.command)?;
+
+        for argument in &mut parsed.arguments {
+            if matches!(argument.value, Argument::Message(_)) {
+                argument.signature = packet
+                    .arg_signatures
+                    .iter()
+                    .find(|signature| signature.name == argument.name)
+                    .map(|signature| signature.signature.clone());
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Suggests how to complete the text of a Command Suggestions Request, which starts with a
+    /// slash.
+    ///
+    /// Arguments with custom suggestions, like [`SuggestionType::AskServer`], get theirs from
+    /// `custom`, which is given the argument node, the command up to it and the text typed so far.
+    ///
+    /// [`SuggestionType::AskServer`]: crate::play::command::SuggestionType::AskServer
+    pub fn suggest<F>(&self, text: &str, mut custom: F) -> Suggestions
+    where
+        F: FnMut(&CommandNode, &ParsedCommand, &str) -> Vec<SuggestionMatch>,
+    {
+        let offset = text.starts_with('/') as usize;
+        let command = &text[offset..];
+
+        let attempt = self.parse_children(
+            &self.root,
+            command,
+            Attempt {
+                parsed: ParsedCommand::default(),
+                steps: Vec::new(),
+                cursor: 0,
+                errors: Vec::new(),
+            },
+        );
+
+        // Complete the last node if the text ends in it, or its children if it ends after it.
+        let (parent, start) = match attempt.steps.last() {
+            None => (&self.root, 0),
+            Some(step) if step.range.end < command.len() => {
+                (self.redirect_target(step.node), step.range.end + 1)
+            }
+            Some(step) => (step.parent, step.range.start),
+        };
+
+        let mut parsed = attempt.parsed;
+        parsed
+            .arguments
+            .retain(|argument| argument.range.end < start);
+        let remaining = &command[start.min(command.len())..];
+
+        let mut matches = Vec::new();
+        for child in &parent.children {
+            match &child.kind {
+                NodeKind::Root => {}
+                NodeKind::Literal(name) => {
+                    if name.starts_with(&remaining.to_lowercase()) {
+                        matches.push(suggestion(name));
+                    }
+                }
+                NodeKind::Argument {
+                    suggestions: Some(_),
+                    ..
+                } => matches.extend(custom(child, &parsed, remaining)),
+                NodeKind::Argument { parser, .. } => {
+                    matches.extend(
+                        builtin_suggestions(parser)
+                            .iter()
+                            .filter(|text| text.starts_with(remaining))
+                            .map(|text| suggestion(text)),
+                    );
+                }
+            }
+        }
+
+        matches.sort_by_key(|suggestion| suggestion.text.to_lowercase());
+        matches.dedup_by(|a, b| a.text == b.text);
+
+        Suggestions {
+            text: text.to_owned(),
+            range: offset + start.min(command.len())..text.len(),
+            matches,
+        }
+    }
+
+    fn redirect_target<'a>(&'a self, node: &'a CommandNode) -> &'a CommandNode {
+        match &node.redirect {
+            Some(path) => self.root.find(path).unwrap_or(node),
+            None => node,
+        }
+    }
+
+    /// Parses the input at the cursor of `attempt` with each child of `node` it may match, and
+    /// keeps the best outcome like Brigadier: one which parses all input, ideally without errors.
+    fn parse_children<'a>(
+        &'a self,
+        node: &'a CommandNode,
+        input: &str,
+        attempt: Attempt<'a>,
+    ) -> Attempt<'a> {
+        let cursor = attempt.cursor;
+        let mut best: Option<Attempt> = None;
+        let mut errors = Vec::new();
+
+        for child in relevant_children(node, &input[cursor..]) {
+            let mut next = attempt.clone();
+            let end = match parse_node(child, input, cursor, &mut next.parsed) {
+                Ok(end) if end < input.len() && !input[end..].starts_with(' ') => {
+                    errors.push((CommandError::ExpectedSeparator, end));
+                    continue;
+                }
+                Ok(end) => end,
+                Err(error) => {
+                    errors.push(error);
+                    continue;
+                }
+            };
+
+            next.steps.push(Step {
+                parent: node,
+                node: child,
+                range: cursor..end,
+            });
+            next.cursor = end;
+
+            // A redirect continues after the space, other nodes only if something follows it.
+            let target = self.redirect_target(child);
+            let continues = match child.redirect {
+                Some(_) => end < input.len(),
+                None => end + 1 < input.len(),
+            };
+
+            if continues {
+                next.cursor += 1;
+                next = self.parse_children(target, input, next);
+            }
+
+            let rank =
+                |attempt: &Attempt| (attempt.cursor == input.len(), attempt.errors.is_empty());
+
+            if best.as_ref().is_none_or(|best| rank(&next) > rank(best)) {
+                best = Some(next);
+            }
+        }
+
+        best.unwrap_or(Attempt { errors, ..attempt })
+    }
+}
+
+/// The children of a node which may match the input: a literal which matches the next word, or
+/// else all arguments.
+fn relevant_children<'a>(node: &'a CommandNode, input: &str) -> Vec<&'a CommandNode> {
+    let word = input.split(' ').next().unwrap_or_default();
+
+    match node
+        .children
+        .iter()
+        .find(|child| matches!(&child.kind, NodeKind::Literal(name) if name == word))
+    {
+        Some(literal) => vec![literal],
+        None => node
+            .children
+            .iter()
+            .filter(|child| matches!(child.kind, NodeKind::Argument { .. }))
+            .collect(),
+    }
+}
+
+/// Parses one node at the cursor, returning where it ends.
+fn parse_node(
+    node: &CommandNode,
+    input: &str,
+    cursor: usize,
+    parsed: &mut ParsedCommand,
+) -> Parse<usize> {
+    match &node.kind {
+        NodeKind::Root => Err((CommandError::UnknownCommand, cursor)),
+        NodeKind::Literal(name) => {
+            parsed.path.push(name.clone());
+            Ok(cursor + name.len())
+        }
+        NodeKind::Argument { name, parser, .. } => {
+            let mut reader = Reader { input, cursor };
+            let value = parse_argument(parser, &mut reader)?;
+
+            parsed.path.push(name.clone());
+            parsed.arguments.push(ParsedArgument {
+                name: name.clone(),
+                range: cursor..reader.cursor,
+                value,
+                signature: None,
+            });
+
+            Ok(reader.cursor)
+        }
+    }
+}
+
+fn parse_argument(parser: &Parser, reader: &mut Reader) -> Parse<Argument> {
+    let start = reader.cursor;
+
+    Ok(match parser {
+        Parser::Bool => Argument::Bool(reader.read_bool()?),
+        Parser::Float(bounds) => Argument::Float(reader.read_bounded(NumberKind::Float, bounds)?),
+        Parser::Double(bounds) => {
+            Argument::Double(reader.read_bounded(NumberKind::Double, bounds)?)
+        }
+        Parser::Integer(bounds) => {
+            Argument::Integer(reader.read_bounded(NumberKind::Integer, bounds)?)
+        }
+        Parser::Long(bounds) => Argument::Long(reader.read_bounded(NumberKind::Long, bounds)?),
+        Parser::String(StringKind::SingleWord) => {
+            Argument::String(reader.read_unquoted().to_owned())
+        }
+        Parser::String(StringKind::QuotablePhrase) => Argument::String(reader.read_string()?),
+        Parser::String(StringKind::GreedyPhrase) => Argument::String(reader.read_rest().to_owned()),
+        Parser::Message => Argument::Message(reader.read_rest().to_owned()),
+        Parser::Entity(flags) => {
+            let selector = reader.read_selector()?;
+
+            if flags.contains(EntityArgumentFlags::SINGLE) && !selector.is_single() {
+                return Err(match flags.contains(EntityArgumentFlags::PLAYERS_ONLY) {
+                    true => (CommandError::TooManyPlayers, start),
+                    false => (CommandError::TooManyEntities, start),
+                });
+            }
+            // Like vanilla, `@s` is allowed for players, since commands mostly come from them.
+            if flags.contains(EntityArgumentFlags::PLAYERS_ONLY)
+                && !selector.is_players_only()
+                && !matches!(
+                    selector,
+                    EntitySelector::Selector {
+                        target: SelectorTarget::This,
+                        ..
+                    }
+                )
+            {
+                return Err((CommandError::OnlyPlayers, start));
+            }
+
+            Argument::Entity(selector)
+        }
+        Parser::GameProfile => {
+            let selector = reader.read_selector()?;
+
+            if !selector.is_players_only() {
+                return Err((CommandError::OnlyPlayers, start));
+            }
+
+            Argument::Entity(selector)
+        }
+        Parser::BlockPos => Argument::Coordinates(reader.read_coordinates(3, true, false)?),
+        Parser::Vec3 => Argument::Coordinates(reader.read_coordinates(3, false, true)?),
+        Parser::ColumnPos => Argument::Coordinates(reader.read_coordinates(2, true, false)?),
+        Parser::Vec2 => Argument::Coordinates(reader.read_coordinates(2, false, true)?),
+        Parser::Rotation => {
+            let yaw = reader.read_world_coordinate(false, false)?;
+
+            if reader.peek() != Some(' ') {
+                return Err((CommandError::IncompleteCoordinates(2), start));
+            }
+            reader.cursor += 1;
+
+            Argument::Rotation {
+                yaw,
+                pitch: reader.read_world_coordinate(false, false)?,
+            }
+        }
+        Parser::Angle => Argument::Angle(reader.read_world_coordinate(false, false)?),
+        Parser::ResourceOrTag(_) | Parser::Function if reader.peek() == Some('#') => {
+            reader.cursor += 1;
+            Argument::Tag(reader.read_identifier()?)
+        }
+        Parser::ResourceLocation
+        | Parser::Dimension
+        | Parser::Function
+        | Parser::MobEffect
+        | Parser::ItemEnchantment
+        | Parser::EntitySummon
+        | Parser::ResourceOrTag(_)
+        | Parser::Resource(_) => Argument::Identifier(reader.read_identifier()?),
+        Parser::Uuid => {
+            let text = reader.read_while(|c| c.is_ascii_hexdigit() || c == '-');
+
+            match Uuid::try_parse(text) {
+                Ok(uuid) if text.contains('-') => Argument::Uuid(uuid),
+                _ => return Err((CommandError::InvalidUuid, start)),
+            }
+        }
+        _ => Argument::Raw(reader.read_raw()?),
+    })
+}
+
+fn builtin_suggestions(parser: &Parser) -> &'static [&'static str] {
+    match parser {
+        Parser::Bool => &["false", "true"],
+        Parser::Entity(_) | Parser::GameProfile => &["@a", "@e", "@p", "@r", "@s"],
+        Parser::BlockPos | Parser::Vec3 => &["~ ~ ~"],
+        Parser::ColumnPos | Parser::Vec2 | Parser::Rotation => &["~ ~"],
+        _ => &[],
+    }
+}
+
+fn suggestion(text: &str) -> SuggestionMatch {
+    SuggestionMatch {
+        text: text.to_owned(),
+        tooltip: None,
+    }
+}
+
+/// Reads from a command like Brigadier's `StringReader`.
+struct Reader<'a> {
+    input: &'a str,
+    cursor: usize,
+}
+
+fn is_unquoted(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+impl<'a> Reader<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.cursor..].chars().next()
+    }
+
+    fn read_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.cursor;
+        let len = self.input[start..]
+            .find(|c| !f(c))
+            .unwrap_or(self.input.len() - start);
+
+        self.cursor += len;
+        &self.input[start..self.cursor]
+    }
+
+    fn read_rest(&mut self) -> &'a str {
+        self.read_while(|_| true)
+    }
+
+    fn read_unquoted(&mut self) -> &'a str {
+        self.read_while(is_unquoted)
+    }
+
+    fn read_quoted(&mut self) -> Parse<String> {
+        let Some(quote @ ('"' | '\'')) = self.peek() else {
+            return Err((CommandError::ExpectedStartOfQuote, self.cursor));
+        };
+        self.cursor += 1;
+
+        let mut string = String::new();
+        let mut chars = self.input[self.cursor..].char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, c)) if c == quote || c == '\\' => string.push(c),
+                    Some((_, c)) => {
+                        return Err((CommandError::InvalidEscape(c), self.cursor + i + 1))
+                    }
+                    None => break,
+                },
+                c if c == quote => {
+                    self.cursor += i + 1;
+                    return Ok(string);
+                }
+                c => string.push(c),
+            }
+        }
+
+        self.cursor = self.input.len();
+        Err((CommandError::ExpectedEndOfQuote, self.cursor))
+    }
+
+    fn read_string(&mut self) -> Parse<String> {
+        match self.peek() {
+            Some('"' | '\'') => self.read_quoted(),
+            _ => Ok(self.read_unquoted().to_owned()),
+        }
+    }
+
+    fn read_bool(&mut self) -> Parse<bool> {
+        let start = self.cursor;
+
+        match self.read_string()?.as_str() {
+            "" => Err((CommandError::ExpectedBool, start)),
+            "true" => Ok(true),
+            "false" => Ok(false),
+            value => Err((CommandError::InvalidBool(value.to_owned()), start)),
+        }
+    }
+
+    fn read_number<T: FromStr>(&mut self, kind: NumberKind) -> Parse<T> {
+        let start = self.cursor;
+        let text = self.read_while(|c| c.is_ascii_digit() || c == '.' || c == '-');
+
+        if text.is_empty() {
+            return Err((CommandError::ExpectedNumber(kind), start));
+        }
+
+        text.parse().map_err(|_| {
+            self.cursor = start;
+            (CommandError::InvalidNumber(kind, text.to_owned()), start)
+        })
+    }
+
+    fn read_bounded<T>(&mut self, kind: NumberKind, bounds: &Bounds<T>) -> Parse<T>
+    where
+        T: FromStr + PartialOrd + Display + Copy,
+    {
+        let start = self.cursor;
+        let value: T = self.read_number(kind)?;
+
+        match (bounds.min, bounds.max) {
+            (Some(min), _) if value < min => Err((
+                CommandError::NumberTooLow(kind, min.to_string(), value.to_string()),
+                start,
+            )),
+            (_, Some(max)) if value > max => Err((
+                CommandError::NumberTooHigh(kind, max.to_string(), value.to_string()),
+                start,
+            )),
+            _ => Ok(value),
+        }
+    }
+
+    fn read_identifier(&mut self) -> Parse<Identifier> {
+        let start = self.cursor;
+        let text = self.read_while(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | ':' | '/' | '.' | '-')
+        });
+
+        match text.parse() {
+            Ok(identifier) if !text.is_empty() => Ok(identifier),
+            _ => Err((CommandError::InvalidId, start)),
+        }
+    }
+
+    /// Reads a `~` or `^` prefixed coordinate, or an absolute one.
+    fn read_world_coordinate(&mut self, int: bool, center: bool) -> Parse<WorldCoordinate> {
+        let start = self.cursor;
+
+        match self.peek() {
+            None if int => Err((CommandError::ExpectedBlockPosition, start)),
+            None => Err((CommandError::ExpectedCoordinate, start)),
+            Some('^') => Err((CommandError::MixedCoordinates, start)),
+            Some('~') => {
+                self.cursor += 1;
+
+                Ok(WorldCoordinate {
+                    value: self.read_offset()?,
+                    relative: true,
+                })
+            }
+            Some(_) if int => Ok(WorldCoordinate {
+                value: self.read_number::<i32>(NumberKind::Integer)?.into(),
+                relative: false,
+            }),
+            Some(_) => {
+                let value: f64 = self.read_number(NumberKind::Double)?;
+                // Whole block coordinates point at the center of the block.
+                let centered = center && !self.input[start..self.cursor].contains('.');
+
+                Ok(WorldCoordinate {
+                    value: value + if centered { 0.5 } else { 0.0 },
+                    relative: false,
+                })
+            }
+        }
+    }
+
+    /// The number after `~` or `^`, which defaults to 0.
+    fn read_offset(&mut self) -> Parse<f64> {
+        match self.peek() {
+            None | Some(' ') => Ok(0.0),
+            Some(_) => self.read_number(NumberKind::Double),
+        }
+    }
+
+    /// Reads world or local coordinates, where 2D ones are `x z` and have no local form.
+    fn read_coordinates(&mut self, count: usize, int: bool, center: bool) -> Parse<Coordinates> {
+        let start = self.cursor;
+        let local = count == 3 && self.peek() == Some('^');
+        let mut world = [WorldCoordinate::default(); 3];
+        let mut offsets = [0.0; 3];
+
+        for (i, offset) in offsets.iter_mut().enumerate().take(count) {
+            if i > 0 {
+                if self.peek() != Some(' ') {
+                    return Err((CommandError::IncompleteCoordinates(count), start));
+                }
+                self.cursor += 1;
+            }
+
+            if local {
+                if self.peek() != Some('^') {
+                    return Err((CommandError::MixedCoordinates, self.cursor));
+                }
+                self.cursor += 1;
+                *offset = self.read_offset()?;
+            } else {
+                // The y coordinate is never centered, and columns have none.
+                let axis = if count == 2 && i == 1 { 2 } else { i };
+                world[axis] = self.read_world_coordinate(int, center && axis != 1)?;
+            }
+        }
+
+        if local {
+            return Ok(Coordinates::Local(offsets));
+        }
+        if count == 2 {
+            world[1] = WorldCoordinate {
+                value: 0.0,
+                relative: true,
+            };
+        }
+
+        Ok(Coordinates::World(world))
+    }
+
+    fn read_selector(&mut self) -> Parse<EntitySelector> {
+        let start = self.cursor;
+
+        if self.peek() != Some('@') {
+            let name = self.read_string()?;
+
+            if let Ok(uuid) = Uuid::try_parse(&name) {
+                return Ok(EntitySelector::Uuid(uuid));
+            }
+            if name.is_empty() || name.chars().count() > 16 {
+                return Err((CommandError::InvalidEntity, start));
+            }
+            return Ok(EntitySelector::Name(name));
+        }
+
+        self.cursor += 1;
+        let target = match self.peek() {
+            None => return Err((CommandError::MissingSelector, self.cursor)),
+            Some('p') => SelectorTarget::NearestPlayer,
+            Some('r') => SelectorTarget::RandomPlayer,
+            Some('a') => SelectorTarget::AllPlayers,
+            Some('e') => SelectorTarget::AllEntities,
+            Some('s') => SelectorTarget::This,
+            Some(c) => return Err((CommandError::UnknownSelector(format!("@{c}")), start)),
+        };
+        self.cursor += 1;
+
+        let mut options = Vec::new();
+        if self.peek() == Some('[') {
+            self.cursor += 1;
+            self.skip_whitespace();
+
+            while self.peek().is_some_and(|c| c != ']') {
+                let option_start = self.cursor;
+                let option = self.read_string()?;
+
+                if !SELECTOR_OPTIONS.contains(&option.as_str()) {
+                    return Err((CommandError::UnknownOption(option), option_start));
+                }
+
+                self.skip_whitespace();
+                if self.peek() != Some('=') {
+                    return Err((CommandError::ValuelessOption(option), option_start));
+                }
+                self.cursor += 1;
+                self.skip_whitespace();
+
+                let value = self.read_raw_until(|c| c == ',' || c == ']')?;
+                options.push((option, value.trim_end().to_owned()));
+
+                match self.peek() {
+                    Some(',') => {
+                        self.cursor += 1;
+                        self.skip_whitespace();
+                    }
+                    Some(']') => {}
+                    _ => return Err((CommandError::UnterminatedOptions, self.cursor)),
+                }
+            }
+
+            if self.peek() != Some(']') {
+                return Err((CommandError::UnterminatedOptions, self.cursor));
+            }
+            self.cursor += 1;
+        }
+
+        Ok(EntitySelector::Selector { target, options })
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.read_while(char::is_whitespace);
+    }
+
+    fn read_raw(&mut self) -> Parse<String> {
+        self.read_raw_until(|c| c == ' ')
+    }
+
+    /// Reads up to a character which matches `end` outside of quotes and brackets.
+    fn read_raw_until(&mut self, end: impl Fn(char) -> bool) -> Parse<String> {
+        let start = self.cursor;
+        let mut depth = 0usize;
+
+        while let Some(c) = self.peek() {
+            match c {
+                '"' | '\'' => {
+                    self.read_quoted()?;
+                    continue;
+                }
+                '{' | '[' | '(' => depth += 1,
+                '}' | ']' | ')' if depth > 0 => depth -= 1,
+                c if depth == 0 && end(c) => break,
+                _ => {}
+            }
+            self.cursor += c.len_utf8();
+        }
+
+        Ok(self.input[start..self.cursor].to_owned())
+    }
+}
+
+impl CommandError {
+    /// The key of the vanilla translation and its arguments.
+    pub fn translation(&self) -> (&'static str, Vec<String>) {
+        let [expected, invalid] = match self {
+            Self::ExpectedNumber(kind) | Self::InvalidNumber(kind, _) => match kind {
+                NumberKind::Integer => ["parsing.int.expected", "parsing.int.invalid"],
+                NumberKind::Long => ["parsing.long.expected", "parsing.long.invalid"],
+                NumberKind::Float => ["parsing.float.expected", "parsing.float.invalid"],
+                NumberKind::Double => ["parsing.double.expected", "parsing.double.invalid"],
+            },
+            _ => ["", ""],
+        };
+        let [low, big] = match self {
+            Self::NumberTooLow(kind, ..) | Self::NumberTooHigh(kind, ..) => match kind {
+                NumberKind::Integer => ["argument.integer.low", "argument.integer.big"],
+                NumberKind::Long => ["argument.long.low", "argument.long.big"],
+                NumberKind::Float => ["argument.float.low", "argument.float.big"],
+                NumberKind::Double => ["argument.double.low", "argument.double.big"],
+            },
+            _ => ["", ""],
+        };
+
+        match self {
+            Self::UnknownCommand => ("command.unknown.command", vec![]),
+            Self::UnknownArgument => ("command.unknown.argument", vec![]),
+            Self::ExpectedSeparator => ("command.expected.separator", vec![]),
+            Self::ExpectedBool => ("parsing.bool.expected", vec![]),
+            Self::InvalidBool(value) => ("parsing.bool.invalid", vec![value.clone()]),
+            Self::ExpectedNumber(_) => (expected, vec![]),
+            Self::InvalidNumber(_, value) => (invalid, vec![value.clone()]),
+            Self::NumberTooLow(_, min, found) => (low, vec![min.clone(), found.clone()]),
+            Self::NumberTooHigh(_, max, found) => (big, vec![max.clone(), found.clone()]),
+            Self::ExpectedStartOfQuote => ("parsing.quote.expected.start", vec![]),
+            Self::ExpectedEndOfQuote => ("parsing.quote.expected.end", vec![]),
+            Self::InvalidEscape(c) => ("parsing.quote.escape", vec![c.to_string()]),
+            Self::ExpectedCoordinate => ("argument.pos.missing.double", vec![]),
+            Self::ExpectedBlockPosition => ("argument.pos.missing.int", vec![]),
+            Self::IncompleteCoordinates(2) => ("argument.pos2d.incomplete", vec![]),
+            Self::IncompleteCoordinates(_) => ("argument.pos.incomplete", vec![]),
+            Self::MixedCoordinates => ("argument.pos.mixed", vec![]),
+            Self::InvalidEntity => ("argument.entity.invalid", vec![]),
+            Self::MissingSelector => ("argument.entity.selector.missing", vec![]),
+            Self::UnknownSelector(selector) => {
+                ("argument.entity.selector.unknown", vec![selector.clone()])
+            }
+            Self::UnterminatedOptions => ("argument.entity.options.unterminated", vec![]),
+            Self::UnknownOption(option) => {
+                ("argument.entity.options.unknown", vec![option.clone()])
+            }
+            Self::ValuelessOption(option) => {
+                ("argument.entity.options.valueless", vec![option.clone()])
+            }
+            Self::TooManyEntities => ("argument.entity.toomany", vec![]),
+            Self::TooManyPlayers => ("argument.player.toomany", vec![]),
+            Self::OnlyPlayers => ("argument.player.entities", vec![]),
+            Self::InvalidId => ("argument.id.invalid", vec![]),
+            Self::InvalidUuid => ("argument.uuid.invalid", vec![]),
+        }
+    }
+}
+
+fn text(text: impl Into<String>) -> Chat {
+    Chat::default().with_component(Component::String(TextComponent { text: text.into() }))
+}
+
+fn red() -> Color {
+    Color::NormalColor(NormalColor::Red)
+}
+
+impl CommandSyntaxError {
+    /// The cursor in characters.
+    pub fn position(&self) -> usize {
+        self.input[..self.cursor].chars().count()
+    }
+
+    /// Up to 10 characters of the input before the cursor, like `...give @p di<--[HERE]`.
+    pub fn context(&self) -> String {
+        format!("{}<--[HERE]", self.before())
+    }
+
+    fn before(&self) -> String {
+        let position = self.position();
+        let before: String = self.input[..self.cursor]
+            .chars()
+            .skip(position.saturating_sub(10))
+            .collect();
+
+        if position > 10 {
+            format!("...{before}")
+        } else {
+            before
+        }
+    }
+
+    /// The two messages vanilla sends for the error: what went wrong, and where, with the input
+    /// from the error on underlined. Clicking the second one puts the command in the chat box.
+    pub fn to_chat(&self) -> [Chat; 2] {
+        let (key, with) = self.error.translation();
+        let message = Chat::default()
+            .with_component(Component::Translation {
+                translate: key.to_owned(),
+                with: (!with.is_empty()).then(|| {
+                    with.into_iter()
+                        .map(|text| Component::String(TextComponent { text }))
+                        .collect()
+                }),
+            })
+            .with_color(red());
+
+        let mut context = text("")
+            .with_color(Color::NormalColor(NormalColor::Gray))
+            .with_click_event(ClickEvent {
+                action: ClickEventAction::SuggestCommand,
+                value: format!("/{}", self.input),
+            })
+            .with_extra(text(self.before()));
+
+        if self.cursor < self.input.len() {
+            context = context.with_extra(
+                text(&self.input[self.cursor..])
+                    .with_color(red())
+                    .with_underlined(true),
+            );
+        }
+
+        let here = Chat::default()
+            .with_component(Component::Translation {
+                translate: "command.context.here".to_owned(),
+                with: None,
+            })
+            .with_color(red())
+            .with_italic(true);
+
+        [message, context.with_extra(here)]
+    }
+}
@@ -1,124 +1 @@
-use crate::macros::varint_enum;
-
-varint_enum! {
-    EntityType {
-        Allay = 0,
-        AreaEffectCloud = 1,
-        ArmorStand = 2,
-        Arrow = 3,
-        Axolotl = 4,
-        Bat = 5,
-        Bee = 6,
-        Blaze = 7,
-        Boat = 8,
-        ChestBoat = 9,
-        Cat = 10,
-        CaveSpider = 11,
-        Chicken = 12,
-        Cod = 13,
-        Cow = 14,
-        Creeper = 15,
-        Dolphin = 16,
-        Donkey = 17,
-        DragonFireball = 18,
-        Drowned = 19,
-        ElderGuardian = 20,
-        EndCrystal = 21,
-        EnderDragon = 22,
-        Enderman = 23,
-        Endermite = 24,
-        Evoker = 25,
-        EvokerFangs = 26,
-        ExperienceOrb = 27,
-        EyeOfEnder = 28,
-        FallingBlock = 29,
-        FireworkRocket = 30,
-        Fox = 31,
-        Frog = 32,
-        Ghast = 33,
-        Giant = 34,
-        GlowItemFrame = 35,
-        GlowSquid = 36,
-        Goat = 37,
-        Guardian = 38,
-        Hoglin = 39,
-        Horse = 40,
-        Husk = 41,
-        Illusioner = 42,
-        IronGolem = 43,
-        Item = 44,
-        ItemFrame = 45,
-        Fireball = 46,
-        LeashKnot = 47,
-        LightningBolt = 48,
-        Llama = 49,
-        LlamaSpit = 50,
-        MagmaCube = 51,
-        Marker = 52,
-        Minecart = 53,
-        ChestMinecart = 54,
-        CommandblockMinecart = 55,
-        FurnaceMinecart = 56,
-        HopperMinecart = 57,
-        SpawnerMinecart = 58,
-        TntMinecart = 59,
-        Mule = 60,
-        Mooshroom = 61,
-        Ocelot = 62,
-        Painting = 63,
-        Panda = 64,
-        Parrot = 65,
-        Phantom = 66,
-        Pig = 67,
-        Piglin = 68,
-        PiglinBrute = 69,
-        Pillager = 70,
-        PolarBear = 71,
-        Tnt = 72,
-        Pufferfish = 73,
-        Rabbit = 74,
-        Ravager = 75,
-        Salmon = 76,
-        Sheep = 77,
-        Shulker = 78,
-        ShulkerBullet = 79,
-        Silverfish = 80,
-        Skeleton = 81,
-        SkeletonHorse = 82,
-        Slime = 83,
-        SmallFireball = 84,
-        SnowGolem = 85,
-        Snowball = 86,
-        SpectralArrow = 87,
-        Spider = 88,
-        Squid = 89,
-        Stray = 90,
-        Strider = 91,
-        Tadpole = 92,
-        Egg = 93,
-        EnderPearl = 94,
-        ExperienceBottle = 95,
-        Potion = 96,
-        Trident = 97,
-        TraderLlama = 98,
-        TropicalFish = 99,
-        Turtle = 100,
-        Vex = 101,
-        Villager = 102,
-        Vindicator = 103,
-        WanderingTrader = 104,
-        Warden = 105,
-        Witch = 106,
-        Wither = 107,
-        WitherSkeleton = 108,
-        WitherSkull = 109,
-        Wolf = 110,
-        Zoglin = 111,
-        Zombie = 112,
-        ZombieHorse = 113,
-        ZombieVillager = 114,
-        ZombifiedPiglin = 115,
-        Player = 116,
-        FishingBobber = 117
-    }
-}
+pub use crate::registry::{EntityCategory, EntityType};
@@ -0,0 +1,505 @@
+use std::collections::BTreeMap;
+
+use bytes::{Buf, BufMut};
+use uuid::Uuid;
+
+use crate::{
+    chat::Chat,
+    error::{DeRes, DeserializeError, SerRes, SerializeError},
+    macros::{bitflags, data, varint_enum},
+    nbt::Nbt,
+    play::{entity_type::EntityType, particle::Particle, Face, SkinParts},
+    registry::{
+        BlockState, CatVariant, FrogVariant, PaintingVariant, VillagerProfession, VillagerType,
+    },
+    types::{
+        identifier::Identifier, position::Position, slot::Slot, varint::VarInt, Context, Decode,
+        Encode,
+    },
+    version::ProtocolVersion,
+};
+
+/// Ends the list of entries.
+const END: u8 = 0xff;
+
+/// The entity data sent in Set Entity Metadata, keyed by index.
+///
+/// Indices only mean something for a given entity type, see [`EntityMetadata`] for typed
+/// access. Only the value types of 1.18 up to 1.19.2 are supported.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata(pub BTreeMap<u8, MetadataValue>);
+
+impl Metadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value at `index` if it has the type `T`.
+    pub fn get<T: MetadataType>(&self, index: u8) -> Option<T> {
+        self.0.get(&index).and_then(T::from_value)
+    }
+
+    pub fn set<T: MetadataType>(&mut self, index: u8, value: T) {
+        self.0.insert(index, value.into_value());
+    }
+
+    pub fn remove(&mut self, index: u8) -> Option<MetadataValue> {
+        self.0.remove(&index)
+    }
+}
+
+impl Encode for Metadata {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        for (index, value) in &self.0 {
+            index.encode(buf)?;
+            value.encode_with(buf, cx)?;
+        }
+
+        END.encode(buf)
+    }
+}
+
+impl Decode for Metadata {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let mut metadata = Self::new();
+
+        loop {
+            let index = u8::decode(buf)?;
+
+            if index == END {
+                return Ok(metadata);
+            }
+
+            metadata
+                .0
+                .insert(index, MetadataValue::decode_with(buf, cx)?);
+        }
+    }
+}
+
+/// A metadata value, written after the id of its type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    Byte(i8),
+    VarInt(i32),
+    Float(f32),
+    String(String),
+    Chat(Chat),
+    OptionalChat(Option<Chat>),
+    Slot(Slot),
+    Boolean(bool),
+    Rotation(Rotation),
+    Position(Position),
+    OptionalPosition(Option<Position>),
+    Direction(Face),
+    OptionalUuid(Option<Uuid>),
+    /// A block state, where air is sent as absent.
+    BlockState(Option<BlockState>),
+    Nbt(Nbt),
+    Particle(Particle),
+    VillagerData(VillagerData),
+    /// A non-negative VarInt, sent shifted by one so that 0 means absent.
+    OptionalVarInt(Option<i32>),
+    Pose(Pose),
+    CatVariant(CatVariant),
+    FrogVariant(FrogVariant),
+    OptionalGlobalPosition(Option<GlobalPosition>),
+    PaintingVariant(PaintingVariant),
+}
+
+impl MetadataValue {
+    /// The id of the type, as of 1.19.
+    pub fn type_id(&self) -> i32 {
+        match self {
+            Self::Byte(_) => 0,
+            Self::VarInt(_) => 1,
+            Self::Float(_) => 2,
+            Self::String(_) => 3,
+            Self::Chat(_) => 4,
+            Self::OptionalChat(_) => 5,
+            Self::Slot(_) => 6,
+            Self::Boolean(_) => 7,
+            Self::Rotation(_) => 8,
+            Self::Position(_) => 9,
+            Self::OptionalPosition(_) => 10,
+            Self::Direction(_) => 11,
+            Self::OptionalUuid(_) => 12,
+            Self::BlockState(_) => 13,
+            Self::Nbt(_) => 14,
+            Self::Particle(_) => 15,
+            Self::VillagerData(_) => 16,
+            Self::OptionalVarInt(_) => 17,
+            Self::Pose(_) => 18,
+            Self::CatVariant(_) => 19,
+            Self::FrogVariant(_) => 20,
+            Self::OptionalGlobalPosition(_) => 21,
+            Self::PaintingVariant(_) => 22,
+        }
+    }
+}
+
+/// Checks that the type ids of 1.18 up to 1.19.2 apply, and `type_id` exists in `version`.
+fn check_type(type_id: i32, version: ProtocolVersion) -> bool {
+    // 1.19.3 inserted a long type, shifting every id after it.
+    version.until(ProtocolVersion::V1_19_3)
+        && (type_id <= 18 || version.since(ProtocolVersion::V1_19))
+}
+
+impl Encode for MetadataValue {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if !check_type(self.type_id(), cx.version) {
+            return Err(SerializeError::UnsupportedValue(
+                "The metadata value",
+                cx.version,
+            ));
+        }
+
+        VarInt(self.type_id()).encode(buf)?;
+
+        match self {
+            Self::Byte(value) => value.encode(buf),
+            Self::VarInt(value) => VarInt(*value).encode(buf),
+            Self::Float(value) => value.encode(buf),
+            Self::String(value) => value.encode(buf),
+            Self::Chat(value) => value.encode_with(buf, cx),
+            Self::OptionalChat(value) => value.encode_with(buf, cx),
+            Self::Slot(value) => value.encode_with(buf, cx),
+            Self::Boolean(value) => value.encode(buf),
+            Self::Rotation(value) => value.encode(buf),
+            Self::Position(value) => value.encode(buf),
+            Self::OptionalPosition(value) => value.encode(buf),
+            Self::Direction(value) => VarInt(*value as i32).encode(buf),
+            Self::OptionalUuid(value) => value.encode(buf),
+            Self::BlockState(value) => match value {
+                Some(state) => state.encode_with(buf, cx),
+                None => VarInt(0).encode(buf),
+            },
+            Self::Nbt(value) => value.encode_with(buf, cx),
+            Self::Particle(value) => value.encode_with(buf, cx),
+            Self::VillagerData(value) => value.encode_with(buf, cx),
+            Self::OptionalVarInt(value) => VarInt(value.map_or(0, |value| value + 1)).encode(buf),
+            Self::Pose(value) => value.encode(buf),
+            Self::CatVariant(value) => value.encode_with(buf, cx),
+            Self::FrogVariant(value) => value.encode_with(buf, cx),
+            Self::OptionalGlobalPosition(value) => value.encode(buf),
+            Self::PaintingVariant(value) => value.encode_with(buf, cx),
+        }
+    }
+}
+
+impl Decode for MetadataValue {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let type_id = VarInt::decode(buf)?.0;
+
+        if !check_type(type_id, cx.version) {
+            return Err(DeserializeError::InvalidEnumVariant(
+                "MetadataValue",
+                type_id as isize,
+            ));
+        }
+
+        Ok(match type_id {
+            0 => Self::Byte(i8::decode(buf)?),
+            1 => Self::VarInt(VarInt::decode(buf)?.0),
+            2 => Self::Float(f32::decode(buf)?),
+            3 => Self::String(String::decode(buf)?),
+            4 => Self::Chat(Chat::decode_with(buf, cx)?),
+            5 => Self::OptionalChat(Decode::decode_with(buf, cx)?),
+            6 => Self::Slot(Slot::decode_with(buf, cx)?),
+            7 => Self::Boolean(bool::decode(buf)?),
+            8 => Self::Rotation(Rotation::decode(buf)?),
+            9 => Self::Position(Position::decode(buf)?),
+            10 => Self::OptionalPosition(Decode::decode(buf)?),
+            11 => {
+                let id = VarInt::decode(buf)?.0;
+                let face = u8::try_from(id)
+                    .ok()
+                    .and_then(|id| Face::decode(&mut [id].as_slice()).ok())
+                    .ok_or(DeserializeError::InvalidEnumVariant("Face", id as isize))?;
+
+                Self::Direction(face)
+            }
+            12 => Self::OptionalUuid(Decode::decode(buf)?),
+            13 => {
+                let state = BlockState::decode_with(buf, cx)?;
+
+                Self::BlockState((state.id() != 0).then_some(state))
+            }
+            14 => Self::Nbt(Nbt::decode_with(buf, cx)?),
+            15 => Self::Particle(Particle::decode_with(buf, cx)?),
+            16 => Self::VillagerData(VillagerData::decode_with(buf, cx)?),
+            17 => {
+                let value = VarInt::decode(buf)?.0;
+
+                Self::OptionalVarInt((value != 0).then(|| value - 1))
+            }
+            18 => Self::Pose(Pose::decode(buf)?),
+            19 => Self::CatVariant(CatVariant::decode_with(buf, cx)?),
+            20 => Self::FrogVariant(FrogVariant::decode_with(buf, cx)?),
+            21 => Self::OptionalGlobalPosition(Decode::decode(buf)?),
+            22 => Self::PaintingVariant(PaintingVariant::decode_with(buf, cx)?),
+            _ => {
+                return Err(DeserializeError::InvalidEnumVariant(
+                    "MetadataValue",
+                    type_id as isize,
+                ))
+            }
+        })
+    }
+}
+
+data! {
+    // The rotation of a part of an armor stand, in degrees.
+    Rotation {
+        x: f32,
+        y: f32,
+        z: f32
+    };
+    VillagerData {
+        ty: VillagerType,
+        profession: VillagerProfession,
+        level: VarInt
+    };
+    // A position in a dimension, like where a player died.
+    GlobalPosition {
+        dimension: Identifier,
+        position: Position
+    }
+}
+
+varint_enum! {
+    Pose {
+        Standing = 0,
+        FallFlying = 1,
+        Sleeping = 2,
+        Swimming = 3,
+        SpinAttack = 4,
+        Crouching = 5,
+        LongJumping = 6,
+        Dying = 7,
+        Croaking = 8,
+        UsingTongue = 9,
+        Roaring = 10,
+        Sniffing = 11,
+        Emerging = 12,
+        Digging = 13
+    }
+}
+
+bitflags! {
+    EntityFlags(u8) {
+        const ON_FIRE = 0x01;
+        const CROUCHING = 0x02;
+        const SPRINTING = 0x08;
+        const SWIMMING = 0x10;
+        const INVISIBLE = 0x20;
+        const GLOWING = 0x40;
+        const FALL_FLYING = 0x80;
+    };
+    LivingEntityFlags(u8) {
+        const HAND_ACTIVE = 0x01;
+        const OFFHAND = 0x02;
+        const SPIN_ATTACK = 0x04;
+    };
+    MobFlags(u8) {
+        const NO_AI = 0x01;
+        const LEFT_HANDED = 0x02;
+        const AGGRESSIVE = 0x04;
+    }
+}
+
+/// A Rust type stored in one kind of [`MetadataValue`].
+pub trait MetadataType: Sized {
+    fn into_value(self) -> MetadataValue;
+
+    fn from_value(value: &MetadataValue) -> Option<Self>;
+}
+
+macro_rules! metadata_types {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl MetadataType for $ty {
+                fn into_value(self) -> MetadataValue {
+                    MetadataValue::$variant(self)
+                }
+
+                fn from_value(value: &MetadataValue) -> Option<Self> {
+                    match value {
+                        MetadataValue::$variant(value) => Some(value.clone()),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+metadata_types! {
+    i8 => Byte,
+    i32 => VarInt,
+    f32 => Float,
+    String => String,
+    Chat => Chat,
+    Option<Chat> => OptionalChat,
+    Slot => Slot,
+    bool => Boolean,
+    Rotation => Rotation,
+    Position => Position,
+    Option<Position> => OptionalPosition,
+    Face => Direction,
+    Option<Uuid> => OptionalUuid,
+    Option<BlockState> => BlockState,
+    Nbt => Nbt,
+    Particle => Particle,
+    VillagerData => VillagerData,
+    Option<i32> => OptionalVarInt,
+    Pose => Pose,
+    CatVariant => CatVariant,
+    FrogVariant => FrogVariant,
+    Option<GlobalPosition> => OptionalGlobalPosition,
+    PaintingVariant => PaintingVariant
+}
+
+/// Flags are stored as bytes.
+macro_rules! metadata_flags {
+    ($($ty:ty),*) => {
+        $(
+            impl MetadataType for $ty {
+                fn into_value(self) -> MetadataValue {
+                    MetadataValue::Byte(self.bits() as i8)
+                }
+
+                fn from_value(value: &MetadataValue) -> Option<Self> {
+                    match value {
+                        MetadataValue::Byte(bits) => Some(Self::from_bits_truncate(*bits as u8)),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+metadata_flags!(EntityFlags, LivingEntityFlags, MobFlags, SkinParts);
+
+/// The kinds of entities which share a range of metadata indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntityClass {
+    Entity,
+    Living,
+    Mob,
+    Ageable,
+    Player,
+    Item,
+}
+
+impl EntityClass {
+    fn contains(self, ty: EntityType) -> bool {
+        match self {
+            Self::Entity => true,
+            Self::Living => ty.is_living(),
+            Self::Mob => ty.is_mob(),
+            Self::Ageable => ty.is_ageable(),
+            Self::Player => ty == EntityType::Player,
+            Self::Item => ty == EntityType::Item,
+        }
+    }
+}
+
+/// The [`Metadata`] of an entity with accessors for the values its type has, as of 1.19.2.
+///
+/// Getters return `None` if the value is not set or the entity does not have it. Setters panic
+/// if the entity does not have the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityMetadata {
+    pub ty: EntityType,
+    pub metadata: Metadata,
+}
+
+macro_rules! accessors {
+    ($($class:ident $index:literal $get:ident $set:ident: $ty:ty;)*) => {
+        $(
+            pub fn $get(&self) -> Option<$ty> {
+                self.get(EntityClass::$class, $index)
+            }
+
+            pub fn $set(&mut self, value: $ty) -> &mut Self {
+                self.set(EntityClass::$class, $index, value)
+            }
+        )*
+    };
+}
+
+impl EntityMetadata {
+    pub fn new(ty: EntityType) -> Self {
+        Self {
+            ty,
+            metadata: Metadata::new(),
+        }
+    }
+
+    accessors! {
+        Entity 0 flags set_flags: EntityFlags;
+        Entity 1 air_ticks set_air_ticks: i32;
+        Entity 3 is_custom_name_visible set_custom_name_visible: bool;
+        Entity 4 is_silent set_silent: bool;
+        Entity 5 has_no_gravity set_no_gravity: bool;
+        Entity 6 pose set_pose: Pose;
+        Entity 7 ticks_frozen set_ticks_frozen: i32;
+        Living 8 living_flags set_living_flags: LivingEntityFlags;
+        Living 9 health set_health: f32;
+        Living 10 potion_effect_color set_potion_effect_color: i32;
+        Living 11 is_potion_effect_ambient set_potion_effect_ambient: bool;
+        Living 12 arrows set_arrows: i32;
+        Living 13 stingers set_stingers: i32;
+        Living 14 sleeping_position set_sleeping_position: Option<Position>;
+        Mob 15 mob_flags set_mob_flags: MobFlags;
+        Ageable 16 is_baby set_baby: bool;
+        Player 15 additional_hearts set_additional_hearts: f32;
+        Player 16 score set_score: i32;
+        Player 17 skin_parts set_skin_parts: SkinParts;
+        Item 8 item set_item: Slot;
+    }
+
+    pub fn custom_name(&self) -> Option<Chat> {
+        self.get(EntityClass::Entity, 2).flatten()
+    }
+
+    pub fn set_custom_name(&mut self, name: Option<Chat>) -> &mut Self {
+        self.set(EntityClass::Entity, 2, name)
+    }
+
+    fn get<T: MetadataType>(&self, class: EntityClass, index: u8) -> Option<T> {
+        class
+            .contains(self.ty)
+            .then(|| self.metadata.get(index))
+            .flatten()
+    }
+
+    fn set<T: MetadataType>(&mut self, class: EntityClass, index: u8, value: T) -> &mut Self {
+        assert!(
+            class.contains(self.ty),
+            "{:?} has no metadata at index {index}",
+            self.ty
+        );
+
+        self.metadata.set(index, value);
+        self
+    }
+}
@@ -1 +1,9 @@
+pub mod chunk;
+pub mod command;
+pub mod dispatcher;
 pub mod entity_type;
+pub mod metadata;
+pub mod movement;
+pub mod particle;
+pub mod registry_codec;
+pub mod spawn;
@@ -0,0 +1,154 @@
+use crate::{
+    play::{
+        spawn::{velocity_from_fixed, velocity_to_fixed},
+        Play, SetEntityVelocity, TeleportEntity, UpdateEntityPosition,
+        UpdateEntityPositionAndRotation, UpdateEntityRotation,
+    },
+    types::{varint::VarInt, Angle},
+};
+
+/// Converts a coordinate to the units of 1/4096 block relative moves are sent in.
+fn to_fixed(coordinate: f64) -> i64 {
+    (coordinate * 4096.0).floor() as i64
+}
+
+/// The difference between two coordinates in units of 1/4096 block, if it fits in a relative
+/// move.
+///
+/// Both coordinates are rounded to 1/4096 block first, like vanilla does, so that a series of
+/// relative moves does not drift away from the actual position.
+pub fn position_delta(previous: f64, current: f64) -> Option<i16> {
+    i16::try_from(to_fixed(current) - to_fixed(previous)).ok()
+}
+
+/// Where an entity is and which way it is facing, as tracked by the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EntityPosition {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// The yaw in degrees.
+    pub yaw: f32,
+    /// The pitch in degrees.
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+/// The packet which moves an entity from one position to another.
+#[derive(Debug, Clone)]
+pub enum EntityMovement {
+    Position(UpdateEntityPosition),
+    PositionAndRotation(UpdateEntityPositionAndRotation),
+    Rotation(UpdateEntityRotation),
+    Teleport(TeleportEntity),
+}
+
+impl EntityMovement {
+    /// Picks the smallest packet telling clients that an entity moved from `previous` to
+    /// `current`, or `None` if clients would not see a difference.
+    ///
+    /// Moves of 8 blocks or more on any axis are sent as a teleport. Keep sending a teleport
+    /// every now and then, since clients accumulate the rounding of relative moves.
+    pub fn new(
+        entity_id: i32,
+        previous: &EntityPosition,
+        current: &EntityPosition,
+    ) -> Option<Self> {
+        let entity_id = VarInt(entity_id);
+        let yaw = Angle::from_degrees(current.yaw);
+        let pitch = Angle::from_degrees(current.pitch);
+        let rotated = yaw != Angle::from_degrees(previous.yaw)
+            || pitch != Angle::from_degrees(previous.pitch);
+
+        let delta = (
+            position_delta(previous.x, current.x),
+            position_delta(previous.y, current.y),
+            position_delta(previous.z, current.z),
+        );
+
+        let (Some(delta_x), Some(delta_y), Some(delta_z)) = delta else {
+            return Some(Self::Teleport(TeleportEntity {
+                entity_id,
+                x: current.x,
+                y: current.y,
+                z: current.z,
+                yaw,
+                pitch,
+                on_ground: current.on_ground,
+            }));
+        };
+
+        let moved = delta_x != 0 || delta_y != 0 || delta_z != 0;
+
+        Some(match (moved, rotated) {
+            (true, true) => Self::PositionAndRotation(UpdateEntityPositionAndRotation {
+                entity_id,
+                delta_x,
+                delta_y,
+                delta_z,
+                yaw,
+                pitch,
+                on_ground: current.on_ground,
+            }),
+            (false, true) => Self::Rotation(UpdateEntityRotation {
+                entity_id,
+                yaw,
+                pitch,
+                on_ground: current.on_ground,
+            }),
+            _ if moved || previous.on_ground != current.on_ground => {
+                Self::Position(UpdateEntityPosition {
+                    entity_id,
+                    delta_x,
+                    delta_y,
+                    delta_z,
+                    on_ground: current.on_ground,
+                })
+            }
+            _ => return None,
+        })
+    }
+}
+
+impl From<EntityMovement> for Play {
+    fn from(movement: EntityMovement) -> Self {
+        match movement {
+            EntityMovement::Position(packet) => packet.into(),
+            EntityMovement::PositionAndRotation(packet) => packet.into(),
+            EntityMovement::Rotation(packet) => packet.into(),
+            EntityMovement::Teleport(packet) => packet.into(),
+        }
+    }
+}
+
+impl UpdateEntityPosition {
+    /// The distance moved in blocks.
+    pub fn delta(&self) -> [f64; 3] {
+        [self.delta_x, self.delta_y, self.delta_z].map(|delta| delta as f64 / 4096.0)
+    }
+}
+
+impl UpdateEntityPositionAndRotation {
+    /// The distance moved in blocks.
+    pub fn delta(&self) -> [f64; 3] {
+        [self.delta_x, self.delta_y, self.delta_z].map(|delta| delta as f64 / 4096.0)
+    }
+}
+
+impl SetEntityVelocity {
+    /// Sets the velocity of an entity in blocks per tick, clamping each axis like
+    /// [`SpawnEntity::set_velocity`](crate::play::SpawnEntity::set_velocity).
+    pub fn new(entity_id: i32, [x, y, z]: [f64; 3]) -> Self {
+        Self {
+            entity_id: VarInt(entity_id),
+            velocity_x: velocity_to_fixed(x),
+            velocity_y: velocity_to_fixed(y),
+            velocity_z: velocity_to_fixed(z),
+        }
+    }
+
+    /// The velocity in blocks per tick.
+    pub fn velocity(&self) -> [f64; 3] {
+        [self.velocity_x, self.velocity_y, self.velocity_z].map(velocity_from_fixed)
+    }
+}
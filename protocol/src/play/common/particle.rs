@@ -0,0 +1,176 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    error::{DeRes, DeserializeError, SerRes, SerializeError, UnknownEntryError},
+    registry::{BlockState, ParticleType},
+    types::{
+        identifier::Identifier, position::Position, slot::Slot, varint::VarInt, Context, Decode,
+        Encode,
+    },
+    version::ProtocolVersion,
+};
+
+/// A particle with the extra data its type needs, in the format of 1.19.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub ty: ParticleType,
+    pub data: ParticleData,
+}
+
+impl From<ParticleType> for Particle {
+    fn from(ty: ParticleType) -> Self {
+        Self {
+            ty,
+            data: ParticleData::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParticleData {
+    None,
+    /// The block of `block`, `block_marker` and `falling_dust` particles.
+    Block(BlockState),
+    Dust {
+        color: [f32; 3],
+        scale: f32,
+    },
+    DustColorTransition {
+        from: [f32; 3],
+        scale: f32,
+        to: [f32; 3],
+    },
+    Item(Slot),
+    Vibration {
+        source: PositionSource,
+        ticks: VarInt,
+    },
+    SculkCharge {
+        roll: f32,
+    },
+    Shriek {
+        delay: VarInt,
+    },
+}
+
+/// Where a vibration particle travels to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PositionSource {
+    Block(Position),
+    Entity { entity_id: VarInt, eye_height: f32 },
+}
+
+impl Encode for Particle {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        self.ty.encode_with(buf, cx)?;
+
+        match &self.data {
+            ParticleData::None => Ok(()),
+            ParticleData::Block(state) => state.encode_with(buf, cx),
+            ParticleData::Dust { color, scale } => {
+                color.encode(buf)?;
+                scale.encode(buf)
+            }
+            ParticleData::DustColorTransition { from, scale, to } => {
+                from.encode(buf)?;
+                scale.encode(buf)?;
+                to.encode(buf)
+            }
+            ParticleData::Item(slot) => slot.encode_with(buf, cx),
+            ParticleData::Vibration { source, ticks } => {
+                // Before 1.19 vibrations had a start and an end instead of a source.
+                if cx.version.until(ProtocolVersion::V1_19) {
+                    return Err(SerializeError::UnsupportedValue(
+                        "The vibration particle",
+                        cx.version,
+                    ));
+                }
+
+                source.encode(buf)?;
+                ticks.encode(buf)
+            }
+            ParticleData::SculkCharge { roll } => roll.encode(buf),
+            ParticleData::Shriek { delay } => delay.encode(buf),
+        }
+    }
+}
+
+impl Decode for Particle {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let ty = ParticleType::decode_with(buf, cx)?;
+
+        let data = match ty {
+            ParticleType::Block | ParticleType::BlockMarker | ParticleType::FallingDust => {
+                ParticleData::Block(BlockState::decode_with(buf, cx)?)
+            }
+            ParticleType::Dust => ParticleData::Dust {
+                color: Decode::decode(buf)?,
+                scale: f32::decode(buf)?,
+            },
+            ParticleType::DustColorTransition => ParticleData::DustColorTransition {
+                from: Decode::decode(buf)?,
+                scale: f32::decode(buf)?,
+                to: Decode::decode(buf)?,
+            },
+            ParticleType::Item => ParticleData::Item(Slot::decode_with(buf, cx)?),
+            ParticleType::Vibration => ParticleData::Vibration {
+                source: PositionSource::decode(buf)?,
+                ticks: VarInt::decode(buf)?,
+            },
+            ParticleType::SculkCharge => ParticleData::SculkCharge {
+                roll: f32::decode(buf)?,
+            },
+            ParticleType::Shriek => ParticleData::Shriek {
+                delay: VarInt::decode(buf)?,
+            },
+            _ => ParticleData::None,
+        };
+
+        Ok(Self { ty, data })
+    }
+}
+
+impl Encode for PositionSource {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        match self {
+            PositionSource::Block(position) => {
+                "minecraft:block".encode(buf)?;
+                position.encode(buf)
+            }
+            PositionSource::Entity {
+                entity_id,
+                eye_height,
+            } => {
+                "minecraft:entity".encode(buf)?;
+                entity_id.encode(buf)?;
+                eye_height.encode(buf)
+            }
+        }
+    }
+}
+
+impl Decode for PositionSource {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let ty = Identifier::decode(buf)?;
+
+        match ty.path() {
+            "block" if ty.is_default_namespace() => Ok(Self::Block(Position::decode(buf)?)),
+            "entity" if ty.is_default_namespace() => Ok(Self::Entity {
+                entity_id: VarInt::decode(buf)?,
+                eye_height: f32::decode(buf)?,
+            }),
+            _ => Err(DeserializeError::UnknownEntry(UnknownEntryError(
+                "minecraft:position_source_type",
+                ty.to_string(),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,442 @@
+use bytes::{Buf, BufMut};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::{
+    error::{DeRes, DeserializeError, SerRes, SerializeError},
+    macros::nbt_data,
+    nbt::{from_compound, from_tag, to_compound, Compound, Tag},
+    types::{identifier::Identifier, Context, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+/// The dimension types, biomes and chat types of vanilla 1.19.2, as JSON.
+const VANILLA: &str = include_str!("../../../data/reports/1.19.2/registry_codec.json");
+
+/// The entries of one registry in the [`RegistryCodec`], whose ids are their index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodecRegistry<T> {
+    #[serde(rename = "type")]
+    pub key: Identifier,
+    pub value: Vec<CodecEntry<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodecEntry<T> {
+    pub name: Identifier,
+    pub id: i32,
+    pub element: T,
+}
+
+impl<T> CodecRegistry<T> {
+    pub fn new(key: Identifier) -> Self {
+        Self {
+            key,
+            value: Vec::new(),
+        }
+    }
+
+    /// Adds an entry with the next id, replacing the element of an entry with the same name.
+    /// Returns the id of the entry.
+    pub fn insert(&mut self, name: Identifier, element: T) -> i32 {
+        if let Some(entry) = self.value.iter_mut().find(|entry| entry.name == name) {
+            entry.element = element;
+
+            return entry.id;
+        }
+
+        let id = self.value.len() as i32;
+        self.value.push(CodecEntry { name, id, element });
+
+        id
+    }
+
+    pub fn get(&self, name: &Identifier) -> Option<&T> {
+        self.entry(name).map(|entry| &entry.element)
+    }
+
+    /// The id of an entry, which is how chunks refer to biomes.
+    pub fn id(&self, name: &Identifier) -> Option<i32> {
+        self.entry(name).map(|entry| entry.id)
+    }
+
+    fn entry(&self, name: &Identifier) -> Option<&CodecEntry<T>> {
+        self.value.iter().find(|entry| &entry.name == name)
+    }
+}
+
+/// The data-driven registries the client needs to join a world, sent in the Login packet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryCodec {
+    #[serde(rename = "minecraft:dimension_type")]
+    pub dimension_types: CodecRegistry<DimensionType>,
+    #[serde(rename = "minecraft:worldgen/biome")]
+    pub biomes: CodecRegistry<Biome>,
+    /// Sent since 1.19.
+    #[serde(rename = "minecraft:chat_type")]
+    pub chat_types: Option<ChatTypes>,
+}
+
+/// The chat types of the [`RegistryCodec`], whose format changed in 1.19.1.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ChatTypes {
+    Current(CodecRegistry<ChatType>),
+    /// The format of 1.19, which is only sent in that version.
+    Legacy(CodecRegistry<LegacyChatType>),
+}
+
+impl ChatTypes {
+    /// Whether this format is the one of `version`.
+    fn applies_to(&self, version: ProtocolVersion) -> bool {
+        match self {
+            Self::Current(_) => version.since(ProtocolVersion::V1_19_1),
+            Self::Legacy(_) => version.until(ProtocolVersion::V1_19_1),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatTypes {
+    // Read as a tag first, since untagged enums lose the NBT types booleans are read from.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = Tag::deserialize(deserializer)?;
+
+        from_tag(tag.clone())
+            .map(Self::Current)
+            .or_else(|_| from_tag(tag).map(Self::Legacy))
+            .map_err(de::Error::custom)
+    }
+}
+
+nbt_data! {
+    DimensionType {
+        piglin_safe: bool,
+        natural: bool,
+        ambient_light: f32,
+        fixed_time: Option<i64>,
+        // A block tag like `#minecraft:infiniburn_overworld`.
+        infiniburn: String,
+        respawn_anchor_works: bool,
+        has_skylight: bool,
+        bed_works: bool,
+        effects: Identifier,
+        has_raids: bool,
+        min_y: i32,
+        height: i32,
+        logical_height: i32,
+        coordinate_scale: f64,
+        ultrawarm: bool,
+        has_ceiling: bool,
+        // Sent since 1.19.
+        monster_spawn_light_level: Option<IntProvider>,
+        monster_spawn_block_light_limit: Option<i32>
+    };
+    Biome {
+        precipitation: String,
+        temperature: f32,
+        temperature_modifier: Option<String>,
+        downfall: f32,
+        // Sent before 1.19.
+        category: Option<String>,
+        effects: BiomeEffects
+    };
+    BiomeEffects {
+        sky_color: i32,
+        water_fog_color: i32,
+        fog_color: i32,
+        water_color: i32,
+        foliage_color: Option<i32>,
+        grass_color: Option<i32>,
+        grass_color_modifier: Option<String>,
+        particle: Option<BiomeParticle>,
+        ambient_sound: Option<Identifier>,
+        mood_sound: Option<MoodSound>,
+        additions_sound: Option<AdditionsSound>,
+        music: Option<BiomeMusic>
+    };
+    BiomeParticle {
+        probability: f32,
+        options: Compound
+    };
+    MoodSound {
+        sound: Identifier,
+        tick_delay: i32,
+        block_search_extent: i32,
+        offset: f64
+    };
+    AdditionsSound {
+        sound: Identifier,
+        tick_chance: f64
+    };
+    BiomeMusic {
+        sound: Identifier,
+        min_delay: i32,
+        max_delay: i32,
+        replace_current_music: bool
+    };
+    // The format of 1.19.1 and later.
+    ChatType {
+        chat: ChatDecoration,
+        narration: ChatDecoration
+    };
+    // The format of 1.19, where a chat type without `chat` or `overlay` is not shown.
+    LegacyChatType {
+        chat: Option<LegacyChatDisplay>,
+        // Shown above the hotbar.
+        overlay: Option<LegacyChatDisplay>,
+        narration: Option<LegacyNarration>
+    };
+    LegacyChatDisplay {
+        // Messages are shown as they are without one.
+        decoration: Option<ChatDecoration>
+    };
+    LegacyNarration {
+        decoration: Option<ChatDecoration>,
+        // Either `chat` or `system`, which the narrator settings filter by.
+        priority: String
+    };
+    ChatDecoration {
+        translation_key: String,
+        // Which of `sender`, `target` and `content` fill in the translation.
+        parameters: Vec<String>,
+        style: Option<ChatStyle>
+    };
+    ChatStyle {
+        color: Option<String>,
+        bold: Option<bool>,
+        italic: Option<bool>,
+        underlined: Option<bool>,
+        strikethrough: Option<bool>,
+        obfuscated: Option<bool>,
+        insertion: Option<String>,
+        font: Option<Identifier>
+    }
+}
+
+/// An integer which is either constant or picked at random, like the light level monsters
+/// spawn at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "IntProviderRepr", into = "IntProviderRepr")]
+pub enum IntProvider {
+    Constant(i32),
+    Uniform {
+        min_inclusive: i32,
+        max_inclusive: i32,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum IntProviderRepr {
+    Constant(i32),
+    Provider {
+        #[serde(rename = "type")]
+        ty: Identifier,
+        value: UniformInt,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct UniformInt {
+    min_inclusive: i32,
+    max_inclusive: i32,
+}
+
+impl TryFrom<IntProviderRepr> for IntProvider {
+    type Error = String;
+
+    fn try_from(repr: IntProviderRepr) -> Result<Self, String> {
+        match repr {
+            IntProviderRepr::Constant(value) => Ok(Self::Constant(value)),
+            IntProviderRepr::Provider { ty, value }
+                if ty.is_default_namespace() && ty.path() == "uniform" =>
+            {
+                Ok(Self::Uniform {
+                    min_inclusive: value.min_inclusive,
+                    max_inclusive: value.max_inclusive,
+                })
+            }
+            IntProviderRepr::Provider { ty, .. } => Err(format!("unsupported int provider {ty}")),
+        }
+    }
+}
+
+impl From<IntProvider> for IntProviderRepr {
+    fn from(provider: IntProvider) -> Self {
+        match provider {
+            IntProvider::Constant(value) => Self::Constant(value),
+            IntProvider::Uniform {
+                min_inclusive,
+                max_inclusive,
+            } => Self::Provider {
+                ty: Identifier::minecraft("uniform").unwrap(),
+                value: UniformInt {
+                    min_inclusive,
+                    max_inclusive,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct VanillaEntry<T> {
+    name: Identifier,
+    element: T,
+}
+
+#[derive(Deserialize)]
+struct Vanilla {
+    #[serde(rename = "minecraft:dimension_type")]
+    dimension_types: Vec<VanillaEntry<DimensionType>>,
+    #[serde(rename = "minecraft:worldgen/biome")]
+    biomes: Vec<VanillaEntry<Biome>>,
+    #[serde(rename = "minecraft:chat_type")]
+    chat_types: Vec<VanillaEntry<ChatType>>,
+}
+
+fn registry<T>(key: &'static str, entries: Vec<VanillaEntry<T>>) -> CodecRegistry<T> {
+    let mut registry = CodecRegistry::new(Identifier::minecraft(key).unwrap());
+
+    for entry in entries {
+        registry.insert(entry.name, entry.element);
+    }
+
+    registry
+}
+
+impl RegistryCodec {
+    /// The registries of vanilla 1.19.2, which also work for 1.19.1.
+    pub fn vanilla() -> Self {
+        let vanilla: Vanilla =
+            serde_json::from_str(VANILLA).expect("the vanilla registry codec is valid");
+
+        Self {
+            dimension_types: registry("dimension_type", vanilla.dimension_types),
+            biomes: registry("worldgen/biome", vanilla.biomes),
+            chat_types: Some(ChatTypes::Current(registry(
+                "chat_type",
+                vanilla.chat_types,
+            ))),
+        }
+    }
+
+    /// Adds a dimension type, or replaces the one with the same name.
+    pub fn with_dimension_type(mut self, name: Identifier, dimension_type: DimensionType) -> Self {
+        self.dimension_types.insert(name, dimension_type);
+        self
+    }
+
+    /// Adds a biome, or replaces the one with the same name.
+    pub fn with_biome(mut self, name: Identifier, biome: Biome) -> Self {
+        self.biomes.insert(name, biome);
+        self
+    }
+
+    /// Adds a chat type, or replaces the one with the same name.
+    ///
+    /// Chat types in the format of 1.19 are dropped.
+    pub fn with_chat_type(mut self, name: Identifier, chat_type: ChatType) -> Self {
+        if !matches!(self.chat_types, Some(ChatTypes::Current(_))) {
+            self.chat_types = Some(ChatTypes::Current(CodecRegistry::new(
+                Identifier::minecraft("chat_type").unwrap(),
+            )));
+        }
+
+        if let Some(ChatTypes::Current(registry)) = &mut self.chat_types {
+            registry.insert(name, chat_type);
+        }
+
+        self
+    }
+}
+
+// Without a context the chat types are written in whichever format they are in, and read in the
+// one they match.
+impl Encode for RegistryCodec {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        to_compound(self)?.encode(buf)
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if let Some(chat_types) = &self.chat_types {
+            if !chat_types.applies_to(cx.version) {
+                return Err(SerializeError::UnsupportedValue(
+                    "The format of the chat types",
+                    cx.version,
+                ));
+            }
+        }
+
+        to_compound(self)?.encode_with(buf, cx)
+    }
+}
+
+impl Decode for RegistryCodec {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Ok(from_compound(Compound::decode(buf)?)?)
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let codec: Self = from_compound(Compound::decode_with(buf, cx)?)?;
+
+        if let Some(chat_types) = &codec.chat_types {
+            if !chat_types.applies_to(cx.version) {
+                return Err(DeserializeError::UnsupportedValue(
+                    "The format of the chat types",
+                    cx.version,
+                ));
+            }
+        }
+
+        Ok(codec)
+    }
+}
+
+/// The dimension type of the world in Login and Respawn, which is sent in full before 1.19 and
+/// by name since.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DimensionTypeRef {
+    Name(Identifier),
+    Element(Box<DimensionType>),
+}
+
+fn sends_name(version: ProtocolVersion) -> bool {
+    version.since(ProtocolVersion::V1_19)
+}
+
+impl Encode for DimensionTypeRef {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match self {
+            Self::Name(name) if sends_name(cx.version) => name.encode(buf),
+            Self::Element(element) if !sends_name(cx.version) => element.encode_with(buf, cx),
+            Self::Name(_) => Err(SerializeError::UnsupportedValue(
+                "A dimension type name",
+                cx.version,
+            )),
+            Self::Element(_) => Err(SerializeError::UnsupportedValue(
+                "A dimension type element",
+                cx.version,
+            )),
+        }
+    }
+}
+
+impl Decode for DimensionTypeRef {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if sends_name(cx.version) {
+            Ok(Self::Name(Identifier::decode(buf)?))
+        } else {
+            Ok(Self::Element(Box::new(DimensionType::decode_with(
+                buf, cx,
+            )?)))
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use uuid::Uuid;
+
+use crate::{
+    error::{DeRes, DeserializeError},
+    play::{entity_type::EntityType, Face, SpawnEntity},
+    registry::BlockState,
+    types::{varint::VarInt, Angle, Decode},
+};
+
+/// The fastest velocity in blocks per tick the client accepts on each axis.
+pub const MAX_VELOCITY: f64 = 3.9;
+
+/// Converts a velocity in blocks per tick to the units of 1/8000 block per tick it is sent in,
+/// clamped to [`MAX_VELOCITY`] like vanilla.
+pub fn velocity_to_fixed(velocity: f64) -> i16 {
+    (velocity.clamp(-MAX_VELOCITY, MAX_VELOCITY) * 8000.0) as i16
+}
+
+/// Converts a velocity sent in units of 1/8000 block per tick to blocks per tick.
+pub fn velocity_from_fixed(velocity: i16) -> f64 {
+    velocity as f64 / 8000.0
+}
+
+/// The meaning of the `data` field of [`SpawnEntity`], which depends on the type of entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectData {
+    /// The entity does not use the field, which is sent as 0.
+    None,
+    /// The block of a falling block.
+    BlockState(BlockState),
+    /// The direction item frames and paintings face.
+    Direction(Face),
+    /// The entity id of whoever shot or threw a projectile, or cast a fishing bobber.
+    Owner(Option<i32>),
+    /// Whether a warden is emerging from the ground.
+    Emerging(bool),
+}
+
+impl ObjectData {
+    /// Reads the `data` of an entity of type `ty`.
+    pub fn from_raw(ty: EntityType, data: i32) -> DeRes<Self> {
+        Ok(match ty {
+            EntityType::FallingBlock => Self::BlockState(BlockState::from_id(data).ok_or(
+                DeserializeError::InvalidEnumVariant("BlockState", data as isize),
+            )?),
+            EntityType::ItemFrame | EntityType::GlowItemFrame | EntityType::Painting => {
+                let face = u8::try_from(data)
+                    .ok()
+                    .and_then(|data| Face::decode(&mut [data].as_slice()).ok())
+                    .ok_or(DeserializeError::InvalidEnumVariant("Face", data as isize))?;
+
+                Self::Direction(face)
+            }
+            EntityType::Warden => Self::Emerging(data == 1),
+            _ if is_projectile(ty) => Self::Owner((data != 0).then_some(data)),
+            _ => Self::None,
+        })
+    }
+
+    pub fn to_raw(self) -> i32 {
+        match self {
+            Self::None => 0,
+            Self::BlockState(state) => state.id(),
+            Self::Direction(face) => face as i32,
+            Self::Owner(owner) => owner.unwrap_or(0),
+            Self::Emerging(emerging) => emerging as i32,
+        }
+    }
+
+    /// Whether entities of type `ty` read this kind of data.
+    pub fn applies_to(self, ty: EntityType) -> bool {
+        match self {
+            Self::None => true,
+            Self::BlockState(_) => ty == EntityType::FallingBlock,
+            Self::Direction(_) => matches!(
+                ty,
+                EntityType::ItemFrame | EntityType::GlowItemFrame | EntityType::Painting
+            ),
+            Self::Owner(_) => is_projectile(ty),
+            Self::Emerging(_) => ty == EntityType::Warden,
+        }
+    }
+}
+
+/// Entities whose data is the id of their owner.
+fn is_projectile(ty: EntityType) -> bool {
+    matches!(
+        ty,
+        EntityType::Arrow
+            | EntityType::SpectralArrow
+            | EntityType::Trident
+            | EntityType::FishingBobber
+            | EntityType::Snowball
+            | EntityType::Egg
+            | EntityType::EnderPearl
+            | EntityType::ExperienceBottle
+            | EntityType::Potion
+            | EntityType::LlamaSpit
+            | EntityType::ShulkerBullet
+            | EntityType::Fireball
+            | EntityType::SmallFireball
+            | EntityType::DragonFireball
+            | EntityType::WitherSkull
+            | EntityType::FireworkRocket
+    )
+}
+
+impl SpawnEntity {
+    /// Spawns an entity at rest, facing south, without object data.
+    pub fn new(entity_id: i32, uuid: Uuid, ty: EntityType, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            entity_id: VarInt(entity_id),
+            uuid,
+            ty,
+            x,
+            y,
+            z,
+            pitch: Angle::from_degrees(0.0),
+            yaw: Angle::from_degrees(0.0),
+            head_yaw: Angle::from_degrees(0.0),
+            data: VarInt(0),
+            velocity_x: 0,
+            velocity_y: 0,
+            velocity_z: 0,
+        }
+    }
+
+    pub fn object_data(&self) -> DeRes<ObjectData> {
+        ObjectData::from_raw(self.ty, self.data.0)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the type of entity does not read this kind of data.
+    pub fn set_object_data(&mut self, data: ObjectData) -> &mut Self {
+        assert!(
+            data.applies_to(self.ty),
+            "{:?} does not take {data:?}",
+            self.ty
+        );
+
+        self.data = VarInt(data.to_raw());
+        self
+    }
+
+    /// The velocity in blocks per tick.
+    pub fn velocity(&self) -> [f64; 3] {
+        [self.velocity_x, self.velocity_y, self.velocity_z].map(velocity_from_fixed)
+    }
+
+    /// Sets the velocity in blocks per tick, clamping each axis to [`MAX_VELOCITY`].
+    pub fn set_velocity(&mut self, [x, y, z]: [f64; 3]) -> &mut Self {
+        self.velocity_x = velocity_to_fixed(x);
+        self.velocity_y = velocity_to_fixed(y);
+        self.velocity_z = velocity_to_fixed(z);
+        self
+    }
+}
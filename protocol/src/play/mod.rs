@@ -6,48 +6,73 @@ pub use clientbound::*;
 pub use common::*;
 pub use serverbound::*;
 
-use crate::macros::state;
+use crate::{macros::state, packet_ids::play as ids};
 
 state! {
-    Play;
+    // Packets with a range after their ids changed layout in later versions in ways not modeled
+    // here, so they are only sent within that range.
+    Play(V1_18..=V1_21_7);
     serverbound {
-        0x00 => ConfirmTeleportation,
-        0x01 => QueryBlockEntityTag,
-        0x02 => ChangeDifficulty,
-        0x03 => ChatCommand,
-        0x04 => ChatMessage,
-        0x05 => ChatPreview,
-        0x06 => ClientCommand,
-        0x07 => ClientInformation,
-        0x08 => CommandSuggestionsRequest,
-        0x09 => ClickContainerButton,
-        0x0a => ClickContainer,
-        0x0b => CloseContainer,
-        0x0c => ServerboundPluginMessage,
-        0x0d => EditBook,
-        0x0e => QueryEntityTag,
-        0x0f => Interact,
-        0x10 => JigsawGenerate,
-        0x11 => ServerboundKeepAlive,
-        0x12 => LockDifficulty,
-        0x13 => SetPlayerPosition,
-        0x14 => SetPlayerPositionAndRotation,
-        0x15 => SetPlayerRotation,
-        0x16 => SetPlayerOnGround,
-        0x17 => ServerboundMoveVehicle,
-        0x18 => PaddleBoat,
-        0x19 => PickItem,
-        0x1a => PlaceRecipe,
-        0x1b => PlayerAbilities,
-        0x1c => PlayerAction,
-        0x1d => PlayerCommand,
-        0x1e => PlayerInput,
-        0x1f => Pong
+        ConfirmTeleportation(ids::serverbound::ACCEPT_TELEPORTATION),
+        QueryBlockEntityTag(ids::serverbound::BLOCK_ENTITY_TAG_QUERY),
+        ChangeDifficulty(ids::serverbound::CHANGE_DIFFICULTY),
+        MessageAcknowledgment(ids::serverbound::CHAT_ACK),
+        ChatCommand(ids::serverbound::CHAT_COMMAND, ..V1_19_3),
+        ChatMessage(ids::serverbound::CHAT, ..V1_19_3),
+        ChatPreview(ids::serverbound::CHAT_PREVIEW),
+        ClientCommand(ids::serverbound::CLIENT_COMMAND),
+        ClientInformation(ids::serverbound::CLIENT_INFORMATION),
+        CommandSuggestionsRequest(ids::serverbound::COMMAND_SUGGESTION),
+        ClickContainerButton(ids::serverbound::CONTAINER_BUTTON_CLICK, ..V1_21_2),
+        ClickContainer(ids::serverbound::CONTAINER_CLICK, ..V1_20_2),
+        CloseContainer(ids::serverbound::CONTAINER_CLOSE, ..V1_21_2),
+        ServerboundPluginMessage(ids::serverbound::CUSTOM_PAYLOAD),
+        EditBook(ids::serverbound::EDIT_BOOK),
+        QueryEntityTag(ids::serverbound::ENTITY_TAG_QUERY),
+        Interact(ids::serverbound::INTERACT),
+        JigsawGenerate(ids::serverbound::JIGSAW_GENERATE),
+        ServerboundKeepAlive(ids::serverbound::KEEP_ALIVE),
+        LockDifficulty(ids::serverbound::LOCK_DIFFICULTY),
+        SetPlayerPosition(ids::serverbound::MOVE_PLAYER_POS, ..V1_21_2),
+        SetPlayerPositionAndRotation(ids::serverbound::MOVE_PLAYER_POS_ROT, ..V1_21_2),
+        SetPlayerRotation(ids::serverbound::MOVE_PLAYER_ROT, ..V1_21_2),
+        SetPlayerOnGround(ids::serverbound::MOVE_PLAYER_STATUS_ONLY, ..V1_21_2),
+        ServerboundMoveVehicle(ids::serverbound::MOVE_VEHICLE, ..V1_21_2),
+        PaddleBoat(ids::serverbound::PADDLE_BOAT),
+        PickItem(ids::serverbound::PICK_ITEM),
+        PlaceRecipe(ids::serverbound::PLACE_RECIPE, ..V1_21_2),
+        PlayerAbilities(ids::serverbound::PLAYER_ABILITIES),
+        PlayerAction(ids::serverbound::PLAYER_ACTION),
+        PlayerCommand(ids::serverbound::PLAYER_COMMAND, ..V1_21_6),
+        PlayerInput(ids::serverbound::PLAYER_INPUT, ..V1_21_2),
+        Pong(ids::serverbound::PONG),
+        ConfigurationAcknowledged(ids::serverbound::CONFIGURATION_ACKNOWLEDGED)
     };
     clientbound {
-        0x00 => SpawnEntity,
-        0x01 => SpawnExperienceOrb,
-        0x02 => SpawnPlayer,
-        0x03 => EntityAnimation
+        // Mobs are spawned with this packet since 1.19, which changed its layout.
+        SpawnEntity(ids::clientbound::ADD_ENTITY, V1_19..),
+        SpawnExperienceOrb(ids::clientbound::ADD_EXPERIENCE_ORB),
+        SpawnPlayer(ids::clientbound::ADD_PLAYER),
+        EntityAnimation(ids::clientbound::ANIMATE),
+        BlockDestroyStage(ids::clientbound::BLOCK_DESTRUCTION),
+        BlockEntityData(ids::clientbound::BLOCK_ENTITY_DATA, ..V1_20_2),
+        BlockAction(ids::clientbound::BLOCK_EVENT),
+        BlockUpdate(ids::clientbound::BLOCK_UPDATE),
+        ChunkDataAndUpdateLight(ids::clientbound::LEVEL_CHUNK_WITH_LIGHT, ..V1_20_2),
+        UpdateLight(ids::clientbound::LIGHT_UPDATE),
+        UpdateSectionBlocks(ids::clientbound::SECTION_BLOCKS_UPDATE),
+        SetEntityMetadata(ids::clientbound::SET_ENTITY_DATA),
+        UpdateEntityPosition(ids::clientbound::MOVE_ENTITY_POS),
+        UpdateEntityPositionAndRotation(ids::clientbound::MOVE_ENTITY_POS_ROT),
+        UpdateEntityRotation(ids::clientbound::MOVE_ENTITY_ROT),
+        SetHeadRotation(ids::clientbound::ROTATE_HEAD),
+        TeleportEntity(ids::clientbound::TELEPORT_ENTITY, ..V1_21_2),
+        SetEntityVelocity(ids::clientbound::SET_ENTITY_MOTION),
+        RemoveEntities(ids::clientbound::REMOVE_ENTITIES),
+        LoginPlay(ids::clientbound::LOGIN, ..V1_19_4),
+        Respawn(ids::clientbound::RESPAWN, ..V1_19_3),
+        Commands(ids::clientbound::COMMANDS, ..V1_19_3),
+        CommandSuggestionsResponse(ids::clientbound::COMMAND_SUGGESTIONS),
+        StartConfiguration(ids::clientbound::START_CONFIGURATION)
     }
 }
@@ -1,45 +1,70 @@
-use bytes::BufMut;
+use bytes::{Buf, BufMut};
+use uuid::Uuid;
 
 use crate::{
     bitflags,
     error::DeserializeError,
     macros::{data, int_enum, packets, varint_enum},
-    types::{position::Position, slot::Slot, varint::VarInt, InferredLenByteArray, Serialize},
+    types::{
+        identifier::Identifier, position::Position, slot::Slot, varint::VarInt, ByteArray, Decode,
+        Encode, InferredLenByteArray,
+    },
 };
 
 packets! {
-    ConfirmTeleportation(0x00) {
+    ConfirmTeleportation {
         id: VarInt
     };
-    QueryBlockEntityTag(0x01) {
+    QueryBlockEntityTag {
         id: VarInt,
         location: Position
     };
-    ChangeDifficulty(0x02) {
+    ChangeDifficulty {
         new_difficulty: Difficulty
     };
-    ChatCommand(0x03) {
+    MessageAcknowledgment {
+        #[until(V1_19_3)]
+        last_seen_messages: Vec<LastSeenMessage>,
+        #[until(V1_19_3)]
+        last_received_message: Option<LastSeenMessage>,
+        // The number of messages seen since the last acknowledgment.
+        #[since(V1_19_3)]
+        message_count: VarInt
+    };
+    ChatCommand {
         command: String,
         timestamp: i64,
         salt: i64,
         arg_signatures: Vec<ArgumentSignature>,
-        signed_preview: bool
+        signed_preview: bool,
+        #[since(V1_19_1)]
+        last_seen_messages: Vec<LastSeenMessage>,
+        #[since(V1_19_1)]
+        last_received_message: Option<LastSeenMessage>
     };
-    ChatMessage(0x04) {
+    ChatMessage {
         message: String,
+        #[since(V1_19)]
         timestamp: i64,
+        #[since(V1_19)]
         salt: i64,
-        signature: Vec<u8>,
-        signed_preview: bool
-    };
-    ChatPreview(0x05) {
+        #[since(V1_19)]
+        signature: ByteArray,
+        #[since(V1_19)]
+        signed_preview: bool,
+        #[since(V1_19_1)]
+        last_seen_messages: Vec<LastSeenMessage>,
+        #[since(V1_19_1)]
+        last_received_message: Option<LastSeenMessage>
+    };
+    ChatPreview {
         query: i32,
         message: String
     };
-    ClientCommand(0x06) {
+    ClientCommand {
         id: ActionId
     };
-    ClientInformation(0x07) {
+    ClientInformation {
         locale: String,
         view_distance: u8,
         chat_mode: ChatMode,
@@ -47,17 +72,19 @@ packets! {
         displayed_skin_parts: SkinParts,
         main_hand: MainHand,
         enable_text_filtering: bool,
-        allow_server_listings: bool
+        allow_server_listings: bool,
+        #[since(V1_21_2)]
+        particle_status: ParticleStatus
     };
-    CommandSuggestionsRequest(0x08) {
+    CommandSuggestionsRequest {
         id: VarInt,
         text: String
     };
-    ClickContainerButton(0x09) {
+    ClickContainerButton {
         window_id: u8,
         button_id: u8
     };
-    ClickContainer(0x0a) {
+    ClickContainer {
         window_id: u8,
         state_id: VarInt,
         slot: i16,
@@ -66,45 +93,45 @@ packets! {
         slots: (i16, Slot),
         carried_item: Slot
     };
-    CloseContainer(0x0b) {
+    CloseContainer {
         window_id: u8
     };
-    ServerboundPluginMessage(0x0c) {
-        channel: String,
+    ServerboundPluginMessage {
+        channel: Identifier,
         data: InferredLenByteArray
     };
-    EditBook(0x0d) {
+    EditBook {
         slot: VarInt,
         entries: Vec<String>,
         title: Option<String>
     };
-    QueryEntityTag(0x0e) {
+    QueryEntityTag {
         transaction_id: VarInt,
         entity_id: VarInt
     };
-    Interact(0x0f) {
+    Interact {
         entity_id: VarInt,
         ty: InteractionType,
         sneaking: bool
     };
-    JigsawGenerate(0x10) {
+    JigsawGenerate {
         position: Position,
         levels: VarInt,
         keep_jigsaws: bool
     };
-    ServerboundKeepAlive(0x11) {
+    ServerboundKeepAlive {
         id: i64
     };
-    LockDifficulty(0x12) {
+    LockDifficulty {
         locked: bool
     };
-    SetPlayerPosition(0x13) {
+    SetPlayerPosition {
         x: f64,
         feet_y: f64,
         z: f64,
         on_ground: bool
     };
-    SetPlayerPositionAndRotation(0x14) {
+    SetPlayerPositionAndRotation {
         x: f64,
         feet_y: f64,
         z: f64,
@@ -112,56 +139,57 @@ packets! {
         pitch: f32,
         on_ground: bool
     };
-    SetPlayerRotation(0x15) {
+    SetPlayerRotation {
         yaw: f32,
         pitch: f32,
         on_ground: bool
     };
-    SetPlayerOnGround(0x16) {
+    SetPlayerOnGround {
         on_ground: bool
     };
-    ServerboundMoveVehicle(0x17) {
+    ServerboundMoveVehicle {
         x: f64,
         y: f64,
         z: f64,
         yaw: f32,
         pitch: f32
     };
-    PaddleBoat(0x18) {
+    PaddleBoat {
         left_paddle: bool,
         right_paddle: bool
     };
-    PickItem(0x19) {
+    PickItem {
         slot: VarInt
     };
-    PlaceRecipe(0x1a) {
+    PlaceRecipe {
         window_id: i8,
-        // TODO: Identifier
-        recipe: String,
+        recipe: Identifier,
         make_all: bool
     };
-    PlayerAbilities(0x1b) {
+    PlayerAbilities {
         flags: PlayerAbilityFlags
     };
-    PlayerAction(0x1c) {
+    PlayerAction {
         status: PlayerActionStatus,
         position: Position,
         face: Face,
+        #[since(V1_19)]
         sequence: VarInt
     };
-    PlayerCommand(0x1d) {
+    PlayerCommand {
         player_id: VarInt,
         action_id: PlayerCommandAction,
         jump_boost: VarInt
     };
-    PlayerInput(0x1e) {
+    PlayerInput {
         sideways: f32,
         forward: f32,
         flags: PlayerInputFlags
     };
-    Pong(0x1f) {
+    Pong {
         id: i32
-    }
+    };
+    ConfigurationAcknowledged
 }
 
 int_enum! {
@@ -195,6 +223,13 @@ varint_enum! {
         Left = 0,
         Right = 1
     };
+    #[derive(Default)]
+    ParticleStatus {
+        #[default]
+        All = 0,
+        Decreased = 1,
+        Minimal = 2
+    };
     InventoryOperationMode {
         MouseClick = 0,
         ShiftMouseClick = 1,
@@ -233,7 +268,11 @@ varint_enum! {
 data! {
     ArgumentSignature {
         name: String,
-        signature: Vec<u8>
+        signature: ByteArray
+    };
+    LastSeenMessage {
+        sender: Uuid,
+        signature: ByteArray
     }
 }
 
@@ -268,12 +307,12 @@ pub enum InteractionType {
     },
 }
 
-impl Serialize for InteractionType {
-    fn serialize(&self, buf: &mut bytes::BytesMut) -> crate::error::SerRes<()> {
+impl Encode for InteractionType {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> crate::error::SerRes<()> {
         match self {
             InteractionType::Interact(hand) => {
                 buf.put_u8(0);
-                hand.serialize(buf)?;
+                hand.encode(buf)?;
             }
             InteractionType::Attack => buf.put_u8(1),
             InteractionType::InteractAt {
@@ -283,33 +322,32 @@ impl Serialize for InteractionType {
                 hand,
             } => {
                 buf.put_u8(2);
-                target_x.serialize(buf)?;
-                target_y.serialize(buf)?;
-                target_z.serialize(buf)?;
-                hand.serialize(buf)?;
+                target_x.encode(buf)?;
+                target_y.encode(buf)?;
+                target_z.encode(buf)?;
+                hand.encode(buf)?;
             }
         }
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut bytes::BytesMut) -> crate::error::DeRes<Self>
-    where
-        Self: Sized,
-    {
-        let variant = u8::deserialize(buf)?;
+impl Decode for InteractionType {
+    fn decode<B: Buf>(buf: &mut B) -> crate::error::DeRes<Self> {
+        let variant = u8::decode(buf)?;
 
         match variant {
             0 => {
-                let hand = Hand::deserialize(buf)?;
+                let hand = Hand::decode(buf)?;
                 Ok(Self::Interact(hand))
             }
             1 => Ok(Self::Attack),
             2 => {
-                let target_x = f32::deserialize(buf)?;
-                let target_y = f32::deserialize(buf)?;
-                let target_z = f32::deserialize(buf)?;
-                let hand = Hand::deserialize(buf)?;
+                let target_x = f32::decode(buf)?;
+                let target_y = f32::decode(buf)?;
+                let target_z = f32::decode(buf)?;
+                let hand = Hand::decode(buf)?;
 
                 Ok(Self::InteractAt {
                     target_x,
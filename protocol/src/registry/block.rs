@@ -0,0 +1,41 @@
+use std::ops::RangeInclusive;
+
+use super::{Block, BLOCK_LIGHT, BLOCK_STATES};
+
+impl Block {
+    /// Returns the global palette ids of all states of this block.
+    pub fn states(self) -> RangeInclusive<i32> {
+        let (_, min, max, _) = BLOCK_STATES[self as usize];
+
+        min..=max
+    }
+
+    pub fn default_state(self) -> i32 {
+        BLOCK_STATES[self as usize].3
+    }
+
+    /// The light level the block emits, in its default state.
+    pub fn light_emission(self) -> u8 {
+        BLOCK_LIGHT[self as usize].0
+    }
+
+    /// How much light passing through the block is reduced, from 0 for transparent blocks to 15
+    /// for blocks which let no light through.
+    pub fn light_opacity(self) -> u8 {
+        BLOCK_LIGHT[self as usize].1
+    }
+
+    pub fn is_air(self) -> bool {
+        matches!(self, Self::Air | Self::CaveAir | Self::VoidAir)
+    }
+
+    /// Looks up the block a global palette id belongs to.
+    pub fn from_state(state: i32) -> Option<Self> {
+        let index = BLOCK_STATES.partition_point(|(_, min, _, _)| *min <= state);
+
+        BLOCK_STATES[..index]
+            .last()
+            .filter(|(_, _, max, _)| state <= *max)
+            .map(|(block, _, _, _)| *block)
+    }
+}
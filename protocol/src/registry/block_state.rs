@@ -0,0 +1,187 @@
+use std::{fmt, str::FromStr};
+
+use bytes::{Buf, BufMut};
+
+use crate::{
+    error::{BlockStateError, DeRes, DeserializeError, SerRes, SerializeError},
+    types::{varint::VarInt, Context, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+use super::{Block, BLOCK_PROPERTIES, BLOCK_STATE_IDS};
+
+/// A block with a value for each of its properties, identified by its global palette id.
+///
+/// Written as `minecraft:oak_stairs[facing=north,half=top]`, where omitted properties take
+/// their default value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct BlockState(i32);
+
+impl BlockState {
+    pub fn from_id(id: i32) -> Option<Self> {
+        Block::from_state(id).map(|_| Self(id))
+    }
+
+    /// The global palette id.
+    pub fn id(self) -> i32 {
+        self.0
+    }
+
+    /// The global palette id in `version`, or `None` if the block does not exist there or the
+    /// blocks of that version are not known.
+    pub fn id_in(self, version: ProtocolVersion) -> Option<i32> {
+        super::id_in(BLOCK_STATE_IDS, self.0, version)
+    }
+
+    /// Looks up a state by its global palette id in `version`.
+    pub fn from_id_in(id: i32, version: ProtocolVersion) -> Option<Self> {
+        super::from_id_in(BLOCK_STATE_IDS, id, version).and_then(Self::from_id)
+    }
+
+    pub fn block(self) -> Block {
+        Block::from_state(self.0).unwrap()
+    }
+
+    pub fn is_default(self) -> bool {
+        self.0 == self.block().default_state()
+    }
+
+    pub fn is_air(self) -> bool {
+        self.block().is_air()
+    }
+
+    /// Returns the properties of the block with their values, in the order of the reports.
+    pub fn properties(self) -> impl Iterator<Item = (&'static str, &'static str)> {
+        let block = self.block();
+        let properties = BLOCK_PROPERTIES[block as usize];
+        let mut offset = (self.0 - block.states().start()) as usize;
+        let mut values = vec![""; properties.len()];
+
+        for (value, (_, allowed)) in values.iter_mut().zip(properties).rev() {
+            *value = allowed[offset % allowed.len()];
+            offset /= allowed.len();
+        }
+
+        properties.iter().map(|(name, _)| *name).zip(values)
+    }
+
+    pub fn property(self, name: &str) -> Option<&'static str> {
+        self.properties()
+            .find(|(property, _)| *property == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns this state with one property changed.
+    pub fn with_property(self, name: &str, value: &str) -> Result<Self, BlockStateError> {
+        let block = self.block();
+        let properties = BLOCK_PROPERTIES[block as usize];
+        let index = properties
+            .iter()
+            .position(|(property, _)| *property == name)
+            .ok_or_else(|| BlockStateError::UnknownProperty(block.to_string(), name.to_owned()))?;
+
+        let (_, allowed) = properties[index];
+        let new = allowed
+            .iter()
+            .position(|allowed| *allowed == value)
+            .ok_or_else(|| BlockStateError::InvalidValue(name.to_owned(), value.to_owned()))?;
+
+        let stride: usize = properties[index + 1..]
+            .iter()
+            .map(|(_, values)| values.len())
+            .product();
+        let offset = (self.0 - block.states().start()) as usize;
+        let old = offset / stride % allowed.len();
+
+        Ok(Self(self.0 + (new as i32 - old as i32) * stride as i32))
+    }
+}
+
+impl From<Block> for BlockState {
+    /// Returns the default state of the block.
+    fn from(block: Block) -> Self {
+        Self(block.default_state())
+    }
+}
+
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.block())?;
+
+        for (i, (name, value)) in self.properties().enumerate() {
+            let prefix = if i == 0 { '[' } else { ',' };
+
+            write!(f, "{prefix}{name}={value}")?;
+        }
+
+        if self.properties().next().is_some() {
+            f.write_str("]")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for BlockState {
+    type Err = BlockStateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (block, properties) = match s.split_once('[') {
+            Some((block, properties)) => (
+                block,
+                properties
+                    .strip_suffix(']')
+                    .ok_or_else(|| BlockStateError::Invalid(s.to_owned()))?,
+            ),
+            None => (s, ""),
+        };
+
+        let mut state = Self::from(block.parse::<Block>()?);
+
+        for property in properties
+            .split(',')
+            .filter(|property| !property.is_empty())
+        {
+            let (name, value) = property
+                .split_once('=')
+                .ok_or_else(|| BlockStateError::Invalid(s.to_owned()))?;
+
+            state = state.with_property(name, value)?;
+        }
+
+        Ok(state)
+    }
+}
+
+impl Encode for BlockState {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        let id = self
+            .id_in(cx.version)
+            .ok_or(SerializeError::UnsupportedValue("BlockState", cx.version))?;
+
+        VarInt(id).encode(buf)
+    }
+}
+
+impl Decode for BlockState {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let id = VarInt::decode(buf)?.0;
+
+        if !super::supports(cx.version) {
+            return Err(DeserializeError::UnsupportedValue("BlockState", cx.version));
+        }
+
+        Self::from_id_in(id, cx.version).ok_or(DeserializeError::InvalidEnumVariant(
+            "BlockState",
+            id as isize,
+        ))
+    }
+}
@@ -0,0 +1,158 @@
+use super::{EntityType, ENTITY_INFO};
+
+/// The spawn group of an entity type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityCategory {
+    Hostile,
+    Passive,
+    Projectile,
+    Vehicle,
+    /// Decorations like paintings and item frames.
+    Immobile,
+    Misc,
+}
+
+impl EntityType {
+    /// The width of the hitbox in blocks.
+    pub fn width(self) -> f32 {
+        ENTITY_INFO[self as usize].0
+    }
+
+    /// The height of the hitbox in blocks.
+    pub fn height(self) -> f32 {
+        ENTITY_INFO[self as usize].1
+    }
+
+    pub fn category(self) -> EntityCategory {
+        ENTITY_INFO[self as usize].2
+    }
+}
+
+impl EntityType {
+    /// Whether the entity has health and the metadata of living entities.
+    pub fn is_living(self) -> bool {
+        self.is_mob() || matches!(self, Self::ArmorStand | Self::Player)
+    }
+
+    /// Whether the entity is a mob, which can have AI.
+    pub fn is_mob(self) -> bool {
+        matches!(
+            self,
+            Self::Allay
+                | Self::Axolotl
+                | Self::Bat
+                | Self::Bee
+                | Self::Blaze
+                | Self::Cat
+                | Self::CaveSpider
+                | Self::Chicken
+                | Self::Cod
+                | Self::Cow
+                | Self::Creeper
+                | Self::Dolphin
+                | Self::Donkey
+                | Self::Drowned
+                | Self::ElderGuardian
+                | Self::EnderDragon
+                | Self::Enderman
+                | Self::Endermite
+                | Self::Evoker
+                | Self::Fox
+                | Self::Frog
+                | Self::Ghast
+                | Self::Giant
+                | Self::GlowSquid
+                | Self::Goat
+                | Self::Guardian
+                | Self::Hoglin
+                | Self::Horse
+                | Self::Husk
+                | Self::Illusioner
+                | Self::IronGolem
+                | Self::Llama
+                | Self::MagmaCube
+                | Self::Mule
+                | Self::Mooshroom
+                | Self::Ocelot
+                | Self::Panda
+                | Self::Parrot
+                | Self::Phantom
+                | Self::Pig
+                | Self::Piglin
+                | Self::PiglinBrute
+                | Self::Pillager
+                | Self::PolarBear
+                | Self::Pufferfish
+                | Self::Rabbit
+                | Self::Ravager
+                | Self::Salmon
+                | Self::Sheep
+                | Self::Shulker
+                | Self::Silverfish
+                | Self::Skeleton
+                | Self::SkeletonHorse
+                | Self::Slime
+                | Self::SnowGolem
+                | Self::Spider
+                | Self::Squid
+                | Self::Stray
+                | Self::Strider
+                | Self::Tadpole
+                | Self::TraderLlama
+                | Self::TropicalFish
+                | Self::Turtle
+                | Self::Vex
+                | Self::Villager
+                | Self::Vindicator
+                | Self::WanderingTrader
+                | Self::Warden
+                | Self::Witch
+                | Self::Wither
+                | Self::WitherSkeleton
+                | Self::Wolf
+                | Self::Zoglin
+                | Self::Zombie
+                | Self::ZombieHorse
+                | Self::ZombieVillager
+                | Self::ZombifiedPiglin
+        )
+    }
+
+    /// Whether the entity is an animal or villager, which can be a baby.
+    ///
+    /// Zombies, piglins and zoglins can be babies too, but are not ageable mobs.
+    pub fn is_ageable(self) -> bool {
+        matches!(
+            self,
+            Self::Axolotl
+                | Self::Bee
+                | Self::Cat
+                | Self::Chicken
+                | Self::Cow
+                | Self::Donkey
+                | Self::Fox
+                | Self::Frog
+                | Self::Goat
+                | Self::Hoglin
+                | Self::Horse
+                | Self::Llama
+                | Self::Mooshroom
+                | Self::Mule
+                | Self::Ocelot
+                | Self::Panda
+                | Self::Parrot
+                | Self::Pig
+                | Self::PolarBear
+                | Self::Rabbit
+                | Self::Sheep
+                | Self::SkeletonHorse
+                | Self::Strider
+                | Self::TraderLlama
+                | Self::Turtle
+                | Self::Villager
+                | Self::WanderingTrader
+                | Self::Wolf
+                | Self::ZombieHorse
+        )
+    }
+}
@@ -0,0 +1,95 @@
+//! The built-in registries of the game.
+//!
+//! Every registry is an enum generated from the data generator reports. Its variants can be
+//! looked up by their protocol id or their name, and are written as a [`VarInt`] on the wire.
+//!
+//! The protocol ids of the variants are the ones of the newest version with reports. Older
+//! versions get their ids remapped when encoding and decoding with a [`Context`], and versions
+//! without reports cannot carry registry values at all.
+//!
+//! [`Context`]: crate::types::Context
+//! [`VarInt`]: crate::types::varint::VarInt
+
+mod block;
+mod block_state;
+mod entity_type;
+
+pub use block_state::BlockState;
+pub use entity_type::EntityCategory;
+
+use crate::{macros::registry, types::identifier::Identifier, version::ProtocolVersion};
+
+/// The ids of a registry in the versions where they differ from the newest one, as the ids in
+/// that version by the newest ids, and the newest ids by the ids in that version.
+type VersionIds = &'static [(ProtocolVersion, &'static [i32], &'static [i32])];
+
+include!(concat!(env!("OUT_DIR"), "/registries.rs"));
+
+/// Whether the ids of the registries in `version` are known.
+pub(crate) fn supports(version: ProtocolVersion) -> bool {
+    REGISTRY_VERSIONS.contains(&version)
+}
+
+/// Maps the newest id to the one in `version`, or `None` if the entry does not exist in it.
+pub(crate) fn id_in(ids: VersionIds, id: i32, version: ProtocolVersion) -> Option<i32> {
+    remap(ids, id, version, true)
+}
+
+/// Maps an id in `version` to the newest one, or `None` if it is not known.
+pub(crate) fn from_id_in(ids: VersionIds, id: i32, version: ProtocolVersion) -> Option<i32> {
+    remap(ids, id, version, false)
+}
+
+fn remap(ids: VersionIds, id: i32, version: ProtocolVersion, to_version: bool) -> Option<i32> {
+    if !supports(version) {
+        return None;
+    }
+
+    match ids.iter().find(|(other, ..)| *other == version) {
+        Some((_, to, from)) => usize::try_from(id)
+            .ok()
+            .and_then(|id| if to_version { to } else { from }.get(id))
+            .copied()
+            .filter(|id| *id >= 0),
+        None => Some(id),
+    }
+}
+
+pub trait Registry: Sized + Copy + 'static {
+    /// The name of the registry, like `minecraft:item`.
+    const KEY: &'static str;
+    /// All entries, indexed by their protocol id.
+    const ALL: &'static [Self];
+
+    fn id(self) -> i32;
+
+    /// The path of the name of this entry in the `minecraft` namespace.
+    fn path(self) -> &'static str;
+
+    fn from_path(path: &str) -> Option<Self>;
+
+    fn from_id(id: i32) -> Option<Self> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| Self::ALL.get(id))
+            .copied()
+    }
+
+    /// The protocol id of this entry in `version`, or `None` if it does not exist there or the
+    /// registry of that version is not known.
+    fn id_in(self, version: ProtocolVersion) -> Option<i32>;
+
+    /// Looks up an entry by its protocol id in `version`.
+    fn from_id_in(id: i32, version: ProtocolVersion) -> Option<Self>;
+
+    fn identifier(self) -> Identifier {
+        Identifier::minecraft(self.path()).unwrap()
+    }
+
+    fn from_identifier(identifier: &Identifier) -> Option<Self> {
+        identifier
+            .is_default_namespace()
+            .then(|| Self::from_path(identifier.path()))
+            .flatten()
+    }
+}
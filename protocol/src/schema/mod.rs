@@ -0,0 +1,337 @@
+//! Packet definitions loaded at runtime.
+//!
+//! A [`Schema`] describes the packets of one protocol version as JSON, using the primitive types
+//! of [`types`](crate::types). It decodes packets into a dynamic [`Value`] tree and encodes them
+//! back, which allows handling versions that have no compile-time definitions yet:
+//!
+//! ```json
+//! {
+//!     "version": "1.19",
+//!     "states": {
+//!         "status": {
+//!             "serverbound": [
+//!                 { "id": 1, "name": "PingRequest", "fields": [{ "name": "payload", "type": "i64" }] }
+//!             ]
+//!         }
+//!     }
+//! }
+//! ```
+
+mod value;
+
+pub use value::Value;
+
+use std::collections::HashMap;
+
+use bytes::{Buf, BufMut};
+use serde::Deserialize;
+
+use crate::{
+    chat::Chat,
+    error::{DeRes, DeserializeError, SerRes, SerializeError},
+    types::{
+        identifier::Identifier, position::Position, raw_nbt::RawNbt, varint::VarInt,
+        varint::VarLong, ByteArray, Context, Decode, Encode, InferredLenByteArray,
+    },
+    version::ProtocolVersion,
+    Bound, State,
+};
+
+/// The packets of a single protocol version.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Schema {
+    pub version: ProtocolVersion,
+    #[serde(default)]
+    pub states: HashMap<State, StateSchema>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StateSchema {
+    #[serde(default)]
+    pub serverbound: Vec<PacketSchema>,
+    #[serde(default)]
+    pub clientbound: Vec<PacketSchema>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PacketSchema {
+    pub id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: FieldType,
+}
+
+/// The type of a field.
+///
+/// Primitives are written as strings like `"varint"`, compound types as objects like
+/// `{ "option": "string" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    I64,
+    F32,
+    F64,
+    #[serde(rename = "varint")]
+    VarInt,
+    #[serde(rename = "varlong")]
+    VarLong,
+    String,
+    Uuid,
+    Identifier,
+    Angle,
+    Position,
+    /// A byte array prefixed with its length.
+    ByteArray,
+    /// The remaining bytes of the packet.
+    RestBuffer,
+    Nbt,
+    Chat,
+    /// A value prefixed with a bool which tells whether it is present.
+    Option(Box<FieldType>),
+    /// A list prefixed with its length as a [`VarInt`].
+    Array(Box<FieldType>),
+    Container(Vec<Field>),
+}
+
+/// A packet decoded with a [`Schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicPacket {
+    pub name: String,
+    /// The fields of the packet as a [`Value::Struct`].
+    pub value: Value,
+}
+
+impl Schema {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn packets(&self, bound: Bound, state: State) -> &[PacketSchema] {
+        self.states.get(&state).map_or(&[], |schema| match bound {
+            Bound::Serverbound => &schema.serverbound,
+            Bound::Clientbound => &schema.clientbound,
+        })
+    }
+
+    pub fn packet(&self, bound: Bound, state: State, id: i32) -> Option<&PacketSchema> {
+        self.packets(bound, state)
+            .iter()
+            .find(|packet| packet.id == id)
+    }
+
+    pub fn packet_by_name(&self, bound: Bound, state: State, name: &str) -> Option<&PacketSchema> {
+        self.packets(bound, state)
+            .iter()
+            .find(|packet| packet.name == name)
+    }
+
+    pub fn decode<B: Buf>(
+        &self,
+        bound: Bound,
+        state: State,
+        id: i32,
+        buf: &mut B,
+    ) -> DeRes<DynamicPacket> {
+        let packet = self
+            .packet(bound, state, id)
+            .ok_or(DeserializeError::UnknownPacketId(bound, state, id))?;
+
+        Ok(DynamicPacket {
+            name: packet.name.clone(),
+            value: decode_fields(&packet.fields, buf, &Context::new(self.version))?,
+        })
+    }
+
+    /// Encodes a packet and returns its id and data.
+    pub fn encode(
+        &self,
+        bound: Bound,
+        state: State,
+        packet: &DynamicPacket,
+    ) -> SerRes<(i32, Vec<u8>)> {
+        let schema = self
+            .packet_by_name(bound, state, &packet.name)
+            .ok_or_else(|| SerializeError::UnknownPacket(packet.name.clone()))?;
+
+        let mut data = Vec::new();
+
+        encode_fields(
+            &schema.fields,
+            &packet.value,
+            &mut data,
+            &Context::new(self.version),
+        )?;
+
+        Ok((schema.id, data))
+    }
+}
+
+impl FieldType {
+    /// The name of this type as used in the schema.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldType::Bool => "bool",
+            FieldType::I8 => "i8",
+            FieldType::U8 => "u8",
+            FieldType::I16 => "i16",
+            FieldType::U16 => "u16",
+            FieldType::I32 => "i32",
+            FieldType::I64 => "i64",
+            FieldType::F32 => "f32",
+            FieldType::F64 => "f64",
+            FieldType::VarInt => "varint",
+            FieldType::VarLong => "varlong",
+            FieldType::String => "string",
+            FieldType::Uuid => "uuid",
+            FieldType::Identifier => "identifier",
+            FieldType::Angle => "angle",
+            FieldType::Position => "position",
+            FieldType::ByteArray => "byte_array",
+            FieldType::RestBuffer => "rest_buffer",
+            FieldType::Nbt => "nbt",
+            FieldType::Chat => "chat",
+            FieldType::Option(_) => "option",
+            FieldType::Array(_) => "array",
+            FieldType::Container(_) => "container",
+        }
+    }
+
+    pub fn decode_value<B: Buf>(&self, buf: &mut B, cx: &Context) -> DeRes<Value> {
+        let value = match self {
+            FieldType::Bool => Value::Bool(bool::decode(buf)?),
+            FieldType::I8 => Value::Int(i8::decode(buf)?.into()),
+            FieldType::U8 | FieldType::Angle => Value::Int(u8::decode(buf)?.into()),
+            FieldType::I16 => Value::Int(i16::decode(buf)?.into()),
+            FieldType::U16 => Value::Int(u16::decode(buf)?.into()),
+            FieldType::I32 => Value::Int(i32::decode(buf)?.into()),
+            FieldType::I64 => Value::Int(i64::decode(buf)?),
+            FieldType::F32 => Value::Float(f32::decode(buf)?.into()),
+            FieldType::F64 => Value::Float(f64::decode(buf)?),
+            FieldType::VarInt => Value::Int(VarInt::decode(buf)?.0.into()),
+            FieldType::VarLong => Value::Int(VarLong::decode(buf)?.0),
+            FieldType::String => Value::String(String::decode(buf)?),
+            FieldType::Uuid => Value::Uuid(Decode::decode(buf)?),
+            FieldType::Identifier => Value::String(Identifier::decode(buf)?.to_string()),
+            FieldType::Position => Value::Position(Position::decode_with(buf, cx)?),
+            FieldType::ByteArray => Value::Bytes(ByteArray::decode(buf)?.0),
+            FieldType::RestBuffer => Value::Bytes(InferredLenByteArray::decode(buf)?.0),
+            FieldType::Nbt => Value::Nbt(RawNbt::decode_with(buf, cx)?),
+            FieldType::Chat => Value::Chat(Chat::decode_with(buf, cx)?),
+            FieldType::Option(ty) => Value::Option(match bool::decode(buf)? {
+                true => Some(Box::new(ty.decode_value(buf, cx)?)),
+                false => None,
+            }),
+            FieldType::Array(ty) => {
+                let len = VarInt::decode(buf)?.0;
+                let len = usize::try_from(len)
+                    .map_err(|_| DeserializeError::InvalidLength(len as i64))?;
+
+                Value::List(
+                    (0..len)
+                        .map(|_| ty.decode_value(buf, cx))
+                        .collect::<DeRes<_>>()?,
+                )
+            }
+            FieldType::Container(fields) => decode_fields(fields, buf, cx)?,
+        };
+
+        Ok(value)
+    }
+
+    pub fn encode_value<B: BufMut>(&self, value: &Value, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match (self, value) {
+            (FieldType::Bool, Value::Bool(b)) => b.encode(buf),
+            (FieldType::I8, Value::Int(i)) => self.int(*i).and_then(|i: i8| i.encode(buf)),
+            (FieldType::U8 | FieldType::Angle, Value::Int(i)) => {
+                self.int(*i).and_then(|i: u8| i.encode(buf))
+            }
+            (FieldType::I16, Value::Int(i)) => self.int(*i).and_then(|i: i16| i.encode(buf)),
+            (FieldType::U16, Value::Int(i)) => self.int(*i).and_then(|i: u16| i.encode(buf)),
+            (FieldType::I32, Value::Int(i)) => self.int(*i).and_then(|i: i32| i.encode(buf)),
+            (FieldType::I64, Value::Int(i)) => i.encode(buf),
+            (FieldType::F32, Value::Float(f)) => (*f as f32).encode(buf),
+            (FieldType::F64, Value::Float(f)) => f.encode(buf),
+            (FieldType::VarInt, Value::Int(i)) => self.int(*i).and_then(|i| VarInt(i).encode(buf)),
+            (FieldType::VarLong, Value::Int(i)) => VarLong(*i).encode(buf),
+            (FieldType::String, Value::String(s)) => s.encode(buf),
+            (FieldType::Uuid, Value::Uuid(uuid)) => uuid.encode(buf),
+            (FieldType::Identifier, Value::String(s)) => s
+                .parse::<Identifier>()
+                .map_err(|_| SerializeError::TypeMismatch(self.name()))?
+                .encode(buf),
+            (FieldType::Position, Value::Position(pos)) => pos.encode_with(buf, cx),
+            (FieldType::ByteArray, Value::Bytes(bytes)) => ByteArray(bytes.clone()).encode(buf),
+            (FieldType::RestBuffer, Value::Bytes(bytes)) => {
+                InferredLenByteArray(bytes.clone()).encode(buf)
+            }
+            (FieldType::Nbt, Value::Nbt(nbt)) => nbt.encode_with(buf, cx),
+            (FieldType::Chat, Value::Chat(chat)) => chat.encode_with(buf, cx),
+            (FieldType::Option(ty), Value::Option(value)) => {
+                value.is_some().encode(buf)?;
+
+                match value {
+                    Some(value) => ty.encode_value(value, buf, cx),
+                    None => Ok(()),
+                }
+            }
+            (FieldType::Array(ty), Value::List(values)) => {
+                let len = i32::try_from(values.len())
+                    .map_err(|_| SerializeError::LengthTooLarge(values.len()))?;
+
+                VarInt(len).encode(buf)?;
+
+                values
+                    .iter()
+                    .try_for_each(|value| ty.encode_value(value, buf, cx))
+            }
+            (FieldType::Container(fields), value) => encode_fields(fields, value, buf, cx),
+            _ => Err(SerializeError::TypeMismatch(self.name())),
+        }
+    }
+
+    fn int<T: TryFrom<i64>>(&self, i: i64) -> SerRes<T> {
+        T::try_from(i).map_err(|_| SerializeError::TypeMismatch(self.name()))
+    }
+}
+
+fn decode_fields<B: Buf>(fields: &[Field], buf: &mut B, cx: &Context) -> DeRes<Value> {
+    fields
+        .iter()
+        .map(|field| Ok((field.name.clone(), field.ty.decode_value(buf, cx)?)))
+        .collect::<DeRes<_>>()
+        .map(Value::Struct)
+}
+
+fn encode_fields<B: BufMut>(
+    fields: &[Field],
+    value: &Value,
+    buf: &mut B,
+    cx: &Context,
+) -> SerRes<()> {
+    if !matches!(value, Value::Struct(_)) {
+        return Err(SerializeError::TypeMismatch("container"));
+    }
+
+    for field in fields {
+        let value = value
+            .get(&field.name)
+            .ok_or_else(|| SerializeError::MissingField(field.name.clone()))?;
+
+        field.ty.encode_value(value, buf, cx)?;
+    }
+
+    Ok(())
+}
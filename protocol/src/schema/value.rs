@@ -0,0 +1,88 @@
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::{chat::Chat, types::position::Position, types::raw_nbt::RawNbt};
+
+/// A decoded field of a packet described by a [`Schema`](super::Schema).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    /// Any integer type, including var ints and angles.
+    Int(i64),
+    Float(f64),
+    /// Strings and identifiers.
+    String(String),
+    Uuid(Uuid),
+    Bytes(Bytes),
+    Position(Position),
+    Nbt(RawNbt),
+    Chat(Chat),
+    Option(Option<Box<Value>>),
+    List(Vec<Value>),
+    /// The fields of a container in the order of the schema.
+    Struct(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Looks up a field of a [`Value::Struct`].
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Self::Int(i)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Self::Float(f)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_owned())
+    }
+}
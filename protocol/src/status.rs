@@ -1,26 +1,26 @@
 use crate::{packets, state};
 
 state! {
-    Status;
+    Status(V1_7_2..);
     serverbound {
-        0x00 => StatusRequest,
-        0x01 => PingRequest
+        StatusRequest { V1_7_2 = 0x00 },
+        PingRequest { V1_7_2 = 0x01 }
     };
     clientbound {
-        0x00 => StatusResponse,
-        0x01 => PingResponse
+        StatusResponse { V1_7_2 = 0x00 },
+        PingResponse { V1_7_2 = 0x01 }
     }
 }
 
 packets! {
-    StatusRequest(0x00);
-    PingRequest(0x01) {
+    StatusRequest;
+    PingRequest {
         payload: i64
     };
-    StatusResponse(0x00) {
+    StatusResponse {
         response: String
     };
-    PingResponse(0x01) {
+    PingResponse {
         payload: i64
     }
 }
@@ -0,0 +1,82 @@
+use bytes::{Buf, BufMut};
+
+use crate::error::{DeRes, SerRes};
+
+use super::{Context, Decode, Encode};
+
+/// A set of bits packed into longs, prefixed with the number of longs as a VarInt.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitSet(pub Vec<i64>);
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.0
+            .get(index / 64)
+            .is_some_and(|long| long >> (index % 64) & 1 != 0)
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        if index / 64 >= self.0.len() {
+            if !value {
+                return;
+            }
+
+            self.0.resize(index / 64 + 1, 0);
+        }
+
+        if value {
+            self.0[index / 64] |= 1 << (index % 64);
+        } else {
+            self.0[index / 64] &= !(1 << (index % 64));
+
+            while self.0.last() == Some(&0) {
+                self.0.pop();
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|long| *long == 0)
+    }
+
+    /// Iterates over the indices of the set bits in ascending order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.0.len() * 64).filter(|i| self.get(*i))
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for index in iter {
+            set.set(index, true);
+        }
+
+        set
+    }
+}
+
+impl Encode for BitSet {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        self.0.encode_with(buf, cx)
+    }
+}
+
+impl Decode for BitSet {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        Ok(Self(Vec::decode_with(buf, cx)?))
+    }
+}
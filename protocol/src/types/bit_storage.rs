@@ -0,0 +1,142 @@
+use crate::error::{DeRes, DeserializeError};
+
+/// Fixed-width entries packed into longs from the least significant bit, without spanning two
+/// longs.
+///
+/// This is the layout of paletted containers since 1.16 and of heightmaps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitStorage {
+    bits: u8,
+    len: usize,
+    data: Vec<i64>,
+}
+
+impl BitStorage {
+    /// Creates a storage of `len` zeroes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is more than 64.
+    pub fn new(bits: u8, len: usize) -> Self {
+        assert!(bits <= 64, "too many bits per entry");
+
+        Self {
+            bits,
+            len,
+            data: vec![0; packed_len(bits, len)],
+        }
+    }
+
+    /// Wraps packed longs, which must be exactly as many as `len` entries need.
+    pub fn from_data(bits: u8, len: usize, data: Vec<i64>) -> DeRes<Self> {
+        if bits > 64 || data.len() != packed_len(bits, len) {
+            return Err(DeserializeError::InvalidLength(data.len() as i64));
+        }
+
+        Ok(Self { bits, len, data })
+    }
+
+    /// Packs `entries`, which must all fit in `bits` bits.
+    pub fn from_entries(bits: u8, entries: impl ExactSizeIterator<Item = u64>) -> Self {
+        let mut storage = Self::new(bits, entries.len());
+
+        for (index, entry) in entries.enumerate() {
+            storage.set(index, entry);
+        }
+
+        storage
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn data(&self) -> &[i64] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<i64> {
+        self.data
+    }
+
+    /// Returns the entry at `index`. With zero bits every entry is zero.
+    pub fn get(&self, index: usize) -> u64 {
+        assert!(index < self.len, "index out of bounds");
+
+        if self.bits == 0 {
+            return 0;
+        }
+
+        let (long, shift) = self.position(index);
+
+        (self.data[long] as u64 >> shift) & self.mask()
+    }
+
+    /// Sets the entry at `index` and returns the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds or `value` does not fit in the bits per entry.
+    pub fn set(&mut self, index: usize, value: u64) -> u64 {
+        assert!(value & !self.mask() == 0, "value does not fit");
+
+        let previous = self.get(index);
+
+        if self.bits != 0 {
+            let (long, shift) = self.position(index);
+            let cleared = self.data[long] as u64 & !(self.mask() << shift);
+
+            self.data[long] = (cleared | value << shift) as i64;
+        }
+
+        previous
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..self.len).map(|index| self.get(index))
+    }
+
+    /// Repacks the entries with a different number of bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry does not fit in the new number of bits.
+    pub fn resize(&self, bits: u8) -> Self {
+        Self::from_entries(bits, self.iter().collect::<Vec<_>>().into_iter())
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX.checked_shr(64 - self.bits as u32).unwrap_or(0)
+    }
+
+    fn position(&self, index: usize) -> (usize, u32) {
+        let per_long = 64 / self.bits as usize;
+
+        (
+            index / per_long,
+            (index % per_long) as u32 * self.bits as u32,
+        )
+    }
+}
+
+/// The number of longs `len` entries of `bits` bits take up.
+pub fn packed_len(bits: u8, len: usize) -> usize {
+    if bits == 0 {
+        return 0;
+    }
+
+    len.div_ceil(64 / bits as usize)
+}
+
+/// The bits needed to tell `n` values apart.
+pub(crate) fn ceil_log2(n: usize) -> u8 {
+    (usize::BITS - n.saturating_sub(1).leading_zeros()) as u8
+}
@@ -0,0 +1,111 @@
+use std::{fmt, ops::Deref, str};
+
+use bytes::{Buf, BufMut, Bytes};
+
+use crate::error::{DeRes, DeserializeError, SerRes};
+
+use super::{varint::VarInt, Decode, Encode};
+
+/// A UTF-8 string backed by [`Bytes`].
+///
+/// Decoding a `BytesStr` from a [`Bytes`] or [`bytes::BytesMut`] buffer shares the frame's
+/// allocation instead of copying the string out of it. On the wire it is identical to [`String`].
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BytesStr(Bytes);
+
+impl BytesStr {
+    pub const fn from_static(s: &'static str) -> Self {
+        Self(Bytes::from_static(s.as_bytes()))
+    }
+
+    pub fn from_utf8(bytes: Bytes) -> Result<Self, str::Utf8Error> {
+        str::from_utf8(&bytes)?;
+
+        Ok(Self(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the contents are checked to be valid UTF-8 on construction.
+        unsafe { str::from_utf8_unchecked(&self.0) }
+    }
+
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
+}
+
+impl Deref for BytesStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for BytesStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for BytesStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for BytesStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl From<String> for BytesStr {
+    fn from(s: String) -> Self {
+        Self(s.into_bytes().into())
+    }
+}
+
+impl From<&'static str> for BytesStr {
+    fn from(s: &'static str) -> Self {
+        Self::from_static(s)
+    }
+}
+
+impl From<BytesStr> for String {
+    fn from(s: BytesStr) -> Self {
+        s.as_str().to_owned()
+    }
+}
+
+impl PartialEq<str> for BytesStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for BytesStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Encode for BytesStr {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        VarInt(self.0.len() as i32).encode(buf)?;
+        buf.put_slice(&self.0);
+        Ok(())
+    }
+}
+
+impl Decode for BytesStr {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let len = VarInt::decode(buf)?.0 as usize;
+
+        if buf.remaining() < len {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+
+        Self::from_utf8(buf.copy_to_bytes(len)).map_err(Into::into)
+    }
+}
@@ -0,0 +1,128 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use bytes::{Buf, BufMut};
+
+use crate::error::{DeRes, IdentifierError, SerRes};
+
+use super::{Decode, Encode};
+
+/// A namespaced resource location like `minecraft:stone`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Identifier {
+    namespace: Cow<'static, str>,
+    path: Cow<'static, str>,
+}
+
+impl Identifier {
+    pub const DEFAULT_NAMESPACE: &'static str = "minecraft";
+
+    pub fn new(
+        namespace: impl Into<Cow<'static, str>>,
+        path: impl Into<Cow<'static, str>>,
+    ) -> Result<Self, IdentifierError> {
+        let namespace = namespace.into();
+        let path = path.into();
+
+        if !namespace.chars().all(is_namespace_char) {
+            return Err(IdentifierError::InvalidNamespace(namespace.into_owned()));
+        }
+        if !path.chars().all(is_path_char) {
+            return Err(IdentifierError::InvalidPath(path.into_owned()));
+        }
+
+        Ok(Self { namespace, path })
+    }
+
+    /// Creates an identifier in the `minecraft` namespace.
+    pub fn minecraft(path: impl Into<Cow<'static, str>>) -> Result<Self, IdentifierError> {
+        Self::new(Self::DEFAULT_NAMESPACE, path)
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn is_default_namespace(&self) -> bool {
+        self.namespace == Self::DEFAULT_NAMESPACE
+    }
+}
+
+fn is_namespace_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.')
+}
+
+fn is_path_char(c: char) -> bool {
+    is_namespace_char(c) || c == '/'
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+impl FromStr for Identifier {
+    type Err = IdentifierError;
+
+    /// Parses `namespace:path`, falling back to the `minecraft` namespace if it is missing or
+    /// empty.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("", path)) => Self::minecraft(path.to_owned()),
+            Some((namespace, path)) => Self::new(namespace.to_owned(), path.to_owned()),
+            None => Self::minecraft(s.to_owned()),
+        }
+    }
+}
+
+impl TryFrom<&str> for Identifier {
+    type Error = IdentifierError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for Identifier {
+    type Error = IdentifierError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Identifier> for String {
+    fn from(identifier: Identifier) -> Self {
+        identifier.to_string()
+    }
+}
+
+impl Encode for Identifier {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.to_string().encode(buf)
+    }
+}
+
+impl Decode for Identifier {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        String::decode(buf)?.parse().map_err(Into::into)
+    }
+}
+
+impl serde::Serialize for Identifier {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Identifier {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <Cow<str>>::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
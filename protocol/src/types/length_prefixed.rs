@@ -0,0 +1,159 @@
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use bytes::{Buf, BufMut};
+
+use crate::error::{DeRes, DeserializeError, SerRes, SerializeError};
+
+use super::{varint::VarInt, Context, Decode, Encode};
+
+/// An integer type that can prefix a sequence with its length.
+pub trait LengthPrefix: Encode + Decode {
+    fn from_len(len: usize) -> SerRes<Self>;
+    fn to_len(&self) -> DeRes<usize>;
+}
+
+macro_rules! length_prefix {
+    ($($ty:ty),*) => {
+        $(
+            impl LengthPrefix for $ty {
+                fn from_len(len: usize) -> SerRes<Self> {
+                    len.try_into().map_err(|_| SerializeError::LengthTooLarge(len))
+                }
+
+                fn to_len(&self) -> DeRes<usize> {
+                    (*self)
+                        .try_into()
+                        .map_err(|_| DeserializeError::InvalidLength(*self as i64))
+                }
+            }
+        )*
+    };
+}
+
+length_prefix!(u8, u16, i16, i32);
+
+impl LengthPrefix for VarInt {
+    fn from_len(len: usize) -> SerRes<Self> {
+        i32::from_len(len).map(Self)
+    }
+
+    fn to_len(&self) -> DeRes<usize> {
+        self.0.to_len()
+    }
+}
+
+pub(crate) fn encode_seq<L, T, B>(
+    len: usize,
+    items: impl IntoIterator<Item = T>,
+    buf: &mut B,
+    cx: &Context,
+) -> SerRes<()>
+where
+    L: LengthPrefix,
+    T: Encode,
+    B: BufMut,
+{
+    L::from_len(len)?.encode(buf)?;
+
+    for item in items {
+        item.encode_with(buf, cx)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn decode_seq<L, T, B>(buf: &mut B, cx: &Context) -> DeRes<Vec<T>>
+where
+    L: LengthPrefix,
+    T: Decode,
+    B: Buf,
+{
+    let len = L::decode(buf)?.to_len()?;
+
+    // Every element takes up at least one byte, which bounds the allocation for bogus lengths.
+    let mut vec = Vec::with_capacity(len.min(buf.remaining()));
+
+    for _ in 0..len {
+        vec.push(T::decode_with(buf, cx)?);
+    }
+
+    Ok(vec)
+}
+
+/// A sequence of `T` prefixed with its length encoded as `L`.
+///
+/// `Vec<T>` is always prefixed with a [`VarInt`], but vanilla also uses `u8`, `i16` and `i32`
+/// prefixes in some places.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthPrefixed<L, T> {
+    pub items: Vec<T>,
+    prefix: PhantomData<L>,
+}
+
+impl<L, T> LengthPrefixed<L, T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            prefix: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<L, T> Default for LengthPrefixed<L, T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<L, T> From<Vec<T>> for LengthPrefixed<L, T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<L, T> From<LengthPrefixed<L, T>> for Vec<T> {
+    fn from(prefixed: LengthPrefixed<L, T>) -> Self {
+        prefixed.items
+    }
+}
+
+impl<L, T> Deref for LengthPrefixed<L, T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.items
+    }
+}
+
+impl<L, T> DerefMut for LengthPrefixed<L, T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.items
+    }
+}
+
+impl<L: LengthPrefix, T: Encode> Encode for LengthPrefixed<L, T> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        encode_seq::<L, _, B>(self.items.len(), &self.items, buf, cx)
+    }
+}
+
+impl<L: LengthPrefix, T: Decode> Decode for LengthPrefixed<L, T> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        decode_seq::<L, T, B>(buf, cx).map(Self::new)
+    }
+}
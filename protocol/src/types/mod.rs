@@ -1,18 +1,87 @@
+pub mod bit_set;
+pub mod bit_storage;
+pub mod bytes_str;
+pub mod identifier;
+pub mod length_prefixed;
+pub(crate) mod mutf8;
+pub mod paletted_container;
 pub mod position;
+pub mod raw_nbt;
 pub mod slot;
 pub mod varint;
 
-use bytes::{Buf, BufMut, BytesMut};
+pub use self::{
+    bit_storage::BitStorage,
+    paletted_container::{Palette, PaletteKind, PalettedContainer},
+};
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+    ops::Deref,
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use rsa::{
     pkcs8::{DecodePublicKey, EncodePublicKey},
     RsaPublicKey,
 };
 use uuid::Uuid;
 
-use crate::error::{DeRes, DeserializeError, SerRes};
+use crate::{
+    error::{DeRes, DeserializeError, SerRes},
+    version::ProtocolVersion,
+};
+
+use self::{
+    length_prefixed::{decode_seq, encode_seq},
+    varint::VarInt,
+};
+
+/// The negotiated connection parameters some types need to pick their wire format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Context {
+    pub version: ProtocolVersion,
+}
+
+impl Context {
+    pub fn new(version: ProtocolVersion) -> Self {
+        Self { version }
+    }
+}
+
+/// Writes a value in its wire format into any [`BufMut`].
+pub trait Encode {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()>;
+
+    /// Encodes the value in the format used by the connection described by `cx`.
+    ///
+    /// Types whose format depends on the protocol version override this. By default it is the
+    /// same as [`Encode::encode`].
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        let _ = cx;
+        self.encode(buf)
+    }
+}
+
+/// Reads a value in its wire format from any [`Buf`].
+pub trait Decode: Sized {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self>;
 
-use self::varint::VarInt;
+    /// Decodes the value in the format used by the connection described by `cx`.
+    ///
+    /// Types whose format depends on the protocol version override this. By default it is the
+    /// same as [`Decode::decode`].
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let _ = cx;
+        Self::decode(buf)
+    }
+}
 
+/// Shorthand for encoding into and decoding from a [`BytesMut`].
+///
+/// This is implemented for every type that is both [`Encode`] and [`Decode`].
 pub trait Serialize {
     fn serialize(&self, buf: &mut BytesMut) -> SerRes<()>;
     fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
@@ -20,13 +89,25 @@ pub trait Serialize {
         Self: Sized;
 }
 
-impl Serialize for bool {
+impl<T: Encode + Decode> Serialize for T {
     fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+        self.encode(buf)
+    }
+
+    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+        Self::decode(buf)
+    }
+}
+
+impl Encode for bool {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_u8(*self as u8);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for bool {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if !buf.has_remaining() {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -35,13 +116,15 @@ impl Serialize for bool {
     }
 }
 
-impl Serialize for u8 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for u8 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_u8(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for u8 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if !buf.has_remaining() {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -50,13 +133,15 @@ impl Serialize for u8 {
     }
 }
 
-impl Serialize for u16 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for u16 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_u16(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for u16 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 2 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -65,13 +150,15 @@ impl Serialize for u16 {
     }
 }
 
-impl Serialize for u32 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for u32 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_u32(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for u32 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 4 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -80,13 +167,15 @@ impl Serialize for u32 {
     }
 }
 
-impl Serialize for u64 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for u64 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_u64(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for u64 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 8 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -95,13 +184,15 @@ impl Serialize for u64 {
     }
 }
 
-impl Serialize for i8 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for i8 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_i8(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for i8 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if !buf.has_remaining() {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -110,13 +201,15 @@ impl Serialize for i8 {
     }
 }
 
-impl Serialize for i16 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for i16 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_i16(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for i16 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 2 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -125,13 +218,15 @@ impl Serialize for i16 {
     }
 }
 
-impl Serialize for i32 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for i32 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_i32(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for i32 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 4 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -140,13 +235,15 @@ impl Serialize for i32 {
     }
 }
 
-impl Serialize for i64 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for i64 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put_i64(*self);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for i64 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 8 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -155,17 +252,16 @@ impl Serialize for i64 {
     }
 }
 
-impl Serialize for f32 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        buf.extend_from_slice(&self.to_be_bytes());
+impl Encode for f32 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        buf.put_slice(&self.to_be_bytes());
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
+impl Decode for f32 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 4 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -174,17 +270,16 @@ impl Serialize for f32 {
     }
 }
 
-impl Serialize for f64 {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        buf.extend_from_slice(&self.to_be_bytes());
+impl Encode for f64 {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        buf.put_slice(&self.to_be_bytes());
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
+impl Decode for f64 {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 8 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -193,67 +288,241 @@ impl Serialize for f64 {
     }
 }
 
-impl Serialize for String {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        VarInt(self.len() as i32).serialize(buf)?;
-        buf.extend_from_slice(self.as_bytes());
+impl Encode for str {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        VarInt(self.len() as i32).encode(buf)?;
+        buf.put_slice(self.as_bytes());
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
-        let len = VarInt::deserialize(buf)?.0 as usize;
+impl Encode for String {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.as_str().encode(buf)
+    }
+}
+
+impl Decode for String {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let len = VarInt::decode(buf)?.0 as usize;
 
         if buf.remaining() < len {
             return Err(DeserializeError::UnexpectedEof);
         }
 
-        let bytes = buf.split_to(len).to_vec();
+        let bytes = buf.copy_to_bytes(len).to_vec();
 
         String::from_utf8(bytes).map_err(Into::into)
     }
 }
 
-impl<T: Serialize> Serialize for Option<T> {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        self.is_some().serialize(buf)?;
+impl<T: Encode> Encode for Option<T> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
 
-        if self.is_some() {
-            self.as_ref().unwrap().serialize(buf)?
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        self.is_some().encode(buf)?;
+
+        if let Some(value) = self {
+            value.encode_with(buf, cx)?
         }
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
-        let present = bool::deserialize(buf)?;
+impl<T: Decode> Decode for Option<T> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let present = bool::decode(buf)?;
 
         if present {
-            Ok(Some(T::deserialize(buf)?))
+            Ok(Some(T::decode_with(buf, cx)?))
         } else {
             Ok(None)
         }
     }
 }
 
-impl<T: Serialize> Serialize for Vec<T> {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        VarInt(self.len() as i32).serialize(buf)?;
+/// An optional value that is prefixed with a boolean telling whether it is present.
+///
+/// This is how `Option<T>` is encoded. It exists to make the choice explicit next to
+/// [`RemainingOption`].
+pub type PrefixedOption<T> = Option<T>;
+
+/// An optional value at the end of a packet, which is present if there are bytes left.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemainingOption<T>(pub Option<T>);
 
+impl<T> From<Option<T>> for RemainingOption<T> {
+    fn from(option: Option<T>) -> Self {
+        Self(option)
+    }
+}
+
+impl<T> From<RemainingOption<T>> for Option<T> {
+    fn from(option: RemainingOption<T>) -> Self {
+        option.0
+    }
+}
+
+impl<T: Encode> Encode for RemainingOption<T> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        match &self.0 {
+            Some(value) => value.encode_with(buf, cx),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Decode> Decode for RemainingOption<T> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if buf.has_remaining() {
+            Ok(Self(Some(T::decode_with(buf, cx)?)))
+        } else {
+            Ok(Self(None))
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        encode_seq::<VarInt, _, B>(self.len(), self, buf, cx)
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        decode_seq::<VarInt, T, B>(buf, cx)
+    }
+}
+
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
         for x in self {
-            x.serialize(buf)?;
+            x.encode_with(buf, cx)?;
         }
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
-        let len = VarInt::deserialize(buf)?.0 as usize;
+impl<T: Decode, const N: usize> Decode for [T; N] {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
 
-        let mut vec = Vec::with_capacity(len);
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let mut vec = Vec::with_capacity(N);
 
-        for _ in 0..len {
-            vec.push(T::deserialize(buf)?);
+        for _ in 0..N {
+            vec.push(T::decode_with(buf, cx)?);
         }
 
-        Ok(vec)
+        Ok(vec.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for Box<T> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        (**self).encode(buf)
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        (**self).encode_with(buf, cx)
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        T::decode(buf).map(Box::new)
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        T::decode_with(buf, cx).map(Box::new)
+    }
+}
+
+impl Encode for Cow<'_, str> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        (**self).encode(buf)
+    }
+}
+
+impl Decode for Cow<'_, str> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        String::decode(buf).map(Cow::Owned)
+    }
+}
+
+impl<K: Encode, V: Encode, S> Encode for HashMap<K, V, S> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        encode_seq::<VarInt, _, B>(self.len(), self, buf, cx)
+    }
+}
+
+impl<K, V, S> Decode for HashMap<K, V, S>
+where
+    K: Decode + Eq + Hash,
+    V: Decode,
+    S: BuildHasher + Default,
+{
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        Ok(decode_seq::<VarInt, (K, V), B>(buf, cx)?
+            .into_iter()
+            .collect())
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for BTreeMap<K, V> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        encode_seq::<VarInt, _, B>(self.len(), self, buf, cx)
+    }
+}
+
+impl<K: Decode + Ord, V: Decode> Decode for BTreeMap<K, V> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        Ok(decode_seq::<VarInt, (K, V), B>(buf, cx)?
+            .into_iter()
+            .collect())
     }
 }
 
@@ -263,42 +532,52 @@ pub enum Either<L, R> {
     Right(R),
 }
 
-impl<L: Serialize, R: Serialize> Serialize for Either<L, R> {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl<L: Encode, R: Encode> Encode for Either<L, R> {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
         match self {
             Either::Left(l) => {
-                true.serialize(buf)?;
-                l.serialize(buf)?;
+                true.encode(buf)?;
+                l.encode_with(buf, cx)?;
             }
             Either::Right(r) => {
-                false.serialize(buf)?;
-                r.serialize(buf)?;
+                false.encode(buf)?;
+                r.encode_with(buf, cx)?;
             }
         }
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        let b = bool::deserialize(buf)?;
+impl<L: Decode, R: Decode> Decode for Either<L, R> {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let b = bool::decode(buf)?;
 
         if b {
-            L::deserialize(buf).map(Self::Left)
+            L::decode_with(buf, cx).map(Self::Left)
         } else {
-            R::deserialize(buf).map(Self::Right)
+            R::decode_with(buf, cx).map(Self::Right)
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A rotation in steps of 1/256 of a full turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Angle(u8);
 
 impl Angle {
+    /// Rounds down to the closest step, wrapping around like vanilla so that negative angles
+    /// and angles past a full turn work.
     pub fn from_degrees(angle: f32) -> Self {
-        Self((angle * (256.0 / 360.0)) as u8)
+        Self(((angle * (256.0 / 360.0)).floor() as i32) as u8)
     }
 
     pub fn to_degrees(&self) -> f32 {
@@ -306,23 +585,103 @@ impl Angle {
     }
 }
 
-impl Serialize for Angle {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        self.0.serialize(buf)
+impl Encode for Angle {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.0.encode(buf)
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        Ok(Self(u8::deserialize(buf)?))
+impl Decode for Angle {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Ok(Self(u8::decode(buf)?))
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct InferredLenByteArray(pub Vec<u8>);
+/// A byte array prefixed with its length as a [`VarInt`].
+///
+/// This has the same wire format as `Vec<u8>`, but is encoded and decoded in bulk, and decoding
+/// it from a [`Bytes`] or [`BytesMut`] buffer does not copy the data.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ByteArray(pub Bytes);
+
+impl Deref for ByteArray {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for ByteArray {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<ByteArray> for Vec<u8> {
+    fn from(array: ByteArray) -> Self {
+        array.0.into()
+    }
+}
+
+impl From<ByteArray> for Bytes {
+    fn from(array: ByteArray) -> Self {
+        array.0
+    }
+}
+
+impl From<Vec<u8>> for ByteArray {
+    fn from(vec: Vec<u8>) -> Self {
+        Self(vec.into())
+    }
+}
+
+impl From<Bytes> for ByteArray {
+    fn from(bytes: Bytes) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&'static [u8]> for ByteArray {
+    fn from(slice: &'static [u8]) -> Self {
+        Self(Bytes::from_static(slice))
+    }
+}
+
+impl Encode for ByteArray {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        VarInt(self.0.len() as i32).encode(buf)?;
+        buf.put_slice(&self.0);
+
+        Ok(())
+    }
+}
+
+impl Decode for ByteArray {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let len = VarInt::decode(buf)?.0 as usize;
+
+        if buf.remaining() < len {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+
+        Ok(Self(buf.copy_to_bytes(len)))
+    }
+}
+
+/// A byte array that takes up the rest of the packet.
+///
+/// Decoding it from a [`Bytes`] or [`BytesMut`] buffer does not copy the data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InferredLenByteArray(pub Bytes);
 
 impl From<InferredLenByteArray> for Vec<u8> {
+    fn from(array: InferredLenByteArray) -> Self {
+        array.0.into()
+    }
+}
+
+impl From<InferredLenByteArray> for Bytes {
     fn from(array: InferredLenByteArray) -> Self {
         array.0
     }
@@ -330,32 +689,39 @@ impl From<InferredLenByteArray> for Vec<u8> {
 
 impl From<Vec<u8>> for InferredLenByteArray {
     fn from(vec: Vec<u8>) -> Self {
-        Self(vec)
+        Self(vec.into())
     }
 }
 
-impl Serialize for InferredLenByteArray {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        buf.extend_from_slice(self.0.as_slice());
+impl From<Bytes> for InferredLenByteArray {
+    fn from(bytes: Bytes) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Encode for InferredLenByteArray {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        buf.put_slice(&self.0);
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        Ok(Self(buf[..buf.remaining()].to_vec()))
+impl Decode for InferredLenByteArray {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Ok(Self(buf.copy_to_bytes(buf.remaining())))
     }
 }
 
-impl Serialize for Uuid {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for Uuid {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         buf.put(&self.as_bytes()[..]);
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for Uuid {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         if buf.remaining() < 16 {
             return Err(DeserializeError::UnexpectedEof);
         }
@@ -364,70 +730,80 @@ impl Serialize for Uuid {
     }
 }
 
-impl Serialize for RsaPublicKey {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for RsaPublicKey {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         let document = self.to_public_key_der()?;
         let bytes = document.as_bytes();
 
-        VarInt(bytes.len() as i32).serialize(buf)?;
+        VarInt(bytes.len() as i32).encode(buf)?;
 
-        buf.extend_from_slice(bytes);
+        buf.put_slice(bytes);
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        let len = VarInt::deserialize(buf)?.0 as usize;
+impl Decode for RsaPublicKey {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let len = VarInt::decode(buf)?.0 as usize;
 
         if buf.remaining() < len {
             return Err(DeserializeError::UnexpectedEof);
         }
 
-        let bytes = buf.split_to(len);
+        let bytes = buf.copy_to_bytes(len);
 
         RsaPublicKey::from_public_key_der(&bytes).map_err(Into::into)
     }
 }
 
-impl Serialize for nbt::Blob {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        nbt::to_writer(&mut buf.writer(), &self, None).map_err(Into::into)
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        (**self).encode(buf)
     }
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        nbt::from_reader(&mut buf.reader()).map_err(Into::into)
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        (**self).encode_with(buf, cx)
     }
 }
 
-impl<A: Serialize> Serialize for (A,) {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        self.0.serialize(buf)
-    }
+macro_rules! tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Encode),+> Encode for ($($name,)+) {
+            fn encode<Buffer: BufMut>(&self, buf: &mut Buffer) -> SerRes<()> {
+                self.encode_with(buf, &Context::default())
+            }
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        Ok((A::deserialize(buf)?,))
-    }
-}
+            #[allow(non_snake_case)]
+            fn encode_with<Buffer: BufMut>(&self, buf: &mut Buffer, cx: &Context) -> SerRes<()> {
+                let ($($name,)+) = self;
 
-impl<A: Serialize, B: Serialize> Serialize for (A, B) {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
-        self.0.serialize(buf)?;
-        self.1.serialize(buf)
-    }
+                $($name.encode_with(buf, cx)?;)+
+                Ok(())
+            }
+        }
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        Ok((A::deserialize(buf)?, B::deserialize(buf)?))
-    }
+        impl<$($name: Decode),+> Decode for ($($name,)+) {
+            fn decode<Buffer: Buf>(buf: &mut Buffer) -> DeRes<Self> {
+                Self::decode_with(buf, &Context::default())
+            }
+
+            fn decode_with<Buffer: Buf>(buf: &mut Buffer, cx: &Context) -> DeRes<Self> {
+                Ok(($($name::decode_with(buf, cx)?,)+))
+            }
+        }
+    };
 }
+
+tuple!(A);
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+tuple!(A, B, C, D, E);
+tuple!(A, B, C, D, E, F);
+tuple!(A, B, C, D, E, F, G);
+tuple!(A, B, C, D, E, F, G, H);
+tuple!(A, B, C, D, E, F, G, H, I);
+tuple!(A, B, C, D, E, F, G, H, I, J);
+tuple!(A, B, C, D, E, F, G, H, I, J, K);
+tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
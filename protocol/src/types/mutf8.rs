@@ -0,0 +1,66 @@
+//! The "modified UTF-8" Java uses for NBT strings: `\0` takes two bytes and characters outside
+//! the BMP are written as two three-byte surrogates.
+
+use crate::error::{DeRes, DeserializeError};
+
+pub(crate) fn encode(s: &str) -> Vec<u8> {
+    if s.bytes().all(|b| b != 0 && b < 0xf0) {
+        return s.as_bytes().to_vec();
+    }
+
+    let mut out = Vec::with_capacity(s.len() + 2);
+
+    for unit in s.encode_utf16() {
+        match unit {
+            0x01..=0x7f => out.push(unit as u8),
+            0x00 | 0x80..=0x7ff => {
+                out.push(0xc0 | (unit >> 6) as u8);
+                out.push(0x80 | (unit & 0x3f) as u8);
+            }
+            _ => {
+                out.push(0xe0 | (unit >> 12) as u8);
+                out.push(0x80 | ((unit >> 6) & 0x3f) as u8);
+                out.push(0x80 | (unit & 0x3f) as u8);
+            }
+        }
+    }
+
+    out
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> DeRes<String> {
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        if !s.contains('\0') {
+            return Ok(s.to_owned());
+        }
+    }
+
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter().copied();
+
+    let mut next = || iter.next().ok_or(DeserializeError::BrokenPacket);
+    let mut remaining = bytes.len();
+
+    while remaining > 0 {
+        let b = next()?;
+
+        let (unit, len) = match b {
+            0x01..=0x7f => (b as u16, 1),
+            0xc0..=0xdf => (((b as u16 & 0x1f) << 6) | (next()? as u16 & 0x3f), 2),
+            0xe0..=0xef => {
+                let b2 = next()? as u16 & 0x3f;
+                let b3 = next()? as u16 & 0x3f;
+
+                (((b as u16 & 0x0f) << 12) | (b2 << 6) | b3, 3)
+            }
+            _ => return Err(DeserializeError::BrokenPacket),
+        };
+
+        units.push(unit);
+        remaining = remaining
+            .checked_sub(len)
+            .ok_or(DeserializeError::BrokenPacket)?;
+    }
+
+    String::from_utf16(&units).map_err(Into::into)
+}
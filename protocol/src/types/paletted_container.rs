@@ -0,0 +1,360 @@
+use std::{collections::HashMap, mem};
+
+use bytes::{Buf, BufMut};
+
+use crate::{
+    error::{DeRes, DeserializeError, SerRes},
+    registry::{Block, Registry},
+};
+
+use super::{
+    bit_storage::{ceil_log2, BitStorage},
+    length_prefixed::encode_seq,
+    varint::VarInt,
+    Context, Decode, Encode,
+};
+
+/// What a [`PalettedContainer`] stores, which decides its size and how many bits it uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaletteKind {
+    /// The 16×16×16 block states of a chunk section.
+    BlockStates,
+    /// The 4×4×4 biomes of a chunk section, out of this many biomes in the registry, which
+    /// decides the bits of ids stored directly.
+    Biomes(usize),
+}
+
+impl PaletteKind {
+    /// The number of entries along each axis.
+    pub fn edge(self) -> usize {
+        match self {
+            Self::BlockStates => 16,
+            Self::Biomes(_) => 4,
+        }
+    }
+
+    /// The number of entries in a container.
+    pub fn size(self) -> usize {
+        self.edge().pow(3)
+    }
+
+    /// The index of an entry, in `y, z, x` order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate is not less than [`PaletteKind::edge`].
+    pub fn index(self, x: usize, y: usize, z: usize) -> usize {
+        let edge = self.edge();
+
+        assert!(
+            x < edge && y < edge && z < edge,
+            "coordinates out of bounds"
+        );
+
+        (y * edge + z) * edge + x
+    }
+
+    /// The bits per entry used for the global palette.
+    pub fn direct_bits(self) -> u8 {
+        match self {
+            Self::BlockStates => {
+                let states = Block::ALL
+                    .last()
+                    .map_or(1, |block| block.states().end() + 1);
+
+                ceil_log2(states as usize)
+            }
+            Self::Biomes(count) => ceil_log2(count),
+        }
+    }
+
+    /// The bits per entry used for a palette of `len` values.
+    pub fn bits_for(self, len: usize) -> u8 {
+        let bits = ceil_log2(len);
+
+        match self {
+            _ if len <= 1 => 0,
+            Self::BlockStates if bits <= 8 => bits.max(4),
+            Self::Biomes(_) if bits <= 3 => bits,
+            _ => self.direct_bits(),
+        }
+    }
+
+    fn is_indirect(self, bits: u8) -> bool {
+        matches!(
+            (self, bits),
+            (Self::BlockStates, 1..=8) | (Self::Biomes(_), 1..=3)
+        )
+    }
+
+    /// The bits the vanilla client actually stores entries with, given the bits on the wire.
+    fn storage_bits(self, bits: u8) -> u8 {
+        match bits {
+            0 => 0,
+            _ if !self.is_indirect(bits) => self.direct_bits(),
+            _ if self == Self::BlockStates => bits.max(4),
+            _ => bits,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Palette {
+    /// Every entry has the same value, and no data is sent.
+    Single(i32),
+    /// Entries are indices into a list of values.
+    Indirect(Vec<i32>),
+    /// Entries are global ids.
+    Direct,
+}
+
+/// The block states or biomes of a chunk section, as sent in the Chunk Data packet.
+///
+/// The palette grows as new values are set and shrinks once values are no longer used, picking
+/// the same bits per entry as vanilla. Decoding and encoding a container leaves its palette
+/// untouched, so the bytes round-trip exactly if the bits per entry on the wire are the ones the
+/// vanilla client stores entries with, as vanilla servers send them. Other bits, like 2 for
+/// block states or 10 for a direct palette of 63 biomes, are encoded as the bits the entries
+/// are stored with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PalettedContainer {
+    kind: PaletteKind,
+    palette: Palette,
+    storage: BitStorage,
+    /// How many entries hold each value.
+    counts: HashMap<i32, usize>,
+}
+
+impl PalettedContainer {
+    /// A container where every entry is `value`.
+    pub fn single(kind: PaletteKind, value: i32) -> Self {
+        Self {
+            kind,
+            palette: Palette::Single(value),
+            storage: BitStorage::new(0, kind.size()),
+            counts: HashMap::from([(value, kind.size())]),
+        }
+    }
+
+    /// Packs `values` with the smallest palette, in index order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not exactly [`PaletteKind::size`] values, or a value does not fit in
+    /// the global palette.
+    pub fn from_values(kind: PaletteKind, values: &[i32]) -> Self {
+        assert_eq!(values.len(), kind.size(), "wrong number of values");
+
+        let mut palette = Vec::new();
+        let mut indices = HashMap::new();
+        let mut counts = HashMap::new();
+
+        for &value in values {
+            indices.entry(value).or_insert_with(|| {
+                palette.push(value);
+                palette.len() as u64 - 1
+            });
+            *counts.entry(value).or_default() += 1;
+        }
+
+        let bits = kind.bits_for(palette.len());
+
+        let (palette, storage) = match bits {
+            0 => return Self::single(kind, palette[0]),
+            _ if kind.is_indirect(bits) => (
+                Palette::Indirect(palette),
+                BitStorage::from_entries(bits, values.iter().map(|value| indices[value])),
+            ),
+            _ => (
+                Palette::Direct,
+                BitStorage::from_entries(bits, values.iter().map(|value| *value as u64)),
+            ),
+        };
+
+        Self {
+            kind,
+            palette,
+            storage,
+            counts,
+        }
+    }
+
+    pub fn kind(&self) -> PaletteKind {
+        self.kind
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn storage(&self) -> &BitStorage {
+        &self.storage
+    }
+
+    /// The bits per entry, which are 0 for a single value.
+    pub fn bits(&self) -> u8 {
+        self.storage.bits()
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> i32 {
+        self.value(self.storage.get(self.kind.index(x, y, z)))
+    }
+
+    /// Sets an entry and returns its previous value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate is out of bounds, or `value` does not fit in the global palette.
+    pub fn set(&mut self, x: usize, y: usize, z: usize, value: i32) -> i32 {
+        let index = self.kind.index(x, y, z);
+        let previous = self.value(self.storage.get(index));
+
+        if previous == value {
+            return previous;
+        }
+
+        if let Some(count) = self.counts.get_mut(&previous) {
+            *count -= 1;
+
+            if *count == 0 {
+                self.counts.remove(&previous);
+            }
+        }
+
+        *self.counts.entry(value).or_default() += 1;
+
+        let entry = self.entry(value);
+        self.storage.set(index, entry);
+
+        if self.kind.bits_for(self.counts.len()) < self.bits() {
+            *self = Self::from_values(self.kind, &self.values());
+        }
+
+        previous
+    }
+
+    /// Returns the value of every entry, in index order.
+    pub fn values(&self) -> Vec<i32> {
+        self.storage.iter().map(|entry| self.value(entry)).collect()
+    }
+
+    /// Decodes a container of the given kind, whose palette depends on it.
+    pub fn decode_kind<B: Buf>(kind: PaletteKind, buf: &mut B) -> DeRes<Self> {
+        let bits = u8::decode(buf)?;
+
+        let palette = match bits {
+            0 => Palette::Single(VarInt::decode(buf)?.0),
+            _ if kind.is_indirect(bits) => Palette::Indirect(
+                Vec::<VarInt>::decode(buf)?
+                    .into_iter()
+                    .map(|value| value.0)
+                    .collect(),
+            ),
+            _ => Palette::Direct,
+        };
+
+        let data = Vec::<i64>::decode(buf)?;
+        let storage = BitStorage::from_data(kind.storage_bits(bits), kind.size(), data)?;
+        let mut counts = HashMap::new();
+
+        for entry in storage.iter() {
+            let value = match &palette {
+                Palette::Single(value) => *value,
+                Palette::Indirect(palette) => *palette
+                    .get(entry as usize)
+                    .ok_or(DeserializeError::InvalidPaletteIndex(entry))?,
+                Palette::Direct => entry as i32,
+            };
+
+            *counts.entry(value).or_default() += 1;
+        }
+
+        Ok(Self {
+            kind,
+            palette,
+            storage,
+            counts,
+        })
+    }
+
+    fn value(&self, entry: u64) -> i32 {
+        match &self.palette {
+            Palette::Single(value) => *value,
+            Palette::Indirect(palette) => palette[entry as usize],
+            Palette::Direct => entry as i32,
+        }
+    }
+
+    /// Returns the entry for `value`, growing the palette if it is missing.
+    fn entry(&mut self, value: i32) -> u64 {
+        let mut palette = match mem::replace(&mut self.palette, Palette::Direct) {
+            Palette::Single(single) => vec![single],
+            Palette::Indirect(palette) => palette,
+            Palette::Direct => return value as u64,
+        };
+
+        if let Some(index) = palette.iter().position(|v| *v == value) {
+            self.palette = Palette::Indirect(palette);
+
+            return index as u64;
+        }
+
+        // Reuse the slot of a value which is no longer in use.
+        if let Some(index) = palette.iter().position(|v| !self.counts.contains_key(v)) {
+            palette[index] = value;
+            self.palette = Palette::Indirect(palette);
+
+            return index as u64;
+        }
+
+        palette.push(value);
+
+        let index = palette.len() as u64 - 1;
+        let bits = self.kind.bits_for(palette.len());
+
+        if self.kind.is_indirect(bits) {
+            if bits != self.bits() {
+                self.storage = self.storage.resize(bits);
+            }
+
+            self.palette = Palette::Indirect(palette);
+
+            index
+        } else {
+            let entries: Vec<_> = self
+                .storage
+                .iter()
+                .map(|entry| palette[entry as usize] as u64)
+                .collect();
+
+            self.storage = BitStorage::from_entries(bits, entries.into_iter());
+
+            value as u64
+        }
+    }
+}
+
+impl Encode for PalettedContainer {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        self.bits().encode(buf)?;
+
+        match &self.palette {
+            Palette::Single(value) => VarInt(*value).encode(buf)?,
+            Palette::Indirect(palette) => encode_seq::<VarInt, _, B>(
+                palette.len(),
+                palette.iter().map(|value| VarInt(*value)),
+                buf,
+                cx,
+            )?,
+            Palette::Direct => {}
+        }
+
+        let data = self.storage.data();
+
+        encode_seq::<VarInt, _, B>(data.len(), data, buf, cx)
+    }
+}
@@ -1,7 +1,15 @@
-use crate::error::{DeRes, SerRes};
+use bytes::{Buf, BufMut};
 
-use super::Serialize;
+use crate::{
+    error::{DeRes, SerRes},
+    version::ProtocolVersion,
+};
 
+use super::{Context, Decode, Encode};
+
+/// A block position packed into a `u64`.
+///
+/// Since 1.14 the layout is `x << 38 | z << 12 | y`, before that it was `x << 38 | y << 26 | z`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Position {
     pub x: i32,
@@ -9,28 +17,49 @@ pub struct Position {
     pub z: i32,
 }
 
-impl Serialize for Position {
-    fn serialize(&self, buf: &mut bytes::BytesMut) -> SerRes<()> {
+impl Encode for Position {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
         let x = self.x as u64 & 0x1ffffff | ((self.x.is_negative() as u64) << 25);
         let z = self.z as u64 & 0x1ffffff | ((self.z.is_negative() as u64) << 25);
         let y = self.y as u64 & 0x7ff | ((self.y.is_negative() as u64) << 11);
 
-        let pos = x << 38 | z << 12 | y;
+        let pos = if cx.version.since(ProtocolVersion::V1_14) {
+            x << 38 | z << 12 | y
+        } else {
+            x << 38 | y << 26 | z
+        };
 
-        pos.serialize(buf)?;
+        pos.encode(buf)?;
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut bytes::BytesMut) -> DeRes<Self>
-    where
-        Self: Sized,
-    {
-        let pos = u64::deserialize(buf)?;
+impl Decode for Position {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let pos = u64::decode(buf)?;
 
-        let mut x = (pos >> 38) as i32;
-        let mut z = ((pos >> 12) & 0x3ffffff) as i32;
-        let mut y = (pos & 0xfff) as i16;
+        let (mut x, mut y, mut z) = if cx.version.since(ProtocolVersion::V1_14) {
+            (
+                (pos >> 38) as i32,
+                (pos & 0xfff) as i16,
+                ((pos >> 12) & 0x3ffffff) as i32,
+            )
+        } else {
+            (
+                (pos >> 38) as i32,
+                ((pos >> 26) & 0xfff) as i16,
+                (pos & 0x3ffffff) as i32,
+            )
+        };
 
         if x >= 1 << 25 {
             x -= 1 << 26;
@@ -45,3 +74,57 @@ impl Serialize for Position {
         Ok(Self { x, y, z })
     }
 }
+
+impl Position {
+    /// The section containing this position.
+    pub fn section(&self) -> SectionPosition {
+        SectionPosition {
+            x: self.x >> 4,
+            y: self.y as i32 >> 4,
+            z: self.z >> 4,
+        }
+    }
+}
+
+/// The position of a chunk section, packed into an `i64` as `x << 42 | z << 20 | y`, with 22
+/// bits for `x` and `z` and 20 bits for `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SectionPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl SectionPosition {
+    /// The position of the block at section-relative coordinates.
+    pub fn block(&self, x: u8, y: u8, z: u8) -> Position {
+        Position {
+            x: self.x * 16 + (x & 15) as i32,
+            y: (self.y * 16 + (y & 15) as i32) as i16,
+            z: self.z * 16 + (z & 15) as i32,
+        }
+    }
+}
+
+impl Encode for SectionPosition {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        let x = self.x as i64 & 0x3fffff;
+        let z = self.z as i64 & 0x3fffff;
+        let y = self.y as i64 & 0xfffff;
+
+        (x << 42 | z << 20 | y).encode(buf)
+    }
+}
+
+impl Decode for SectionPosition {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        let pos = i64::decode(buf)?;
+
+        // Shifting the fields up to the sign bit and back sign-extends them.
+        Ok(Self {
+            x: (pos >> 42) as i32,
+            y: (pos << 44 >> 44) as i32,
+            z: (pos << 22 >> 42) as i32,
+        })
+    }
+}
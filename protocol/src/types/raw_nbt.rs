@@ -0,0 +1,184 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{
+    error::{DeRes, DeserializeError, SerRes},
+    nbt::MAX_DEPTH,
+    version::ProtocolVersion,
+};
+
+use super::{Context, Decode, Encode};
+
+const TAG_END: u8 = 0;
+
+/// An undecoded NBT tag, which is only checked to be well-formed.
+///
+/// The bytes are kept in the network format of 1.20.2+: the tag type followed by the payload,
+/// without the root name older versions send. A lone `TAG_End` means there is no value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawNbt(Bytes);
+
+impl RawNbt {
+    /// Checks that `bytes` holds exactly one nameless tag.
+    pub fn from_bytes(bytes: Bytes) -> DeRes<Self> {
+        let mut buf = bytes.clone();
+
+        Self::decode_with(&mut buf, &Context::new(ProtocolVersion::V1_20_2))?;
+
+        if buf.has_remaining() {
+            return Err(DeserializeError::BrokenPacket);
+        }
+
+        Ok(Self(bytes))
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.0[..] == [TAG_END]
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
+}
+
+impl Default for RawNbt {
+    fn default() -> Self {
+        Self(Bytes::from_static(&[TAG_END]))
+    }
+}
+
+impl Encode for RawNbt {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        buf.put_u8(self.0[0]);
+
+        if !is_nameless(cx) && !self.is_end() {
+            buf.put_u16(0);
+        }
+
+        buf.put_slice(&self.0[1..]);
+
+        Ok(())
+    }
+}
+
+impl Decode for RawNbt {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        let mut out = BytesMut::new();
+
+        let tag = u8::decode(buf)?;
+        out.put_u8(tag);
+
+        if tag != TAG_END {
+            if !is_nameless(cx) {
+                let len = u16::decode(buf)? as usize;
+                skip(buf, len)?;
+            }
+
+            copy_payload(buf, &mut out, tag, 0)?;
+        }
+
+        Ok(Self(out.freeze()))
+    }
+}
+
+/// The root tag is sent without a name since 1.20.2.
+pub(crate) fn is_nameless(cx: &Context) -> bool {
+    cx.version.since(ProtocolVersion::V1_20_2)
+}
+
+fn skip<B: Buf>(buf: &mut B, len: usize) -> DeRes<()> {
+    if buf.remaining() < len {
+        return Err(DeserializeError::UnexpectedEof);
+    }
+
+    buf.advance(len);
+
+    Ok(())
+}
+
+fn copy<B: Buf>(buf: &mut B, out: &mut BytesMut, len: usize) -> DeRes<()> {
+    if buf.remaining() < len {
+        return Err(DeserializeError::UnexpectedEof);
+    }
+
+    out.put(buf.take(len));
+
+    Ok(())
+}
+
+fn copy_len<B: Buf>(buf: &mut B, out: &mut BytesMut) -> DeRes<usize> {
+    let len = i32::decode(buf)?;
+    out.put_i32(len);
+
+    len.try_into()
+        .map_err(|_| DeserializeError::InvalidLength(len as i64))
+}
+
+fn copy_payload<B: Buf>(buf: &mut B, out: &mut BytesMut, tag: u8, depth: usize) -> DeRes<()> {
+    if depth > MAX_DEPTH {
+        return Err(DeserializeError::BrokenPacket);
+    }
+
+    match tag {
+        1 => copy(buf, out, 1),
+        2 => copy(buf, out, 2),
+        3 | 5 => copy(buf, out, 4),
+        4 | 6 => copy(buf, out, 8),
+        7 => {
+            let len = copy_len(buf, out)?;
+            copy(buf, out, len)
+        }
+        8 => {
+            let len = u16::decode(buf)?;
+            out.put_u16(len);
+            copy(buf, out, len as usize)
+        }
+        9 => {
+            let element = u8::decode(buf)?;
+            out.put_u8(element);
+
+            let len = copy_len(buf, out)?;
+
+            if element == TAG_END && len != 0 {
+                return Err(DeserializeError::BrokenPacket);
+            }
+
+            for _ in 0..len {
+                copy_payload(buf, out, element, depth + 1)?;
+            }
+
+            Ok(())
+        }
+        10 => loop {
+            let tag = u8::decode(buf)?;
+            out.put_u8(tag);
+
+            if tag == TAG_END {
+                return Ok(());
+            }
+
+            copy_payload(buf, out, 8, depth + 1)?;
+            copy_payload(buf, out, tag, depth + 1)?;
+        },
+        11 => {
+            let len = copy_len(buf, out)?;
+            copy(buf, out, len.saturating_mul(4))
+        }
+        12 => {
+            let len = copy_len(buf, out)?;
+            copy(buf, out, len.saturating_mul(8))
+        }
+        _ => Err(DeserializeError::InvalidEnumVariant("NbtTag", tag as isize)),
+    }
+}
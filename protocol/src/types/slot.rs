@@ -1,14 +1,77 @@
-use nbt::Blob;
+use bytes::{Buf, BufMut};
 
-use crate::{macros::data, types::varint::VarInt};
+use crate::{
+    error::{DeRes, SerRes},
+    macros::data,
+    nbt::Nbt,
+    types::varint::VarInt,
+    version::ProtocolVersion,
+};
+
+use super::{Context, Decode, Encode};
+
+/// An item stack in an inventory slot.
+///
+/// Before 1.13.2 there is no presence flag, instead the item id is an `i16` which is `-1` for
+/// empty slots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub data: Option<SlotData>,
+}
 
 data! {
-    Slot {
-        data: Option<SlotData>
-    };
     SlotData {
         id: VarInt,
         count: i8,
-        nbt: Blob
+        nbt: Nbt
+    }
+}
+
+impl Encode for Slot {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
+        self.encode_with(buf, &Context::default())
+    }
+
+    fn encode_with<B: BufMut>(&self, buf: &mut B, cx: &Context) -> SerRes<()> {
+        if cx.version.since(ProtocolVersion::V1_13_2) {
+            return self.data.encode_with(buf, cx);
+        }
+
+        match &self.data {
+            Some(data) => {
+                (data.id.0 as i16).encode(buf)?;
+                data.count.encode(buf)?;
+                data.nbt.encode(buf)
+            }
+            None => (-1i16).encode(buf),
+        }
+    }
+}
+
+impl Decode for Slot {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
+        Self::decode_with(buf, &Context::default())
+    }
+
+    fn decode_with<B: Buf>(buf: &mut B, cx: &Context) -> DeRes<Self> {
+        if cx.version.since(ProtocolVersion::V1_13_2) {
+            return Ok(Self {
+                data: Decode::decode_with(buf, cx)?,
+            });
+        }
+
+        let id = i16::decode(buf)?;
+
+        if id == -1 {
+            return Ok(Self { data: None });
+        }
+
+        Ok(Self {
+            data: Some(SlotData {
+                id: VarInt(id as i32),
+                count: i8::decode(buf)?,
+                nbt: Nbt::decode(buf)?,
+            }),
+        })
     }
 }
@@ -6,11 +6,11 @@ use crate::{
     Decryptor,
 };
 
-use super::Serialize;
+use super::{Decode, Encode};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct VarInt(pub i32);
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct VarLong(pub i64);
 
 impl VarInt {
@@ -60,8 +60,8 @@ pub(crate) fn read_encrypted_varint(buf: &mut BytesMut, decryptor: &mut Decrypto
     Ok(value as i32)
 }
 
-impl Serialize for VarInt {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for VarInt {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         let mut value = self.0 as u32;
 
         loop {
@@ -76,8 +76,10 @@ impl Serialize for VarInt {
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for VarInt {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         let mut value = 0;
         let mut len = 0;
 
@@ -101,8 +103,8 @@ impl Serialize for VarInt {
     }
 }
 
-impl Serialize for VarLong {
-    fn serialize(&self, buf: &mut BytesMut) -> SerRes<()> {
+impl Encode for VarLong {
+    fn encode<B: BufMut>(&self, buf: &mut B) -> SerRes<()> {
         let mut value = self.0 as u64;
 
         loop {
@@ -117,8 +119,10 @@ impl Serialize for VarLong {
 
         Ok(())
     }
+}
 
-    fn deserialize(buf: &mut BytesMut) -> DeRes<Self> {
+impl Decode for VarLong {
+    fn decode<B: Buf>(buf: &mut B) -> DeRes<Self> {
         let mut value = 0;
         let mut len = 0;
 
@@ -0,0 +1,229 @@
+mod table;
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde::{de::Error as _, Deserialize, Deserializer};
+
+use crate::{error::UnknownVersionError, types::varint::VarInt};
+
+use self::table::VERSIONS;
+
+/// A protocol version number as sent in the [`Handshake`](crate::handshake::Handshake).
+///
+/// Versions are ordered by release date. Snapshots since 1.16.4 use numbers with bit 30 set,
+/// which are ordered right before the release they lead up to. Snapshots newer than
+/// [`newest_snapshot`](Self::newest_snapshot) are unknown, and ordered after every release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProtocolVersion(pub i32);
+
+/// A Minecraft release or snapshot and the protocol version it speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionInfo {
+    pub name: &'static str,
+    pub protocol: ProtocolVersion,
+    /// The data version stored in world saves, which exists since 15w32a.
+    pub data_version: Option<i32>,
+}
+
+impl VersionInfo {
+    /// Returns every known version, newest first.
+    pub fn all() -> &'static [VersionInfo] {
+        VERSIONS
+    }
+
+    pub fn by_name(name: &str) -> Option<&'static VersionInfo> {
+        VERSIONS.iter().find(|info| info.name == name)
+    }
+
+    pub fn by_data_version(data_version: i32) -> Option<&'static VersionInfo> {
+        VERSIONS
+            .iter()
+            .find(|info| info.data_version == Some(data_version))
+    }
+}
+
+impl ProtocolVersion {
+    pub const V1_7_2: Self = Self(4);
+    pub const V1_13_2: Self = Self(404);
+    pub const V1_14: Self = Self(477);
+    pub const V1_18: Self = Self(757);
+    pub const V1_18_2: Self = Self(758);
+    pub const V1_19: Self = Self(759);
+    pub const V1_19_1: Self = Self(760);
+    pub const V1_19_2: Self = Self::V1_19_1;
+    pub const V1_19_3: Self = Self(761);
+    pub const V1_19_4: Self = Self(762);
+    pub const V1_20: Self = Self(763);
+    pub const V1_20_2: Self = Self(764);
+    pub const V1_20_3: Self = Self(765);
+    pub const V1_20_5: Self = Self(766);
+    pub const V1_21: Self = Self(767);
+    pub const V1_21_2: Self = Self(768);
+    pub const V1_21_4: Self = Self(769);
+    pub const V1_21_5: Self = Self(770);
+    pub const V1_21_6: Self = Self(771);
+    pub const V1_21_7: Self = Self(772);
+
+    const SNAPSHOT_BIT: i32 = 1 << 30;
+
+    /// The newest release this crate knows about.
+    pub fn latest() -> Self {
+        VERSIONS
+            .iter()
+            .map(|info| info.protocol)
+            .find(|version| !version.is_snapshot())
+            .unwrap_or_default()
+    }
+
+    /// The newest snapshot this crate knows about.
+    pub fn newest_snapshot() -> Self {
+        VERSIONS
+            .iter()
+            .map(|info| info.protocol)
+            .find(|version| version.is_snapshot())
+            .unwrap_or_default()
+    }
+
+    pub fn is_snapshot(self) -> bool {
+        self.0 & Self::SNAPSHOT_BIT != 0
+    }
+
+    /// Returns `true` if this version belongs to a known release or snapshot. This is `false`
+    /// for snapshots newer than [`newest_snapshot`](Self::newest_snapshot).
+    pub fn is_known(self) -> bool {
+        self.versions().next().is_some()
+    }
+
+    /// Returns every release and snapshot using this version, newest first.
+    pub fn versions(self) -> impl Iterator<Item = &'static VersionInfo> {
+        VERSIONS.iter().filter(move |info| info.protocol == self)
+    }
+
+    /// Returns the name of the newest release or snapshot using this version, e.g. `1.19.2` for
+    /// `760`.
+    pub fn name(self) -> Option<&'static str> {
+        self.versions().next().map(|info| info.name)
+    }
+
+    /// Looks up the protocol version of a release or snapshot like `1.19`. Snapshots newer than
+    /// [`newest_snapshot`](Self::newest_snapshot) are not found.
+    pub fn from_name(name: &str) -> Option<Self> {
+        VersionInfo::by_name(name).map(|info| info.protocol)
+    }
+
+    /// Returns `true` if this version is `version` or newer.
+    pub fn since(self, version: Self) -> bool {
+        self >= version
+    }
+
+    /// Returns `true` if this version is older than `version`.
+    pub fn until(self, version: Self) -> bool {
+        self < version
+    }
+
+    /// The key versions are ordered by: the release a snapshot leads up to is the next newer
+    /// non-snapshot entry of the table, unknown snapshots are newer than every release.
+    fn sort_key(self) -> (i32, bool, i32) {
+        if !self.is_snapshot() {
+            return (self.0, true, 0);
+        }
+
+        let release = VERSIONS
+            .iter()
+            .position(|info| info.protocol == self)
+            .and_then(|index| {
+                VERSIONS[..index]
+                    .iter()
+                    .rev()
+                    .find(|info| !info.protocol.is_snapshot())
+            })
+            .map_or(i32::MAX, |info| info.protocol.0);
+
+        (release, false, self.0 & !Self::SNAPSHOT_BIT)
+    }
+}
+
+impl PartialOrd for ProtocolVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ProtocolVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl Default for ProtocolVersion {
+    fn default() -> Self {
+        Self::V1_19
+    }
+}
+
+impl From<i32> for ProtocolVersion {
+    fn from(version: i32) -> Self {
+        Self(version)
+    }
+}
+
+impl From<VarInt> for ProtocolVersion {
+    fn from(version: VarInt) -> Self {
+        Self(version.0)
+    }
+}
+
+impl From<ProtocolVersion> for VarInt {
+    fn from(version: ProtocolVersion) -> Self {
+        Self(version.0)
+    }
+}
+
+impl FromStr for ProtocolVersion {
+    type Err = UnknownVersionError;
+
+    /// Parses either a raw protocol number like `759` or a version name like `1.19`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(Self)
+            .ok()
+            .or_else(|| Self::from_name(s))
+            .ok_or_else(|| UnknownVersionError(s.to_owned()))
+    }
+}
+
+impl<'de> Deserialize<'de> for ProtocolVersion {
+    /// Accepts the same numbers and names as [`FromStr`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(i32),
+            Name(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(version) => Ok(Self(version)),
+            Repr::Name(name) => name.parse().map_err(D::Error::custom),
+        }
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Looks up the packet id for `version` in a table of `(since, id)` entries sorted by version.
+///
+/// An id of `None` marks a version in which the packet was removed.
+pub(crate) fn packet_id(
+    ids: &[(ProtocolVersion, Option<i32>)],
+    version: ProtocolVersion,
+) -> Option<i32> {
+    ids.iter()
+        .take_while(|(since, _)| version.since(*since))
+        .last()
+        .and_then(|(_, id)| *id)
+}
@@ -0,0 +1,731 @@
+use super::{ProtocolVersion, VersionInfo};
+
+macro_rules! versions {
+    ($($name:literal => $protocol:literal, $data:tt;)*) => {
+        &[$(VersionInfo {
+            name: $name,
+            protocol: ProtocolVersion($protocol),
+            data_version: versions!(@data $data),
+        }),*]
+    };
+    (@data _) => {
+        None
+    };
+    (@data $data:literal) => {
+        Some($data)
+    };
+}
+
+/// Every known release and snapshot since 1.7 (the netty rewrite), newest first, up to 1.21.8.
+pub(super) static VERSIONS: &[VersionInfo] = versions! {
+    "1.21.8" => 772, 4440;
+    "1.21.8-rc1" => 0x40000103, 4439;
+    "1.21.7" => 772, 4438;
+    "1.21.7-rc2" => 0x40000102, 4437;
+    "1.21.7-rc1" => 0x40000101, 4436;
+    "1.21.6" => 771, 4435;
+    "1.21.6-rc1" => 0x40000100, 4431;
+    "1.21.6-pre4" => 0x400000ff, 4430;
+    "1.21.6-pre3" => 0x400000fe, 4429;
+    "1.21.6-pre2" => 0x400000fd, 4428;
+    "1.21.6-pre1" => 0x400000fc, 4427;
+    "25w21a" => 0x400000fb, 4426;
+    "25w20a" => 0x400000fa, 4425;
+    "25w19a" => 0x400000f9, 4424;
+    "25w18a" => 0x400000f8, 4423;
+    "25w17a" => 0x400000f7, 4422;
+    "25w16a" => 0x400000f6, 4421;
+    "25w15a" => 0x400000f5, 4420;
+    "25w14craftmine" => 0x400000f4, _;
+    "1.21.5" => 770, 4325;
+    "1.21.5-rc2" => 0x400000f3, 4324;
+    "1.21.5-rc1" => 0x400000f2, 4323;
+    "1.21.5-pre3" => 0x400000f1, 4322;
+    "1.21.5-pre2" => 0x400000f0, 4321;
+    "1.21.5-pre1" => 0x400000ef, 4320;
+    "25w10a" => 0x400000ee, 4319;
+    "25w09b" => 0x400000ed, 4318;
+    "25w09a" => 0x400000ec, 4317;
+    "25w08a" => 0x400000eb, 4316;
+    "25w07a" => 0x400000ea, 4315;
+    "25w06a" => 0x400000e9, 4313;
+    "25w05a" => 0x400000e8, 4310;
+    "25w04a" => 0x400000e7, 4308;
+    "25w03a" => 0x400000e6, 4304;
+    "25w02a" => 0x400000e5, 4298;
+    "1.21.4" => 769, 4189;
+    "1.21.4-rc3" => 0x400000e4, 4188;
+    "1.21.4-rc2" => 0x400000e3, 4186;
+    "1.21.4-rc1" => 0x400000e2, 4184;
+    "1.21.4-pre3" => 0x400000e1, 4183;
+    "1.21.4-pre2" => 0x400000e0, 4182;
+    "1.21.4-pre1" => 0x400000df, 4179;
+    "24w46a" => 0x400000de, 4178;
+    "24w45a" => 0x400000dd, 4177;
+    "24w44a" => 0x400000dc, 4174;
+    "1.21.3" => 768, 4082;
+    "1.21.2" => 768, 4080;
+    "1.21.2-rc2" => 0x400000db, 4079;
+    "1.21.2-rc1" => 0x400000da, 4078;
+    "1.21.2-pre5" => 0x400000d9, 4077;
+    "1.21.2-pre4" => 0x400000d8, 4076;
+    "1.21.2-pre3" => 0x400000d7, 4075;
+    "1.21.2-pre2" => 0x400000d6, 4074;
+    "1.21.2-pre1" => 0x400000d5, 4073;
+    "24w40a" => 0x400000d4, 4072;
+    "24w39a" => 0x400000d3, 4069;
+    "24w38a" => 0x400000d2, 4066;
+    "24w37a" => 0x400000d1, 4065;
+    "24w36a" => 0x400000d0, 4063;
+    "24w35a" => 0x400000cf, 4062;
+    "24w34a" => 0x400000ce, 4060;
+    "24w33a" => 0x400000cd, 4058;
+    "1.21.1" => 767, 3955;
+    "1.21.1-rc1" => 0x400000cc, 3954;
+    "1.21" => 767, 3953;
+    "1.21-rc1" => 0x400000cb, 3952;
+    "1.21-pre4" => 0x400000ca, 3951;
+    "1.21-pre3" => 0x400000c9, 3950;
+    "1.21-pre2" => 0x400000c8, 3949;
+    "1.21-pre1" => 0x400000c7, 3948;
+    "24w21b" => 0x400000c6, 3947;
+    "24w21a" => 0x400000c5, 3946;
+    "24w20a" => 0x400000c4, 3944;
+    "24w19b" => 0x400000c3, 3942;
+    "24w19a" => 0x400000c2, 3941;
+    "24w18a" => 0x400000c1, 3940;
+    "1.20.6" => 766, 3839;
+    "1.20.6-rc1" => 0x400000c0, 3838;
+    "1.20.5" => 766, 3837;
+    "1.20.5-rc3" => 0x400000bf, 3836;
+    "1.20.5-rc2" => 0x400000be, 3835;
+    "1.20.5-rc1" => 0x400000bd, 3834;
+    "1.20.5-pre4" => 0x400000bc, 3832;
+    "1.20.5-pre3" => 0x400000bb, 3831;
+    "1.20.5-pre2" => 0x400000ba, 3830;
+    "1.20.5-pre1" => 0x400000b9, 3829;
+    "24w14a" => 0x400000b8, 3827;
+    "24w14potato" => 0x400000b7, _;
+    "24w13a" => 0x400000b6, 3826;
+    "24w12a" => 0x400000b5, 3824;
+    "24w11a" => 0x400000b4, 3823;
+    "24w10a" => 0x400000b3, 3821;
+    "24w09a" => 0x400000b2, 3819;
+    "24w07a" => 0x400000b1, 3817;
+    "24w06a" => 0x400000b0, 3815;
+    "24w05b" => 0x400000af, 3811;
+    "24w05a" => 0x400000ae, 3809;
+    "24w04a" => 0x400000ad, 3806;
+    "24w03b" => 0x400000ac, 3805;
+    "24w03a" => 0x400000ab, 3804;
+    "23w51b" => 0x400000aa, 3802;
+    "23w51a" => 0x400000a9, 3801;
+    "1.20.4" => 765, 3700;
+    "1.20.4-rc1" => 0x400000a8, 3699;
+    "1.20.3" => 765, 3698;
+    "1.20.3-rc1" => 0x400000a7, 3697;
+    "1.20.3-pre4" => 0x400000a6, 3696;
+    "1.20.3-pre3" => 0x400000a5, 3695;
+    "1.20.3-pre2" => 0x400000a4, 3694;
+    "1.20.3-pre1" => 0x400000a3, 3693;
+    "23w46a" => 0x400000a2, 3691;
+    "23w45a" => 0x400000a1, 3690;
+    "23w44a" => 0x400000a0, 3688;
+    "23w43b" => 0x4000009f, 3687;
+    "23w43a" => 0x4000009e, 3686;
+    "23w42a" => 0x4000009d, 3684;
+    "23w41a" => 0x4000009c, 3681;
+    "23w40a" => 0x4000009b, 3679;
+    "1.20.2" => 764, 3578;
+    "1.20.2-rc2" => 0x40000099, 3577;
+    "1.20.2-rc1" => 0x40000098, 3576;
+    "1.20.2-pre4" => 0x40000097, 3575;
+    "1.20.2-pre3" => 0x40000096, 3574;
+    "1.20.2-pre2" => 0x40000095, 3573;
+    "1.20.2-pre1" => 0x40000094, 3572;
+    "23w35a" => 0x40000093, 3571;
+    "23w33a" => 0x40000092, 3570;
+    "23w32a" => 0x40000091, 3569;
+    "23w31a" => 0x40000090, 3567;
+    "1.20.1" => 763, 3465;
+    "1.20.1-rc1" => 0x4000008e, 3464;
+    "1.20" => 763, 3463;
+    "1.20-rc1" => 0x4000008d, 3462;
+    "1.20-pre7" => 0x4000008c, 3461;
+    "1.20-pre6" => 0x4000008b, 3460;
+    "1.20-pre5" => 0x4000008a, 3458;
+    "1.20-pre4" => 0x40000089, 3457;
+    "1.20-pre3" => 0x40000088, 3456;
+    "1.20-pre2" => 0x40000087, 3455;
+    "1.20-pre1" => 0x40000086, 3454;
+    "23w18a" => 0x40000085, 3453;
+    "23w17a" => 0x40000084, 3452;
+    "23w16a" => 0x40000083, 3449;
+    "23w14a" => 0x40000082, 3445;
+    "23w13a_or_b" => 0x40000081, 3444;
+    "23w13a" => 0x40000080, 3443;
+    "23w12a" => 0x4000007f, 3442;
+    "1.19.4" => 762, 3337;
+    "1.19.4-rc3" => 0x4000007e, 3336;
+    "1.19.4-rc2" => 0x4000007d, 3335;
+    "1.19.4-rc1" => 0x4000007c, 3334;
+    "1.19.4-pre4" => 0x4000007b, 3333;
+    "1.19.4-pre3" => 0x4000007a, 3332;
+    "1.19.4-pre2" => 0x40000079, 3331;
+    "1.19.4-pre1" => 0x40000078, 3330;
+    "23w07a" => 0x40000077, 3329;
+    "23w06a" => 0x40000076, 3326;
+    "23w05a" => 0x40000075, 3323;
+    "23w04a" => 0x40000074, 3321;
+    "23w03a" => 0x40000073, 3320;
+    "1.19.3" => 761, 3218;
+    "1.19.3-rc3" => 0x40000072, 3217;
+    "1.19.3-rc2" => 0x40000071, 3216;
+    "1.19.3-rc1" => 0x40000070, 3215;
+    "1.19.3-pre3" => 0x4000006f, 3213;
+    "1.19.3-pre2" => 0x4000006e, 3212;
+    "1.19.3-pre1" => 0x4000006d, 3211;
+    "22w46a" => 0x4000006c, 3210;
+    "22w45a" => 0x4000006b, 3208;
+    "22w44a" => 0x4000006a, 3207;
+    "22w43a" => 0x40000069, 3206;
+    "22w42a" => 0x40000068, 3205;
+    "1.19.2" => 760, 3120;
+    "1.19.2-rc2" => 0x40000067, 3119;
+    "1.19.2-rc1" => 0x40000066, 3118;
+    "1.19.1" => 760, 3117;
+    "1.19.1-rc3" => 0x40000065, 3116;
+    "1.19.1-rc2" => 0x40000064, 3115;
+    "1.19.1-pre6" => 0x40000063, 3114;
+    "1.19.1-pre5" => 0x40000062, 3113;
+    "1.19.1-pre4" => 0x40000061, 3112;
+    "1.19.1-pre3" => 0x40000060, 3111;
+    "1.19.1-pre2" => 0x4000005f, 3110;
+    "1.19.1-rc1" => 0x4000005e, 3109;
+    "1.19.1-pre1" => 0x4000005d, 3107;
+    "22w24a" => 0x4000005c, 3106;
+    "1.19" => 759, 3105;
+    "1.19-rc2" => 0x4000005b, 3104;
+    "1.19-rc1" => 0x4000005a, 3103;
+    "1.19-pre5" => 0x40000059, 3102;
+    "1.19-pre4" => 0x40000058, 3101;
+    "1.19-pre3" => 0x40000057, 3100;
+    "1.19-pre2" => 0x40000056, 3099;
+    "1.19-pre1" => 0x40000055, 3098;
+    "22w19a" => 0x40000054, 3096;
+    "22w18a" => 0x40000053, 3095;
+    "22w17a" => 0x40000052, 3093;
+    "22w16b" => 0x40000051, 3092;
+    "22w16a" => 0x40000050, 3091;
+    "22w15a" => 0x4000004f, 3089;
+    "22w14a" => 0x4000004e, 3088;
+    "22w13oneblockatatime" => 0x4000004d, 3076;
+    "22w13a" => 0x4000004c, 3085;
+    "22w12a" => 0x4000004b, 3082;
+    "22w11a" => 0x4000004a, 3080;
+    "1.18.2" => 758, 2975;
+    "1.18.2-rc1" => 0x40000049, 2974;
+    "1.18.2-pre3" => 0x40000048, 2973;
+    "1.18.2-pre2" => 0x40000047, 2972;
+    "1.18.2-pre1" => 0x40000046, 2971;
+    "22w07a" => 0x40000044, 2969;
+    "22w06a" => 0x40000043, 2968;
+    "22w05a" => 0x40000042, 2967;
+    "22w03a" => 0x40000041, 2966;
+    "1.18.1" => 757, 2865;
+    "1.18.1-rc3" => 0x40000040, 2864;
+    "1.18.1-rc2" => 0x4000003f, 2863;
+    "1.18.1-rc1" => 0x4000003e, 2862;
+    "1.18.1-pre1" => 0x4000003d, 2861;
+    "1.18" => 757, 2860;
+    "1.18-rc4" => 0x4000003c, 2859;
+    "1.18-rc3" => 0x4000003b, 2858;
+    "1.18-rc2" => 0x4000003a, 2857;
+    "1.18-rc1" => 0x40000039, 2856;
+    "1.18-pre8" => 0x40000038, 2855;
+    "1.18-pre7" => 0x40000037, 2854;
+    "1.18-pre6" => 0x40000036, 2853;
+    "1.18-pre5" => 0x40000035, 2851;
+    "1.18-pre4" => 0x40000034, 2850;
+    "1.18-pre3" => 0x40000033, 2849;
+    "1.18-pre2" => 0x40000032, 2848;
+    "1.18-pre1" => 0x40000031, 2847;
+    "21w44a" => 0x40000030, 2845;
+    "21w43a" => 0x4000002f, 2844;
+    "21w42a" => 0x4000002e, 2840;
+    "21w41a" => 0x4000002d, 2839;
+    "21w40a" => 0x4000002c, 2838;
+    "21w39a" => 0x4000002b, 2836;
+    "21w38a" => 0x4000002a, 2835;
+    "21w37a" => 0x40000029, 2834;
+    "1.17.1" => 756, 2730;
+    "1.17.1-rc2" => 0x40000028, 2729;
+    "1.17.1-rc1" => 0x40000027, 2728;
+    "1.17.1-pre3" => 0x40000026, 2727;
+    "1.17.1-pre2" => 0x40000025, 2726;
+    "1.17.1-pre1" => 0x40000024, 2725;
+    "1.17" => 755, 2724;
+    "1.17-rc2" => 0x40000023, 2723;
+    "1.17-rc1" => 0x40000022, 2722;
+    "1.17-pre5" => 0x40000021, 2721;
+    "1.17-pre4" => 0x40000020, 2720;
+    "1.17-pre3" => 0x4000001f, 2719;
+    "1.17-pre2" => 0x4000001e, 2718;
+    "1.17-pre1" => 0x4000001d, 2716;
+    "21w20a" => 0x4000001c, 2715;
+    "21w19a" => 0x4000001b, 2714;
+    "21w18a" => 0x4000001a, 2713;
+    "21w17a" => 0x40000019, 2712;
+    "21w16a" => 0x40000017, 2711;
+    "21w15a" => 0x40000016, 2709;
+    "21w14a" => 0x40000015, 2706;
+    "21w13a" => 0x40000014, 2705;
+    "21w11a" => 0x40000013, 2703;
+    "21w10a" => 0x40000012, 2699;
+    "21w08b" => 0x40000011, 2698;
+    "21w08a" => 0x40000010, 2697;
+    "21w07a" => 0x4000000f, 2695;
+    "21w06a" => 0x4000000e, 2694;
+    "21w05b" => 0x4000000d, 2692;
+    "21w05a" => 0x4000000c, 2690;
+    "21w03a" => 0x4000000b, 2689;
+    "1.16.5" => 754, 2586;
+    "1.16.5-rc1" => 0x4000000a, 2585;
+    "20w51a" => 0x40000009, 2687;
+    "20w49a" => 0x40000008, 2685;
+    "20w48a" => 0x40000007, 2683;
+    "20w46a" => 0x40000006, 2682;
+    "20w45a" => 0x40000005, 2681;
+    "1.16.4" => 754, 2584;
+    "1.16.4-rc1" => 0x40000003, 2583;
+    "1.16.4-pre2" => 0x40000002, 2582;
+    "1.16.4-pre1" => 0x40000001, 2581;
+    "1.16.3" => 753, 2580;
+    "1.16.3-rc1" => 752, 2579;
+    "1.16.2" => 751, 2578;
+    "1.16.2-rc2" => 750, 2577;
+    "1.16.2-rc1" => 749, 2576;
+    "1.16.2-pre3" => 748, 2575;
+    "1.16.2-pre2" => 746, 2574;
+    "1.16.2-pre1" => 744, 2573;
+    "20w30a" => 743, 2572;
+    "20w29a" => 741, 2571;
+    "20w28a" => 740, 2570;
+    "20w27a" => 738, 2569;
+    "1.16.1" => 736, 2567;
+    "1.16" => 735, 2566;
+    "1.16-rc1" => 734, 2565;
+    "1.16-pre8" => 733, 2564;
+    "1.16-pre7" => 732, 2563;
+    "1.16-pre6" => 730, 2562;
+    "1.16-pre5" => 729, 2561;
+    "1.16-pre4" => 727, 2560;
+    "1.16-pre3" => 725, 2559;
+    "1.16-pre2" => 722, 2557;
+    "1.16-pre1" => 721, 2556;
+    "20w22a" => 719, 2555;
+    "20w21a" => 718, 2554;
+    "20w20b" => 717, 2537;
+    "20w20a" => 716, 2536;
+    "20w19a" => 715, 2534;
+    "20w18a" => 714, 2532;
+    "20w17a" => 713, 2529;
+    "20w16a" => 712, 2526;
+    "20w15a" => 711, 2525;
+    "20w14a" => 710, 2524;
+    "20w14infinite" => 709, 2522;
+    "20w13b" => 709, 2521;
+    "20w13a" => 708, 2520;
+    "20w12a" => 707, 2515;
+    "20w11a" => 706, 2513;
+    "20w10a" => 705, 2512;
+    "20w09a" => 704, 2510;
+    "20w08a" => 703, 2507;
+    "20w07a" => 702, 2506;
+    "20w06a" => 701, 2504;
+    "1.15.2" => 578, 2230;
+    "1.15.2-pre2" => 577, 2229;
+    "1.15.2-pre1" => 576, 2228;
+    "1.15.1" => 575, 2227;
+    "1.15.1-pre1" => 574, 2226;
+    "1.15" => 573, 2225;
+    "1.15-pre7" => 572, 2224;
+    "1.15-pre6" => 571, 2223;
+    "1.15-pre5" => 570, 2222;
+    "1.15-pre4" => 569, 2221;
+    "1.15-pre3" => 567, 2220;
+    "1.15-pre2" => 566, 2219;
+    "1.15-pre1" => 565, 2218;
+    "19w46b" => 564, 2217;
+    "19w46a" => 563, 2216;
+    "19w45b" => 562, 2215;
+    "19w45a" => 561, 2214;
+    "19w44a" => 560, 2213;
+    "19w42a" => 559, 2212;
+    "19w41a" => 558, 2210;
+    "19w40a" => 557, 2208;
+    "19w39a" => 556, 2207;
+    "19w38b" => 555, 2206;
+    "19w38a" => 554, 2205;
+    "19w37a" => 553, 2204;
+    "19w36a" => 552, 2203;
+    "19w35a" => 551, 2201;
+    "19w34a" => 550, 2200;
+    "1.14.4" => 498, 1976;
+    "1.14.4-pre7" => 497, 1975;
+    "1.14.4-pre6" => 496, 1974;
+    "1.14.4-pre5" => 495, 1973;
+    "1.14.4-pre4" => 494, 1972;
+    "1.14.4-pre3" => 493, 1971;
+    "1.14.4-pre2" => 492, 1970;
+    "1.14.4-pre1" => 491, 1969;
+    "1.14.3" => 490, 1968;
+    "1.14.3-pre4" => 489, 1967;
+    "1.14.3-pre3" => 488, 1966;
+    "1.14.3-pre2" => 487, 1965;
+    "1.14.3-pre1" => 486, 1964;
+    "1.14.2" => 485, 1963;
+    "1.14.2-pre4" => 484, 1962;
+    "1.14.2-pre3" => 483, 1960;
+    "1.14.2-pre2" => 482, 1959;
+    "1.14.2-pre1" => 481, 1958;
+    "1.14.1" => 480, 1957;
+    "1.14.1-pre2" => 479, 1956;
+    "1.14.1-pre1" => 478, 1955;
+    "1.14" => 477, 1952;
+    "1.14-pre5" => 476, 1951;
+    "1.14-pre4" => 475, 1950;
+    "1.14-pre3" => 474, 1949;
+    "1.14-pre2" => 473, 1948;
+    "1.14-pre1" => 472, 1947;
+    "19w14b" => 471, 1945;
+    "19w14a" => 470, 1944;
+    "19w13b" => 469, 1943;
+    "19w13a" => 468, 1942;
+    "19w12b" => 467, 1941;
+    "19w12a" => 466, 1940;
+    "19w11b" => 465, 1938;
+    "19w11a" => 464, 1937;
+    "19w09a" => 463, 1935;
+    "19w08b" => 462, 1934;
+    "19w08a" => 461, 1933;
+    "19w07a" => 460, 1932;
+    "19w06a" => 459, 1931;
+    "19w05a" => 458, 1930;
+    "19w04b" => 457, 1927;
+    "19w04a" => 456, 1926;
+    "19w03c" => 455, 1924;
+    "19w03b" => 454, 1923;
+    "19w03a" => 453, 1922;
+    "19w02a" => 452, 1921;
+    "18w50a" => 451, 1919;
+    "18w49a" => 450, 1916;
+    "18w48b" => 449, 1915;
+    "18w48a" => 448, 1914;
+    "18w47b" => 447, 1913;
+    "18w47a" => 446, 1912;
+    "18w46a" => 445, 1910;
+    "18w45a" => 444, 1908;
+    "18w44a" => 443, 1907;
+    "18w43c" => 442, 1903;
+    "18w43b" => 441, 1902;
+    "18w43a" => 441, 1902;
+    "1.13.2" => 404, 1631;
+    "1.13.2-pre2" => 403, 1630;
+    "1.13.2-pre1" => 402, 1629;
+    "1.13.1" => 401, 1628;
+    "1.13.1-pre2" => 400, 1627;
+    "1.13.1-pre1" => 399, 1626;
+    "18w33a" => 398, 1625;
+    "18w32a" => 397, 1623;
+    "18w31a" => 396, 1622;
+    "18w30b" => 395, 1621;
+    "18w30a" => 394, 1620;
+    "1.13" => 393, 1519;
+    "1.13-pre10" => 392, 1518;
+    "1.13-pre9" => 391, 1517;
+    "1.13-pre8" => 390, 1516;
+    "1.13-pre7" => 389, 1513;
+    "1.13-pre6" => 388, 1512;
+    "1.13-pre5" => 387, 1511;
+    "1.13-pre4" => 386, 1504;
+    "1.13-pre3" => 385, 1503;
+    "1.13-pre2" => 384, 1502;
+    "1.13-pre1" => 383, 1501;
+    "18w22c" => 382, 1499;
+    "18w22b" => 381, 1498;
+    "18w22a" => 380, 1497;
+    "18w21b" => 379, 1496;
+    "18w21a" => 378, 1495;
+    "18w20c" => 377, 1493;
+    "18w20b" => 376, 1491;
+    "18w20a" => 375, 1489;
+    "18w19b" => 374, 1485;
+    "18w19a" => 373, 1484;
+    "18w16a" => 372, 1483;
+    "18w15a" => 371, 1482;
+    "18w14b" => 370, 1481;
+    "18w14a" => 369, 1479;
+    "18w11a" => 368, 1478;
+    "18w10d" => 367, 1477;
+    "18w10c" => 366, 1476;
+    "18w10b" => 365, 1474;
+    "18w10a" => 364, 1473;
+    "18w09a" => 363, 1472;
+    "18w08b" => 362, 1471;
+    "18w08a" => 361, 1470;
+    "18w07c" => 360, 1469;
+    "18w07b" => 359, 1468;
+    "18w07a" => 358, 1467;
+    "18w06a" => 357, 1466;
+    "18w05a" => 356, 1464;
+    "18w03b" => 355, 1463;
+    "18w03a" => 354, 1462;
+    "18w02a" => 353, 1461;
+    "18w01a" => 352, 1459;
+    "17w50a" => 351, 1457;
+    "17w49b" => 350, 1455;
+    "17w49a" => 349, 1454;
+    "17w48a" => 348, 1453;
+    "17w47b" => 347, 1452;
+    "17w47a" => 346, 1451;
+    "17w46a" => 345, 1449;
+    "17w45b" => 344, 1448;
+    "17w45a" => 343, 1447;
+    "17w43b" => 342, 1445;
+    "17w43a" => 341, 1444;
+    "1.12.2" => 340, 1343;
+    "1.12.2-pre2" => 339, 1342;
+    "1.12.2-pre1" => 339, 1341;
+    "1.12.1" => 338, 1241;
+    "1.12.1-pre1" => 337, 1240;
+    "17w31a" => 336, 1239;
+    "1.12" => 335, 1139;
+    "1.12-pre7" => 334, 1138;
+    "1.12-pre6" => 333, 1137;
+    "1.12-pre5" => 332, 1136;
+    "1.12-pre4" => 331, 1135;
+    "1.12-pre3" => 330, 1134;
+    "1.12-pre2" => 329, 1133;
+    "1.12-pre1" => 328, 1132;
+    "17w18b" => 327, 1131;
+    "17w18a" => 326, 1130;
+    "17w17b" => 325, 1129;
+    "17w17a" => 324, 1128;
+    "17w16b" => 323, 1127;
+    "17w16a" => 322, 1126;
+    "17w15a" => 321, 1125;
+    "17w14a" => 320, 1124;
+    "17w13b" => 319, 1123;
+    "17w13a" => 318, 1122;
+    "17w06a" => 317, 1022;
+    "1.11.2" => 316, 922;
+    "1.11.1" => 316, 921;
+    "16w50a" => 316, 920;
+    "1.11" => 315, 819;
+    "1.11-pre1" => 314, 818;
+    "16w44a" => 313, 817;
+    "16w43a" => 313, 817;
+    "16w42a" => 312, 815;
+    "16w41a" => 311, 814;
+    "16w40a" => 310, 813;
+    "16w39c" => 309, 812;
+    "16w39b" => 308, 811;
+    "16w39a" => 307, 809;
+    "16w38a" => 306, 807;
+    "16w36a" => 305, 805;
+    "16w35a" => 304, 803;
+    "16w33a" => 303, 802;
+    "16w32b" => 302, 801;
+    "16w32a" => 301, 800;
+    "1.10.2" => 210, 512;
+    "1.10.1" => 210, 511;
+    "1.10" => 210, 510;
+    "1.10-pre2" => 205, 507;
+    "1.10-pre1" => 204, 506;
+    "16w21b" => 203, 504;
+    "16w21a" => 202, 503;
+    "16w20a" => 201, 501;
+    "1.9.4" => 110, 184;
+    "1.9.3" => 110, 183;
+    "1.9.3-pre3" => 110, 182;
+    "1.9.3-pre2" => 110, 181;
+    "1.9.3-pre1" => 109, 180;
+    "16w15b" => 109, 179;
+    "16w15a" => 109, 178;
+    "16w14a" => 109, 177;
+    "1.9.2" => 109, 176;
+    "1.9.1" => 108, 175;
+    "1.9.1-pre3" => 108, 172;
+    "1.9.1-pre2" => 108, 171;
+    "1.9.1-pre1" => 107, 170;
+    "1.9" => 107, 169;
+    "1.9-pre4" => 106, 168;
+    "1.9-pre3" => 105, 167;
+    "1.9-pre2" => 104, 165;
+    "1.9-pre1" => 103, 164;
+    "16w07b" => 102, 163;
+    "16w07a" => 101, 162;
+    "16w06a" => 100, 161;
+    "16w05b" => 99, 160;
+    "16w05a" => 98, 159;
+    "16w04a" => 97, 158;
+    "16w03a" => 96, 157;
+    "16w02a" => 95, 156;
+    "15w51b" => 94, 155;
+    "15w51a" => 93, 154;
+    "15w50a" => 92, 153;
+    "15w49b" => 91, 152;
+    "15w49a" => 90, 151;
+    "15w47c" => 89, 150;
+    "15w47b" => 88, 149;
+    "15w47a" => 87, 148;
+    "15w46a" => 86, 146;
+    "15w45a" => 85, 145;
+    "15w44b" => 84, 143;
+    "15w44a" => 83, 142;
+    "15w43c" => 82, 141;
+    "15w43b" => 81, 140;
+    "15w43a" => 80, 139;
+    "15w42a" => 79, 138;
+    "15w41b" => 78, 137;
+    "15w41a" => 77, 136;
+    "15w40b" => 76, 134;
+    "15w40a" => 75, 133;
+    "15w39c" => 74, 132;
+    "15w39b" => 74, 131;
+    "15w39a" => 74, 130;
+    "15w38b" => 73, 129;
+    "15w38a" => 72, 128;
+    "15w37a" => 71, 127;
+    "15w36d" => 70, 126;
+    "15w36c" => 69, 125;
+    "15w36b" => 68, 124;
+    "15w36a" => 67, 123;
+    "15w35e" => 66, 122;
+    "15w35d" => 65, 121;
+    "15w35c" => 64, 120;
+    "15w35b" => 63, 119;
+    "15w35a" => 62, 118;
+    "15w34d" => 61, 117;
+    "15w34c" => 60, 116;
+    "15w34b" => 59, 115;
+    "15w34a" => 58, 114;
+    "15w33c" => 57, 112;
+    "15w33b" => 56, 111;
+    "15w33a" => 55, 111;
+    "15w32c" => 54, 104;
+    "15w32b" => 53, 103;
+    "15w32a" => 52, 100;
+    "15w31c" => 51, _;
+    "15w31b" => 50, _;
+    "15w31a" => 49, _;
+    "15w14a" => 48, _;
+    "1.8.9" => 47, _;
+    "1.8.8" => 47, _;
+    "1.8.7" => 47, _;
+    "1.8.6" => 47, _;
+    "1.8.5" => 47, _;
+    "1.8.4" => 47, _;
+    "1.8.3" => 47, _;
+    "1.8.2" => 47, _;
+    "1.8.2-pre7" => 47, _;
+    "1.8.2-pre6" => 47, _;
+    "1.8.2-pre5" => 47, _;
+    "1.8.2-pre4" => 47, _;
+    "1.8.2-pre3" => 47, _;
+    "1.8.2-pre2" => 47, _;
+    "1.8.2-pre1" => 47, _;
+    "1.8.1" => 47, _;
+    "1.8.1-pre5" => 47, _;
+    "1.8.1-pre4" => 47, _;
+    "1.8.1-pre3" => 47, _;
+    "1.8.1-pre2" => 47, _;
+    "1.8.1-pre1" => 47, _;
+    "1.8" => 47, _;
+    "1.8-pre3" => 46, _;
+    "1.8-pre2" => 45, _;
+    "1.8-pre1" => 44, _;
+    "14w34d" => 43, _;
+    "14w34c" => 42, _;
+    "14w34b" => 41, _;
+    "14w34a" => 40, _;
+    "14w33c" => 39, _;
+    "14w33b" => 38, _;
+    "14w33a" => 37, _;
+    "14w32d" => 36, _;
+    "14w32c" => 35, _;
+    "14w32b" => 34, _;
+    "14w32a" => 33, _;
+    "14w31a" => 32, _;
+    "14w30c" => 31, _;
+    "14w30b" => 30, _;
+    "14w30a" => 30, _;
+    "14w29b" => 29, _;
+    "14w29a" => 29, _;
+    "14w28b" => 28, _;
+    "14w28a" => 27, _;
+    "14w27b" => 26, _;
+    "14w27a" => 26, _;
+    "14w26c" => 25, _;
+    "14w26b" => 24, _;
+    "14w26a" => 23, _;
+    "14w25b" => 22, _;
+    "14w25a" => 21, _;
+    "14w21b" => 20, _;
+    "14w21a" => 19, _;
+    "14w20b" => 18, _;
+    "14w20a" => 18, _;
+    "14w19a" => 17, _;
+    "14w18b" => 16, _;
+    "14w18a" => 16, _;
+    "14w17a" => 15, _;
+    "14w11b" => 14, _;
+    "14w11a" => 14, _;
+    "14w10c" => 13, _;
+    "14w10b" => 13, _;
+    "14w10a" => 13, _;
+    "14w08a" => 12, _;
+    "14w07a" => 11, _;
+    "14w06b" => 10, _;
+    "14w06a" => 10, _;
+    "14w05b" => 9, _;
+    "14w05a" => 9, _;
+    "14w04b" => 8, _;
+    "14w04a" => 7, _;
+    "14w03b" => 6, _;
+    "14w03a" => 6, _;
+    "14w02c" => 5, _;
+    "14w02b" => 5, _;
+    "14w02a" => 5, _;
+    "1.7.10" => 5, _;
+    "1.7.10-pre4" => 5, _;
+    "1.7.10-pre3" => 5, _;
+    "1.7.10-pre2" => 5, _;
+    "1.7.10-pre1" => 5, _;
+    "1.7.9" => 5, _;
+    "1.7.8" => 5, _;
+    "1.7.7" => 5, _;
+    "1.7.6" => 5, _;
+    "1.7.6-pre2" => 5, _;
+    "1.7.6-pre1" => 5, _;
+    "1.7.5" => 4, _;
+    "1.7.4" => 4, _;
+    "1.7.3-pre" => 4, _;
+    "13w49a" => 4, _;
+    "13w48b" => 4, _;
+    "13w48a" => 4, _;
+    "13w47e" => 4, _;
+    "13w47d" => 4, _;
+    "13w47c" => 4, _;
+    "13w47b" => 4, _;
+    "13w47a" => 4, _;
+    "1.7.2" => 4, _;
+    "1.7.1-pre" => 3, _;
+    "1.7-pre" => 3, _;
+    "13w43a" => 2, _;
+    "13w42b" => 1, _;
+    "13w42a" => 1, _;
+    "13w41b" => 0, _;
+    "13w41a" => 0, _;
+};
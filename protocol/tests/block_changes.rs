@@ -0,0 +1,150 @@
+use arrow_protocol::{
+    nbt::{Compound, Nbt, Tag},
+    play::{
+        BlockAction, BlockDestroyStage, BlockEntityData, BlockUpdate, Face, Play, SectionBlock,
+        UpdateSectionBlocks,
+    },
+    registry::{Block, BlockEntityType, BlockState},
+    types::{
+        position::{Position, SectionPosition},
+        varint::VarInt,
+        Decode, Encode,
+    },
+    version::ProtocolVersion,
+    Bound,
+};
+
+fn round_trip(packet: Play, version: ProtocolVersion) -> (i32, Vec<u8>, Play) {
+    let (id, data) = packet.serialize(version).unwrap();
+    let decoded = Play::deserialize(Bound::Clientbound, version, id, &mut data.as_slice()).unwrap();
+
+    (id, data, decoded)
+}
+
+#[test]
+fn section_position() {
+    let section = SectionPosition { x: -3, y: -4, z: 5 };
+
+    let mut buf = Vec::new();
+    section.encode(&mut buf).unwrap();
+
+    assert_eq!(
+        i64::from_be_bytes(buf.clone().try_into().unwrap()),
+        -3 << 42 | 5 << 20 | 0xffffc
+    );
+    assert_eq!(
+        SectionPosition::decode(&mut buf.as_slice()).unwrap(),
+        section
+    );
+
+    let position = Position {
+        x: -33,
+        y: -64,
+        z: 95,
+    };
+
+    assert_eq!(position.section(), section);
+    assert_eq!(section.block(15, 0, 15), position);
+}
+
+#[test]
+fn update_section_blocks() {
+    let stone = BlockState::from(Block::Stone);
+    let packet = UpdateSectionBlocks {
+        section: SectionPosition { x: 1, y: -2, z: -1 },
+        suppress_light_updates: false,
+        blocks: vec![SectionBlock::new(1, 2, 3, stone)],
+    };
+
+    let (id, data, decoded) = round_trip(packet.clone().into(), ProtocolVersion::V1_19_2);
+
+    assert_eq!(id, 0x40);
+    // The entry is `1 << 12 | 1 << 8 | 3 << 4 | 2` as a VarLong.
+    assert_eq!(&data[8..], [0, 1, 0xb2, 0x22]);
+    assert!(matches!(decoded, Play::UpdateSectionBlocks(update) if update.blocks == packet.blocks));
+    assert_eq!(
+        packet.section.block(1, 2, 3),
+        Position {
+            x: 17,
+            y: -30,
+            z: -13
+        }
+    );
+    assert!(SectionBlock::decode(&mut [0xff, 0xff, 0xff, 0xff, 0x0f].as_slice()).is_err());
+}
+
+#[test]
+fn block_update() {
+    let position = Position { x: 1, y: -60, z: 2 };
+    let state = BlockState::from(Block::OakStairs);
+
+    let (id, _, decoded) = round_trip(
+        BlockUpdate {
+            position: position.clone(),
+            block: state,
+        }
+        .into(),
+        ProtocolVersion::V1_19,
+    );
+
+    assert_eq!(id, 0x09);
+    assert!(
+        matches!(decoded, Play::BlockUpdate(update) if update.position == position && update.block == state)
+    );
+
+    let mut data = Compound::new();
+    data.insert("Text1".to_string(), Tag::from("hi"));
+
+    let (id, _, decoded) = round_trip(
+        BlockEntityData {
+            position: position.clone(),
+            ty: BlockEntityType::Sign,
+            data: Nbt(Some(data.clone())),
+        }
+        .into(),
+        ProtocolVersion::V1_18,
+    );
+
+    assert_eq!(id, 0x0a);
+    assert!(
+        matches!(decoded, Play::BlockEntityData(update) if update.ty == BlockEntityType::Sign && update.data == Nbt(Some(data)))
+    );
+
+    let (id, data, _) = round_trip(
+        BlockDestroyStage {
+            entity_id: VarInt(5),
+            position,
+            stage: 3,
+        }
+        .into(),
+        ProtocolVersion::V1_19,
+    );
+
+    assert_eq!(id, 0x06);
+    assert_eq!((data[0], data[9]), (5, 3));
+}
+
+#[test]
+fn block_action() {
+    let action = BlockAction {
+        position: Position { x: 0, y: 0, z: 0 },
+        action_id: 0,
+        action_param: 4,
+        block: Block::StickyPiston,
+    };
+
+    assert_eq!(action.piston_direction(), Some(Face::West));
+
+    let (id, data, _) = round_trip(action.clone().into(), ProtocolVersion::V1_19);
+
+    assert_eq!(id, 0x08);
+    assert_eq!(data[8..10], [0, 4]);
+    assert_eq!(
+        BlockAction {
+            block: Block::NoteBlock,
+            ..action
+        }
+        .piston_direction(),
+        None
+    );
+}
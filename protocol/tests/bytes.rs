@@ -0,0 +1,65 @@
+use std::ops::Range;
+
+use bytes::Bytes;
+
+use arrow_protocol::types::{bytes_str::BytesStr, ByteArray, Decode, Encode, InferredLenByteArray};
+
+fn within(range: &Range<*const u8>, bytes: &[u8]) -> bool {
+    range.contains(&bytes.as_ptr()) && range.contains(&bytes[bytes.len() - 1..].as_ptr())
+}
+
+#[test]
+fn zero_copy() {
+    let mut vec = Vec::new();
+
+    BytesStr::from("minecraft:brand").encode(&mut vec).unwrap();
+    InferredLenByteArray::from(b"vanilla".to_vec())
+        .encode(&mut vec)
+        .unwrap();
+
+    let frame = Bytes::from(vec);
+    let range = frame.as_ptr_range();
+    let mut buf = frame.clone();
+
+    let channel = BytesStr::decode(&mut buf).unwrap();
+    let data = InferredLenByteArray::decode(&mut buf).unwrap();
+
+    assert_eq!(channel, "minecraft:brand");
+    assert_eq!(data.0, b"vanilla".as_slice());
+    assert!(within(&range, channel.as_bytes()));
+    assert!(within(&range, &data.0));
+}
+
+#[test]
+fn bytes_str_wire_format() {
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+
+    BytesStr::from("foo").encode(&mut a).unwrap();
+    "foo".to_string().encode(&mut b).unwrap();
+
+    assert_eq!(a, b);
+    assert!(BytesStr::decode(&mut [0x02, 0xc3, 0x28].as_slice()).is_err());
+}
+
+#[test]
+fn byte_array_wire_format() {
+    let data: Vec<u8> = (0..=255).collect();
+
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+
+    ByteArray::from(data.clone()).encode(&mut a).unwrap();
+    data.encode(&mut b).unwrap();
+
+    assert_eq!(a, b);
+
+    let frame = Bytes::from(a);
+    let range = frame.as_ptr_range();
+
+    let array = ByteArray::decode(&mut frame.clone()).unwrap();
+
+    assert_eq!(&*array, data.as_slice());
+    assert!(within(&range, &array));
+    assert!(ByteArray::decode(&mut [0x02, 0x00].as_slice()).is_err());
+}
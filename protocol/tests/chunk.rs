@@ -0,0 +1,170 @@
+use arrow_protocol::{
+    light::{LightData, NibbleArray},
+    nbt::{Nbt, Tag},
+    play::{
+        chunk::{ChunkBlockEntity, ChunkBuilder, ChunkSection, Section},
+        ChunkDataAndUpdateLight, UpdateLight,
+    },
+    registry::{Block, BlockEntityType, BlockState},
+    types::{bit_set::BitSet, varint::VarInt, Decode, Encode, Palette},
+    version::ProtocolVersion,
+};
+
+fn stone() -> BlockState {
+    Block::Stone.into()
+}
+
+#[test]
+fn empty_section() {
+    let mut buf = Vec::new();
+    ChunkSection::from(&Section::new())
+        .encode(&mut buf)
+        .unwrap();
+
+    // Block count, then a single-valued block and biome container without data.
+    assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn palettes() {
+    let mut section = Section::filled(stone(), 1);
+    section.set_block(1, 2, 3, BlockState::default());
+    section.set_biome(3, 0, 0, 2);
+    section.set_biome(0, 3, 0, 3);
+
+    let chunk_section = ChunkSection::from(&section);
+
+    assert_eq!(chunk_section.block_count, 4095);
+    // Block palettes use at least 4 bits, packed 16 to a long.
+    assert_eq!(chunk_section.block_states.bits(), 4);
+    assert_eq!(
+        chunk_section.block_states.palette(),
+        &Palette::Indirect(vec![stone().id(), 0])
+    );
+    assert_eq!(chunk_section.block_states.storage().data().len(), 256);
+    assert_eq!(
+        chunk_section.block_states.storage().data()[(2 * 256 + 3 * 16 + 1) / 16],
+        1 << 4
+    );
+    assert_eq!(chunk_section.biomes.bits(), 2);
+    assert_eq!(chunk_section.biomes.storage().data(), [1 << 6, 2 << 32]);
+
+    let mut buf = Vec::new();
+    chunk_section.encode(&mut buf).unwrap();
+
+    let decoded = ChunkSection::decode(&mut buf.as_slice()).unwrap();
+
+    assert_eq!(decoded, chunk_section);
+    assert_eq!(Section::try_from(&decoded).unwrap(), section);
+}
+
+#[test]
+fn chunk_builder() {
+    let mut bottom = Section::new();
+    bottom.set_block(0, 0, 0, stone());
+
+    let mut top = Section::new();
+    top.set_block(1, 1, 0, stone());
+
+    let light = LightData {
+        trust_edges: true,
+        empty_sky_light_mask: [0, 1, 2, 3].into_iter().collect(),
+        ..Default::default()
+    };
+    let packet = ChunkBuilder::new(3, -4)
+        .with_section(bottom)
+        .with_section(top.clone())
+        .with_block_entity(ChunkBlockEntity::new(
+            1,
+            -64,
+            15,
+            BlockEntityType::Chest,
+            Nbt::default(),
+        ))
+        .with_light(light.clone())
+        .build()
+        .unwrap();
+
+    // Heights up to 32 take 6 bits, 10 to a long.
+    let heightmap = packet.heightmaps["MOTION_BLOCKING"].clone();
+    let Tag::LongArray(longs) = heightmap else {
+        panic!("heightmap is not a long array");
+    };
+
+    assert_eq!(longs.len(), 26);
+    assert_eq!(longs[0], 1 | 18 << 6);
+    assert!(longs[1..].iter().all(|long| *long == 0));
+    assert_eq!(packet.block_entities[0].x(), 1);
+    assert_eq!(packet.block_entities[0].z(), 15);
+
+    let data = packet.serialize(ProtocolVersion::V1_19).unwrap();
+    let decoded =
+        ChunkDataAndUpdateLight::deserialize(ProtocolVersion::V1_19, &mut data.as_slice()).unwrap();
+
+    assert_eq!((decoded.x, decoded.z), (3, -4));
+    assert_eq!(decoded.heightmaps, packet.heightmaps);
+    assert_eq!(decoded.block_entities, packet.block_entities);
+    assert_eq!(decoded.light, light);
+
+    let sections = decoded.sections().unwrap();
+
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[1].block_count, 1);
+    assert_eq!(Section::try_from(&sections[1]).unwrap(), top);
+}
+
+#[test]
+fn biome_count() {
+    let mut section = Section::new();
+    for i in 0..9 {
+        section.set_biome(i % 3, i / 3, 0, 90 + i as i32);
+    }
+
+    let packet = ChunkBuilder::new(0, 0)
+        .with_section(section.clone())
+        .with_biomes(100)
+        .build()
+        .unwrap();
+    let sections = packet.sections_with_biomes(100).unwrap();
+
+    // Nine biomes are stored directly, with enough bits for the ids of 100 biomes.
+    assert_eq!(sections[0].biomes.bits(), 7);
+    assert_eq!(sections[0].biomes.palette(), &Palette::Direct);
+    assert_eq!(Section::try_from(&sections[0]).unwrap(), section);
+}
+
+#[test]
+fn update_light() {
+    let packet = UpdateLight {
+        x: VarInt(1),
+        z: VarInt(2),
+        light: LightData {
+            sky_light_mask: [1].into_iter().collect(),
+            sky_light: vec![NibbleArray::filled(15)],
+            ..Default::default()
+        },
+    };
+
+    let data = packet.serialize(ProtocolVersion::V1_19).unwrap();
+
+    assert_eq!(&data[..12], [1, 2, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2]);
+
+    let decoded = UpdateLight::deserialize(ProtocolVersion::V1_19, &mut data.as_slice()).unwrap();
+
+    assert_eq!(decoded.light, packet.light);
+}
+
+#[test]
+fn bit_set() {
+    let mut set = BitSet::new();
+    set.set(70, true);
+    set.set(3, true);
+
+    assert!(set.get(70) && set.get(3) && !set.get(4) && !set.get(1000));
+    assert_eq!(set.0, [8, 64]);
+    assert_eq!(set.ones().collect::<Vec<_>>(), [3, 70]);
+
+    set.set(70, false);
+
+    assert_eq!(set, BitSet(vec![8]));
+}
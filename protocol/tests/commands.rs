@@ -0,0 +1,189 @@
+use arrow_protocol::{
+    error::DeserializeError,
+    play::{
+        command::{
+            Bounds, CommandNode, EntityArgumentFlags, GraphNode, NodeKind, Parser, StringKind,
+            SuggestionType,
+        },
+        Commands,
+    },
+    types::varint::VarInt,
+    version::ProtocolVersion,
+};
+
+fn tree() -> CommandNode {
+    CommandNode::root()
+        .then(
+            CommandNode::literal("tp").then(
+                CommandNode::argument("targets", Parser::Entity(EntityArgumentFlags::empty()))
+                    .then(CommandNode::argument("location", Parser::Vec3).executes()),
+            ),
+        )
+        .then(CommandNode::literal("teleport").redirect(["tp"]))
+        .then(
+            CommandNode::literal("give").then(
+                CommandNode::argument("item", Parser::ItemStack)
+                    .executes()
+                    .then(
+                        CommandNode::argument(
+                            "count",
+                            Parser::Integer(Bounds {
+                                min: Some(1),
+                                max: None,
+                            }),
+                        )
+                        .executes(),
+                    ),
+            ),
+        )
+        .then(
+            CommandNode::literal("say").then(
+                CommandNode::argument("message", Parser::String(StringKind::GreedyPhrase))
+                    .suggests(SuggestionType::AskServer)
+                    .executes(),
+            ),
+        )
+        .then(
+            CommandNode::literal("execute")
+                .then(CommandNode::literal("run").redirect(Vec::<String>::new())),
+        )
+}
+
+#[test]
+fn flatten() {
+    let commands = Commands::new(&tree());
+
+    assert_eq!(commands.root_index, VarInt(0));
+    assert_eq!(commands.nodes.len(), 12);
+    assert_eq!(
+        commands.nodes[0].children,
+        [1, 2, 3, 4, 5].map(VarInt).to_vec()
+    );
+    // `teleport` redirects to `tp`, and `execute run` to the root.
+    assert_eq!(commands.nodes[2].redirect, Some(VarInt(1)));
+    assert_eq!(commands.nodes[9].kind, NodeKind::Literal("run".into()));
+    assert_eq!(commands.nodes[9].redirect, Some(VarInt(0)));
+
+    let data = commands.serialize(ProtocolVersion::V1_19_2).unwrap();
+    let count = [
+        // Argument, executable, no children or redirect.
+        0x06, 0, //
+        5, b'c', b'o', b'u', b'n', b't', //
+        // `brigadier:integer` with a minimum of 1.
+        3, 0x01, 0, 0, 0, 1,
+    ];
+
+    assert!(data.windows(count.len()).any(|window| window == count));
+}
+
+#[test]
+fn round_trip() {
+    let tree = tree();
+
+    for version in [ProtocolVersion::V1_18_2, ProtocolVersion::V1_19_2] {
+        let data = Commands::new(&tree).serialize(version).unwrap();
+        let decoded = Commands::deserialize(version, &mut data.as_slice()).unwrap();
+
+        assert_eq!(decoded.tree().unwrap(), tree);
+    }
+
+    let say = tree.find(&["say", "message"]).unwrap();
+
+    assert!(say.executable);
+    assert_eq!(
+        tree.find(&["teleport"]).unwrap().redirect,
+        Some(vec!["tp".to_owned()])
+    );
+}
+
+#[test]
+fn parsers_by_version() {
+    let node =
+        |parser| Commands::new(&CommandNode::root().then(CommandNode::argument("arg", parser)));
+
+    let data = node(Parser::Resource(
+        "minecraft:worldgen/biome".parse().unwrap(),
+    ))
+    .serialize(ProtocolVersion::V1_18_2)
+    .unwrap();
+
+    // The last byte is the index of the root.
+    assert!(data.ends_with(b"\x12minecraft:resource\x18minecraft:worldgen/biome\0"));
+    assert!(node(Parser::TemplateMirror)
+        .serialize(ProtocolVersion::V1_18_2)
+        .is_err());
+    assert!(node(Parser::Resource("minecraft:item".parse().unwrap()))
+        .serialize(ProtocolVersion::V1_18)
+        .is_err());
+
+    let data = node(Parser::Entity(
+        EntityArgumentFlags::SINGLE | EntityArgumentFlags::PLAYERS_ONLY,
+    ))
+    .serialize(ProtocolVersion::V1_19)
+    .unwrap();
+
+    assert!(data.ends_with(&[6, 0x03, 0]));
+    assert_eq!(Parser::Uuid.id(), 47);
+    assert_eq!(Parser::ScoreHolder(true).name(), "minecraft:score_holder");
+}
+
+#[test]
+fn invalid_graphs() {
+    let node = |children: &[i32], redirect| GraphNode {
+        kind: NodeKind::Literal("a".into()),
+        executable: false,
+        children: children.iter().copied().map(VarInt).collect(),
+        redirect,
+    };
+    let root = GraphNode {
+        kind: NodeKind::Root,
+        ..node(&[1], None)
+    };
+
+    let cyclic = Commands {
+        nodes: vec![root.clone(), node(&[1], None)],
+        root_index: VarInt(0),
+    };
+
+    assert!(matches!(
+        cyclic.tree(),
+        Err(DeserializeError::InvalidCommandGraph(_))
+    ));
+
+    let dangling = Commands {
+        nodes: vec![root.clone(), node(&[], Some(VarInt(2)))],
+        root_index: VarInt(0),
+    };
+
+    assert!(dangling.tree().is_err());
+
+    let unreachable = Commands {
+        nodes: vec![root.clone(), node(&[], Some(VarInt(2))), node(&[], None)],
+        root_index: VarInt(0),
+    };
+
+    assert!(unreachable.tree().is_err());
+
+    // Each layer is a diamond, so copying shared nodes would build 2^depth of them.
+    let mut nodes = vec![root];
+
+    for layer in 0..100 {
+        let top = 3 * layer + 1;
+
+        nodes.push(node(&[top + 1, top + 2], None));
+        nodes.extend([node(&[top + 3], None), node(&[top + 3], None)]);
+    }
+    nodes.push(node(&[], None));
+
+    let diamonds = Commands {
+        nodes,
+        root_index: VarInt(0),
+    };
+
+    assert!(matches!(
+        diamonds.tree(),
+        Err(DeserializeError::InvalidCommandGraph(
+            "a node has several parents"
+        ))
+    ));
+}
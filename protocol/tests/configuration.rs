@@ -0,0 +1,240 @@
+use arrow_protocol::{
+    chat::{Chat, Color, Component, NormalColor, TextComponent},
+    codec::Codec,
+    configuration::{
+        AcknowledgeFinishConfiguration, Configuration, FeatureFlags, FinishConfiguration,
+    },
+    handshake::{Handshake, NextState},
+    login::{Login, LoginAcknowledged, LoginStart},
+    play::{ConfigurationAcknowledged, Play, StartConfiguration},
+    types::{raw_nbt::RawNbt, Context, Decode, Encode},
+    version::ProtocolVersion,
+    Bound, Protocol, State,
+};
+use bytes::{Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+use uuid::Uuid;
+
+#[test]
+fn packet_ids() {
+    let flags = Configuration::from(FeatureFlags {
+        flags: vec!["vanilla".parse().unwrap()],
+    });
+
+    assert_eq!(flags.packet_id(ProtocolVersion::V1_20_2), Some(0x07));
+    assert_eq!(flags.packet_id(ProtocolVersion::V1_20_3), Some(0x08));
+    assert_eq!(flags.packet_id(ProtocolVersion::V1_20_5), Some(0x0c));
+    assert_eq!(flags.packet_id(ProtocolVersion::V1_21_7), Some(0x0c));
+    assert_eq!(flags.packet_id(ProtocolVersion::V1_19_3), None);
+
+    let start = Play::from(StartConfiguration);
+
+    assert_eq!(start.packet_id(ProtocolVersion::V1_20_5), Some(0x69));
+    assert_eq!(start.packet_id(ProtocolVersion::V1_21_5), Some(0x6f));
+
+    let (id, data) = flags.serialize(ProtocolVersion::V1_20_2).unwrap();
+
+    assert_eq!(id, 0x07);
+    assert_eq!(data, b"\x01\x11minecraft:vanilla");
+    assert!(matches!(
+        Configuration::deserialize(
+            Bound::Clientbound,
+            ProtocolVersion::V1_20_5,
+            0x0c,
+            &mut data.as_slice()
+        ),
+        Ok(Configuration::FeatureFlags(flags)) if flags.flags.len() == 1
+    ));
+}
+
+#[test]
+fn raw_nbt_root_name() {
+    // An empty compound.
+    let nbt = RawNbt::from_bytes(Bytes::from_static(&[10, 0])).unwrap();
+
+    let mut named = Vec::new();
+    let mut nameless = Vec::new();
+
+    nbt.encode_with(&mut named, &Context::new(ProtocolVersion(763)))
+        .unwrap();
+    nbt.encode_with(&mut nameless, &Context::new(ProtocolVersion::V1_20_2))
+        .unwrap();
+
+    assert_eq!(named, [10, 0, 0, 0]);
+    assert_eq!(nameless, [10, 0]);
+    assert_eq!(
+        RawNbt::decode_with(&mut named.as_slice(), &Context::new(ProtocolVersion(763))).unwrap(),
+        nbt
+    );
+    assert!(RawNbt::from_bytes(Bytes::from_static(&[10])).is_err());
+    assert!(RawNbt::default().is_end());
+}
+
+#[test]
+fn chat_as_nbt() {
+    let chat = Chat::default()
+        .with_bold(true)
+        .with_color(Color::NormalColor(NormalColor::Gold))
+        .with_extra(Chat::default().with_insertion("\0".to_string()));
+
+    let cx = Context::new(ProtocolVersion::V1_20_3);
+    let mut buf = Vec::new();
+
+    chat.encode_with(&mut buf, &cx).unwrap();
+
+    assert_eq!(buf[0], 10);
+    assert_eq!(Chat::decode_with(&mut buf.as_slice(), &cx).unwrap(), chat);
+
+    // A plain string tag is a text component.
+    let text = Chat::decode_with(&mut b"\x08\x00\x02hi".as_slice(), &cx).unwrap();
+
+    assert_eq!(
+        text,
+        Chat::default().with_component(Component::String(TextComponent {
+            text: "hi".to_string()
+        }))
+    );
+}
+
+#[test]
+fn login_uuid() {
+    let start = LoginStart {
+        name: "a".to_string(),
+        sig_data: None,
+        uuid: Uuid::nil().into(),
+    };
+
+    assert_eq!(
+        start.serialize(ProtocolVersion::V1_19_3).unwrap().len(),
+        2 + 1 + 16
+    );
+    assert_eq!(
+        start.serialize(ProtocolVersion::V1_20_2).unwrap().len(),
+        2 + 16
+    );
+
+    let anonymous = LoginStart {
+        uuid: None.into(),
+        ..start
+    };
+
+    assert!(anonymous.serialize(ProtocolVersion::V1_20_2).is_err());
+}
+
+#[test]
+fn codec_transitions() {
+    let version = ProtocolVersion::V1_20_2;
+    let mut codec = Codec::new(Bound::Serverbound, State::Handshake);
+    let mut bytes = BytesMut::new();
+
+    let packets: [Protocol; 4] = [
+        Protocol::Handshake(
+            Handshake {
+                version: version.into(),
+                address: "localhost".to_string(),
+                port: 25565,
+                next_state: NextState::Login,
+            }
+            .into(),
+        ),
+        Protocol::Login(LoginAcknowledged.into()),
+        Protocol::Configuration(AcknowledgeFinishConfiguration.into()),
+        Protocol::Play(ConfigurationAcknowledged.into()),
+    ];
+    let states = [
+        State::Login,
+        State::Configuration,
+        State::Play,
+        State::Configuration,
+    ];
+
+    for (packet, state) in packets.into_iter().zip(states) {
+        codec.encode(packet, &mut bytes).unwrap();
+
+        assert_eq!(codec.state(), state);
+    }
+
+    let mut server = Codec::new(Bound::Serverbound, State::Handshake);
+
+    while server.decode(&mut bytes).unwrap().is_some() {}
+
+    assert_eq!(server.version(), version);
+    assert_eq!(server.state(), State::Configuration);
+
+    assert_eq!(
+        Protocol::Configuration(FinishConfiguration.into()).next_state(version),
+        None
+    );
+    assert!(matches!(
+        Protocol::Login(Login::LoginAcknowledged(LoginAcknowledged)).next_state(version),
+        Some(State::Configuration)
+    ));
+    assert!(matches!(
+        Protocol::Play(Play::ConfigurationAcknowledged(ConfigurationAcknowledged))
+            .next_state(version),
+        Some(State::Configuration)
+    ));
+}
+
+#[test]
+fn configuration_by_version() {
+    let versions = [
+        ProtocolVersion::V1_20_3,
+        ProtocolVersion::V1_20_5,
+        ProtocolVersion::V1_21,
+        ProtocolVersion::V1_21_2,
+        ProtocolVersion::V1_21_5,
+        ProtocolVersion::V1_21_7,
+    ];
+
+    for version in versions {
+        let mut codec = Codec::new(Bound::Serverbound, State::Login);
+        let mut bytes = BytesMut::new();
+
+        codec.set_version(version);
+        codec
+            .encode(Protocol::Login(LoginAcknowledged.into()), &mut bytes)
+            .unwrap();
+        codec
+            .encode(
+                Protocol::Configuration(AcknowledgeFinishConfiguration.into()),
+                &mut bytes,
+            )
+            .unwrap();
+        codec
+            .encode(Protocol::Play(ConfigurationAcknowledged.into()), &mut bytes)
+            .unwrap();
+
+        let mut server = Codec::new(Bound::Serverbound, State::Login);
+
+        server.set_version(version);
+
+        assert!(matches!(
+            server.decode(&mut bytes).unwrap(),
+            Some(Protocol::Login(Login::LoginAcknowledged(_)))
+        ));
+        assert!(matches!(
+            server.decode(&mut bytes).unwrap(),
+            Some(Protocol::Configuration(
+                Configuration::AcknowledgeFinishConfiguration(_)
+            ))
+        ));
+        assert!(matches!(
+            server.decode(&mut bytes).unwrap(),
+            Some(Protocol::Play(Play::ConfigurationAcknowledged(_)))
+        ));
+        assert_eq!(server.state(), State::Configuration);
+    }
+
+    // Before 1.20.2 logging in goes straight to the play state.
+    let mut codec = Codec::new(Bound::Serverbound, State::Login);
+
+    codec.set_version(ProtocolVersion::V1_19_3);
+
+    assert!(codec
+        .encode(
+            Protocol::Login(LoginAcknowledged.into()),
+            &mut BytesMut::new()
+        )
+        .is_err());
+}
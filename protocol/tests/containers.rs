@@ -0,0 +1,66 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
+
+use arrow_protocol::types::{
+    length_prefixed::LengthPrefixed, varint::VarInt, Decode, Encode, RemainingOption,
+};
+
+fn round_trip<T: Encode + Decode + PartialEq + Debug>(value: T, bytes: &[u8]) {
+    let mut buf = Vec::new();
+
+    value.encode(&mut buf).unwrap();
+
+    assert_eq!(buf, bytes);
+
+    let mut slice = buf.as_slice();
+
+    assert_eq!(T::decode(&mut slice).unwrap(), value);
+    assert!(slice.is_empty());
+}
+
+#[test]
+fn arrays_and_tuples() {
+    round_trip([1u8, 2, 3], &[1, 2, 3]);
+    round_trip(
+        (1u8, true, 2i16, VarInt(300)),
+        &[0x01, 0x01, 0x00, 0x02, 0xac, 0x02],
+    );
+    round_trip(Box::new(7i32), &[0, 0, 0, 7]);
+    round_trip(Cow::Borrowed("hi"), &[2, b'h', b'i']);
+}
+
+#[test]
+fn maps() {
+    round_trip(
+        BTreeMap::from([(1u8, "a".to_string()), (2, "b".to_string())]),
+        &[2, 1, 1, b'a', 2, 1, b'b'],
+    );
+    round_trip(HashMap::from([(VarInt(1), false)]), &[1, 1, 0]);
+}
+
+#[test]
+fn length_prefixed() {
+    round_trip(LengthPrefixed::<u8, u8>::new(vec![4, 5]), &[2, 4, 5]);
+    round_trip(LengthPrefixed::<i16, u8>::new(vec![4]), &[0, 1, 4]);
+    round_trip(
+        LengthPrefixed::<i32, bool>::new(vec![true]),
+        &[0, 0, 0, 1, 1],
+    );
+    round_trip(LengthPrefixed::<VarInt, u8>::new(vec![9]), &[1, 9]);
+
+    assert!(LengthPrefixed::<i32, u8>::decode(&mut [0xff, 0xff, 0xff, 0xff].as_slice()).is_err());
+    assert!(LengthPrefixed::<u8, u8>::new(vec![0; 256])
+        .encode(&mut Vec::new())
+        .is_err());
+}
+
+#[test]
+fn options() {
+    round_trip(Some(3u8), &[1, 3]);
+    round_trip(None::<u8>, &[0]);
+    round_trip(RemainingOption(Some(3u8)), &[3]);
+    round_trip(RemainingOption::<u8>(None), &[]);
+}
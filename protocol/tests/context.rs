@@ -0,0 +1,72 @@
+use arrow_protocol::{
+    types::{position::Position, slot::Slot, Context, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+// 1.12.2
+const LEGACY: Context = Context {
+    version: ProtocolVersion(340),
+};
+
+#[test]
+fn position_layouts() {
+    let pos = Position { x: 1, y: 2, z: 3 };
+
+    let mut modern = Vec::new();
+    let mut legacy = Vec::new();
+
+    pos.encode(&mut modern).unwrap();
+    pos.encode_with(&mut legacy, &LEGACY).unwrap();
+
+    assert_eq!(modern, (1u64 << 38 | 3 << 12 | 2).to_be_bytes());
+    assert_eq!(legacy, (1u64 << 38 | 2 << 26 | 3).to_be_bytes());
+
+    let pos = Position {
+        x: -100,
+        y: -10,
+        z: -69420,
+    };
+
+    let mut buf = Vec::new();
+
+    pos.encode_with(&mut buf, &LEGACY).unwrap();
+
+    assert_eq!(
+        Position::decode_with(&mut buf.as_slice(), &LEGACY).unwrap(),
+        pos
+    );
+}
+
+#[test]
+fn slot_formats() {
+    let empty = Slot { data: None };
+
+    let mut modern = Vec::new();
+    let mut legacy = Vec::new();
+
+    empty.encode(&mut modern).unwrap();
+    empty.encode_with(&mut legacy, &LEGACY).unwrap();
+
+    assert_eq!(modern, [0x00]);
+    assert_eq!(legacy, [0xff, 0xff]);
+    assert!(Slot::decode_with(&mut legacy.as_slice(), &LEGACY)
+        .unwrap()
+        .data
+        .is_none());
+}
+
+#[test]
+fn containers_forward_context() {
+    let positions = vec![Some(Position { x: 1, y: 2, z: 3 })];
+
+    let mut buf = Vec::new();
+
+    positions.encode_with(&mut buf, &LEGACY).unwrap();
+
+    assert_eq!(buf[..2], [0x01, 0x01]);
+    assert_eq!(buf[2..], (1u64 << 38 | 2 << 26 | 3).to_be_bytes());
+    assert_eq!(
+        Vec::<Option<Position>>::decode_with(&mut buf.as_slice(), &LEGACY).unwrap(),
+        positions
+    );
+}
@@ -0,0 +1,370 @@
+use arrow_protocol::{
+    chat::Component,
+    error::{CommandError, NumberKind},
+    play::{
+        command::{Bounds, CommandNode, EntityArgumentFlags, Parser, StringKind, SuggestionType},
+        dispatcher::{
+            Argument, CommandDispatcher, Coordinates, EntitySelector, SelectorTarget,
+            WorldCoordinate,
+        },
+        ArgumentSignature, ChatCommand, CommandSuggestionsRequest, SuggestionMatch,
+    },
+    types::{position::Position, varint::VarInt, ByteArray},
+};
+
+fn dispatcher() -> CommandDispatcher {
+    let single_player = EntityArgumentFlags::SINGLE | EntityArgumentFlags::PLAYERS_ONLY;
+
+    CommandDispatcher::new(
+        CommandNode::root()
+            .then(
+                CommandNode::literal("tp")
+                    .then(CommandNode::argument("location", Parser::Vec3).executes())
+                    .then(
+                        CommandNode::argument(
+                            "targets",
+                            Parser::Entity(EntityArgumentFlags::empty()),
+                        )
+                        .then(CommandNode::argument("destination", Parser::Vec3).executes()),
+                    ),
+            )
+            .then(CommandNode::literal("teleport").redirect(["tp"]))
+            .then(
+                CommandNode::literal("give").then(
+                    CommandNode::argument("target", Parser::Entity(single_player)).then(
+                        CommandNode::argument("item", Parser::ItemStack)
+                            .executes()
+                            .then(
+                                CommandNode::argument(
+                                    "count",
+                                    Parser::Integer(Bounds {
+                                        min: Some(1),
+                                        max: Some(64),
+                                    }),
+                                )
+                                .executes(),
+                            ),
+                    ),
+                ),
+            )
+            .then(
+                CommandNode::literal("setblock").then(
+                    CommandNode::argument("pos", Parser::BlockPos)
+                        .then(CommandNode::argument("block", Parser::BlockState).executes()),
+                ),
+            )
+            .then(
+                CommandNode::literal("msg").then(
+                    CommandNode::argument(
+                        "targets",
+                        Parser::Entity(EntityArgumentFlags::PLAYERS_ONLY),
+                    )
+                    .suggests(SuggestionType::AskServer)
+                    .then(CommandNode::argument("message", Parser::Message).executes()),
+                ),
+            )
+            .then(
+                CommandNode::literal("tag").then(
+                    CommandNode::argument("name", Parser::String(StringKind::QuotablePhrase))
+                        .executes(),
+                ),
+            )
+            .then(
+                CommandNode::literal("execute")
+                    .then(CommandNode::literal("run").redirect(Vec::<String>::new())),
+            ),
+    )
+}
+
+fn error(command: &str) -> (CommandError, usize) {
+    let error = dispatcher().parse(command).unwrap_err();
+    (error.error, error.cursor)
+}
+
+#[test]
+fn arguments() {
+    let dispatcher = dispatcher();
+    let parsed = dispatcher
+        .parse("give Steve minecraft:diamond_sword{Damage:5} 1")
+        .unwrap();
+
+    assert_eq!(parsed.path, ["give", "target", "item", "count"]);
+    assert_eq!(parsed.get::<i32>("count"), Some(1));
+    assert_eq!(
+        parsed.get::<EntitySelector>("target"),
+        Some(EntitySelector::Name("Steve".into()))
+    );
+    assert_eq!(
+        parsed.get::<String>("item").as_deref(),
+        Some("minecraft:diamond_sword{Damage:5}")
+    );
+    assert_eq!(parsed.argument("count").unwrap().range, 45..46);
+
+    let parsed = dispatcher.parse("tag \"a \\\"quoted\\\" tag\"").unwrap();
+
+    assert_eq!(
+        parsed.get::<String>("name").as_deref(),
+        Some("a \"quoted\" tag")
+    );
+
+    let parsed = dispatcher
+        .parse("tp @e[type=cow, limit=2] ~1 64 -3.5")
+        .unwrap();
+    let Some(EntitySelector::Selector { target, options }) = parsed.get("targets") else {
+        panic!("expected a selector");
+    };
+
+    assert_eq!(target, SelectorTarget::AllEntities);
+    assert_eq!(
+        options,
+        [("type".into(), "cow".into()), ("limit".into(), "2".into())]
+    );
+
+    // Whole x and z coordinates point at the center of the block.
+    let destination: Coordinates = parsed.get("destination").unwrap();
+
+    assert_eq!(
+        destination,
+        Coordinates::World([
+            WorldCoordinate {
+                value: 1.0,
+                relative: true
+            },
+            WorldCoordinate {
+                value: 64.0,
+                relative: false
+            },
+            WorldCoordinate {
+                value: -3.5,
+                relative: false
+            },
+        ])
+    );
+    assert_eq!(
+        destination.resolve([10.0, 0.0, 0.0], [0.0, 0.0]),
+        [11.0, 64.0, -3.5]
+    );
+
+    let parsed = dispatcher.parse("setblock ~ ~-1 ~ stone").unwrap();
+    let pos: Coordinates = parsed.get("pos").unwrap();
+
+    assert_eq!(
+        pos.block_position([0.5, 64.0, -0.5], [0.0, 0.0]),
+        Position { x: 0, y: 63, z: -1 }
+    );
+
+    // Local coordinates are left, up and forwards, facing south.
+    let parsed = dispatcher.parse("tp ^1 ^2 ^3").unwrap();
+    let [x, y, z] = parsed
+        .get::<Coordinates>("location")
+        .unwrap()
+        .resolve([0.0; 3], [0.0, 0.0]);
+
+    assert!((x - 1.0).abs() < 1e-6 && (y - 2.0).abs() < 1e-6 && (z - 3.0).abs() < 1e-6);
+
+    let [x, _, z] = parsed
+        .get::<Coordinates>("location")
+        .unwrap()
+        .resolve([0.0; 3], [90.0, 0.0]);
+
+    assert!((x + 3.0).abs() < 1e-6 && (z - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn redirects() {
+    let dispatcher = dispatcher();
+
+    assert_eq!(
+        dispatcher.parse("teleport 1 2 3").unwrap().path,
+        ["teleport", "location"]
+    );
+    assert_eq!(
+        dispatcher
+            .parse("execute run execute run tp Alex")
+            .map_err(|error| error.error),
+        Err(CommandError::UnknownCommand)
+    );
+
+    let parsed = dispatcher.parse("execute run tp Alex 0 0 0").unwrap();
+
+    assert_eq!(
+        parsed.path,
+        ["execute", "run", "tp", "targets", "destination"]
+    );
+    assert!(matches!(
+        parsed.argument("targets").unwrap().value,
+        Argument::Entity(_)
+    ));
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error(""), (CommandError::UnknownCommand, 0));
+    assert_eq!(error("kill"), (CommandError::UnknownCommand, 0));
+    assert_eq!(error("give Steve"), (CommandError::UnknownCommand, 10));
+    assert_eq!(
+        error("give Steve stone 0"),
+        (
+            CommandError::NumberTooLow(NumberKind::Integer, "1".into(), "0".into()),
+            17
+        )
+    );
+    assert_eq!(
+        error("give Steve stone 1.5"),
+        (
+            CommandError::InvalidNumber(NumberKind::Integer, "1.5".into()),
+            17
+        )
+    );
+    assert_eq!(
+        error("give Steve stone 1x"),
+        (CommandError::ExpectedSeparator, 18)
+    );
+    assert_eq!(error("give @a stone"), (CommandError::TooManyPlayers, 5));
+    // Names are limited to 16 characters, not bytes.
+    assert!(dispatcher()
+        .parse(&format!("give \"{}\" stone", "Ä".repeat(16)))
+        .is_ok());
+    assert_eq!(
+        error(&format!("give \"{}\" stone", "Ä".repeat(17))),
+        (CommandError::InvalidEntity, 5)
+    );
+    assert_eq!(
+        error("give @e[limit=1] stone"),
+        (CommandError::OnlyPlayers, 5)
+    );
+    assert_eq!(
+        error("give @x stone"),
+        (CommandError::UnknownSelector("@x".into()), 5)
+    );
+    assert_eq!(
+        error("give @p[color=red] stone"),
+        (CommandError::UnknownOption("color".into()), 8)
+    );
+    assert_eq!(
+        error("setblock 1 2"),
+        (CommandError::IncompleteCoordinates(3), 9)
+    );
+    assert_eq!(
+        error("setblock 1 ^ 2 stone"),
+        (CommandError::MixedCoordinates, 11)
+    );
+    assert_eq!(error("tag \"open"), (CommandError::ExpectedEndOfQuote, 9));
+    assert_eq!(
+        error("give Steve stone 1 more"),
+        (CommandError::UnknownArgument, 19)
+    );
+
+    let error = dispatcher().parse("give Steve diamond 100").unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Integer must not be more than 64, found 100 at position 19: ...e diamond <--[HERE]"
+    );
+
+    let [message, context] = error.to_chat();
+
+    assert_eq!(
+        message.component,
+        Component::Translation {
+            translate: "argument.integer.big".into(),
+            with: Some(
+                vec!["64".into(), "100".into()]
+                    .into_iter()
+                    .map(text)
+                    .collect()
+            ),
+        }
+    );
+
+    let extra = context.extra.unwrap();
+
+    assert_eq!(extra.len(), 3);
+    assert_eq!(extra[1].component, text("100".into()));
+    assert_eq!(extra[1].underlined, Some(true));
+}
+
+fn text(text: String) -> Component {
+    Component::String(arrow_protocol::chat::TextComponent { text })
+}
+
+#[test]
+fn signatures() {
+    let signature = ByteArray::from(vec![1, 2, 3]);
+    let packet = ChatCommand {
+        command: "msg Alex hi there".into(),
+        timestamp: 0,
+        salt: 0,
+        arg_signatures: vec![
+            ArgumentSignature {
+                name: "message".into(),
+                signature: signature.clone(),
+            },
+            ArgumentSignature {
+                name: "targets".into(),
+                signature: signature.clone(),
+            },
+        ],
+        signed_preview: false,
+        last_seen_messages: Vec::new(),
+        last_received_message: None,
+    };
+
+    let parsed = dispatcher().parse_signed(&packet).unwrap();
+
+    assert_eq!(
+        parsed.argument("message").unwrap().value,
+        Argument::Message("hi there".into())
+    );
+    assert_eq!(
+        parsed.argument("message").unwrap().signature,
+        Some(signature)
+    );
+    // Only message arguments are signed.
+    assert_eq!(parsed.argument("targets").unwrap().signature, None);
+}
+
+#[test]
+fn suggestions() {
+    let dispatcher = dispatcher();
+    let texts = |text: &str| {
+        dispatcher
+            .suggest(text, |_, _, _| Vec::new())
+            .matches
+            .into_iter()
+            .map(|suggestion| suggestion.text)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(texts("/t"), ["tag", "teleport", "tp"]);
+    assert_eq!(texts("/give @"), ["@a", "@e", "@p", "@r", "@s"]);
+    assert_eq!(texts("/teleport "), ["@a", "@e", "@p", "@r", "@s", "~ ~ ~"]);
+    assert_eq!(texts("/execute run g"), ["give"]);
+
+    let request = CommandSuggestionsRequest {
+        id: VarInt(7),
+        text: "/msg Äl".into(),
+    };
+    let suggestions = dispatcher.suggest(&request.text, |node, parsed, typed| {
+        assert_eq!(node.name(), Some("targets"));
+        assert_eq!(parsed.path, ["msg"]);
+
+        ["Älex", "Steve"]
+            .into_iter()
+            .filter(|name| name.starts_with(typed))
+            .map(|name| SuggestionMatch {
+                text: name.into(),
+                tooltip: None,
+            })
+            .collect()
+    });
+
+    assert_eq!(suggestions.range, 5..8);
+    assert_eq!(suggestions.text, request.text);
+
+    let response = suggestions.into_response(request.id);
+
+    assert_eq!(response.id, VarInt(7));
+    assert_eq!((response.start, response.length), (VarInt(5), VarInt(2)));
+    assert_eq!(response.matches.len(), 1);
+}
@@ -0,0 +1,70 @@
+use arrow_protocol::{
+    packet_ids,
+    play::{ChatMessage, Play},
+    registry::{Block, EntityType, Item},
+    types::{varint::VarInt, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+#[test]
+fn packet_id_tables() {
+    assert_eq!(ChatMessage::IDS, packet_ids::play::serverbound::CHAT);
+    assert_eq!(
+        packet_ids::play::serverbound::CHAT,
+        [
+            (ProtocolVersion::V1_18, Some(0x03)),
+            (ProtocolVersion::V1_19, Some(0x04)),
+            (ProtocolVersion::V1_19_1, Some(0x05)),
+            (ProtocolVersion::V1_20_5, Some(0x06)),
+            (ProtocolVersion::V1_21_2, Some(0x07)),
+            (ProtocolVersion::V1_21_6, Some(0x08)),
+            (ProtocolVersion(773), None),
+        ]
+    );
+    assert_eq!(
+        packet_ids::play::serverbound::CHAT_ACK,
+        [
+            (ProtocolVersion::V1_19_1, Some(0x03)),
+            (ProtocolVersion::V1_21_2, Some(0x04)),
+            (ProtocolVersion::V1_21_6, Some(0x05)),
+            (ProtocolVersion(773), None),
+        ]
+    );
+    assert!(Play::supports(ProtocolVersion::V1_19_2));
+    assert!(Play::supports(ProtocolVersion::V1_21_7));
+    assert!(!Play::supports(ProtocolVersion(773)));
+
+    // The layout of chat messages is only modeled up to 1.19.2.
+    assert_eq!(ChatMessage::packet_id(ProtocolVersion::V1_19_2), Some(0x05));
+    assert_eq!(ChatMessage::packet_id(ProtocolVersion::V1_19_3), None);
+}
+
+#[test]
+fn registry_enums() {
+    assert_eq!(EntityType::CommandBlockMinecart as i32, 55);
+    assert_eq!(EntityType::FishingBobber as i32, 117);
+    assert_eq!(Item::Stone as i32, 1);
+    assert_eq!(Item::DiamondSword as i32, 755);
+
+    let mut buf = Vec::new();
+    VarInt(755).encode(&mut buf).unwrap();
+
+    assert_eq!(
+        Item::decode(&mut buf.as_slice()).unwrap(),
+        Item::DiamondSword
+    );
+}
+
+#[test]
+fn block_states() {
+    assert_eq!(Block::OakStairs as i32, 161);
+    assert_eq!(Block::OakStairs.states(), 2208..=2287);
+    assert_eq!(Block::OakStairs.default_state(), 2219);
+
+    assert_eq!(Block::from_state(0), Some(Block::Air));
+    assert_eq!(Block::from_state(2208), Some(Block::OakStairs));
+    assert_eq!(Block::from_state(2287), Some(Block::OakStairs));
+    assert_eq!(Block::from_state(21447), Some(Block::ReinforcedDeepslate));
+    assert_eq!(Block::from_state(21448), None);
+    assert_eq!(Block::from_state(-1), None);
+}
@@ -0,0 +1,49 @@
+use arrow_protocol::{
+    error::IdentifierError,
+    types::{identifier::Identifier, Decode, Encode},
+};
+
+#[test]
+fn parse() {
+    let id: Identifier = "stone".parse().unwrap();
+
+    assert_eq!(id.namespace(), "minecraft");
+    assert_eq!(id.path(), "stone");
+    assert_eq!(id, ":stone".parse().unwrap());
+    assert_eq!(id.to_string(), "minecraft:stone");
+
+    let id: Identifier = "arrow:textures/block/foo.png".parse().unwrap();
+
+    assert_eq!(id.namespace(), "arrow");
+    assert_eq!(id.path(), "textures/block/foo.png");
+    assert!(!id.is_default_namespace());
+}
+
+#[test]
+fn invalid() {
+    assert_eq!(
+        "Minecraft:stone".parse::<Identifier>(),
+        Err(IdentifierError::InvalidNamespace("Minecraft".to_string()))
+    );
+    assert_eq!(
+        "foo/bar:stone".parse::<Identifier>(),
+        Err(IdentifierError::InvalidNamespace("foo/bar".to_string()))
+    );
+    assert_eq!(
+        "minecraft:stone:slab".parse::<Identifier>(),
+        Err(IdentifierError::InvalidPath("stone:slab".to_string()))
+    );
+    assert!(Identifier::new("arrow", "white space").is_err());
+}
+
+#[test]
+fn wire_format() {
+    let id = Identifier::new("minecraft", "brand").unwrap();
+
+    let mut buf = Vec::new();
+    id.encode(&mut buf).unwrap();
+
+    assert_eq!(buf, b"\x0fminecraft:brand");
+    assert_eq!(Identifier::decode(&mut buf.as_slice()).unwrap(), id);
+    assert!(Identifier::decode(&mut b"\x03A:b".as_slice()).is_err());
+}
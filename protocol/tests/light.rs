@@ -0,0 +1,139 @@
+use arrow_protocol::{
+    light::{LightEngine, NibbleArray},
+    play::chunk::Section,
+    registry::{Block, BlockState},
+    types::{bit_set::BitSet, Decode, Encode},
+};
+
+fn block(block: Block) -> BlockState {
+    block.into()
+}
+
+/// Two sections with a stone floor at the bottom.
+fn floored() -> Vec<Section> {
+    let mut bottom = Section::new();
+
+    for x in 0..16 {
+        for z in 0..16 {
+            bottom.set_block(x, 0, z, block(Block::Stone));
+        }
+    }
+
+    vec![bottom, Section::new()]
+}
+
+#[test]
+fn nibble_array() {
+    let mut array = NibbleArray::new();
+    array.set(1, 0, 0, 7);
+    array.set(2, 0, 0, 15);
+    array.set(0, 1, 0, 3);
+
+    assert_eq!(&array.as_bytes()[..2], [0x70, 0x0f]);
+    assert_eq!(array.as_bytes()[128], 0x03);
+    assert_eq!(array.get(1, 0, 0), 7);
+    assert!(!array.is_empty() && NibbleArray::new().is_empty());
+
+    let mut buf = Vec::new();
+    array.encode(&mut buf).unwrap();
+
+    assert_eq!(&buf[..2], [0x80, 0x10]);
+    assert_eq!(NibbleArray::decode(&mut buf.as_slice()).unwrap(), array);
+    assert!(NibbleArray::decode(&mut [1, 0].as_slice()).is_err());
+    assert!(NibbleArray::from_bytes(vec![0; 10]).is_none());
+}
+
+#[test]
+fn block_light() {
+    let mut sections = floored();
+    sections[0].set_block(8, 5, 8, block(Block::Glowstone));
+    sections[0].set_block(8, 6, 9, block(Block::Stone));
+
+    let mut engine = LightEngine::new(2);
+    engine.insert(0, 0, sections);
+    engine.compute();
+
+    assert_eq!(engine.block_light(8, 5, 8), 15);
+    assert_eq!(engine.block_light(8, 4, 8), 14);
+    assert_eq!(engine.block_light(8, 10, 8), 10);
+    assert_eq!(engine.block_light(3, 5, 8), 10);
+    // Opaque blocks are dark, but light goes around them.
+    assert_eq!(engine.block_light(8, 6, 9), 0);
+    assert_eq!(engine.block_light(8, 7, 9), 12);
+    assert_eq!(engine.block_light(8, 0, 8), 0);
+    // The neighbouring column is not loaded.
+    assert_eq!(engine.block_light(-1, 5, 8), 0);
+}
+
+#[test]
+fn sky_light() {
+    let mut sections = floored();
+
+    for x in 0..16 {
+        for z in 0..16 {
+            sections[1].set_block(x, 4, z, block(Block::Stone));
+        }
+    }
+
+    sections[1].set_block(0, 4, 0, BlockState::default());
+    sections[1].set_block(3, 12, 3, block(Block::OakLeaves));
+
+    let mut engine = LightEngine::new(2);
+    engine.insert(0, 0, sections);
+    engine.compute();
+
+    assert_eq!(engine.sky_light(5, 31, 5), 15);
+    assert_eq!(engine.sky_light(5, 40, 5), 15);
+    assert_eq!(engine.sky_light(3, 28, 3), 14);
+    // Light under leaves comes in from the sides rather than through them.
+    assert_eq!(engine.sky_light(3, 27, 3), 14);
+    assert_eq!(engine.sky_light(5, 20, 5), 0);
+    // Through the hole in the roof, full light falls down to the floor and spreads out.
+    assert_eq!(engine.sky_light(0, 19, 0), 15);
+    assert_eq!(engine.sky_light(0, 1, 0), 15);
+    assert_eq!(engine.sky_light(1, 1, 0), 14);
+    assert_eq!(engine.sky_light(5, 1, 5), 5);
+    assert_eq!(engine.sky_light(5, 0, 5), 0);
+}
+
+#[test]
+fn neighbouring_columns() {
+    let mut sections = floored();
+    sections[0].set_block(15, 5, 0, block(Block::Glowstone));
+
+    let mut engine = LightEngine::new(2);
+    engine.insert(0, 0, sections);
+    engine.insert(1, 0, floored());
+    engine.compute();
+
+    assert_eq!(engine.block_light(16, 5, 0), 14);
+    assert_eq!(engine.block_light(20, 5, 0), 10);
+    assert!(engine.light_data(2, 0).is_none());
+
+    engine.remove(1, 0).unwrap();
+    engine.compute();
+
+    assert_eq!(engine.block_light(16, 5, 0), 0);
+}
+
+#[test]
+fn light_data() {
+    let mut sections = floored();
+    sections[0].set_block(8, 1, 8, block(Block::Torch));
+
+    let mut engine = LightEngine::new(2);
+    engine.insert(0, 0, sections);
+    engine.compute();
+
+    let light = engine.light_data(0, 0).unwrap();
+
+    assert!(light.trust_edges);
+    // Light sections start one below the world and end one above it.
+    assert_eq!(light.sky_light_mask, BitSet(vec![0b1110]));
+    assert_eq!(light.empty_sky_light_mask, BitSet(vec![0b0001]));
+    assert_eq!(light.block_light_mask, BitSet(vec![0b0010]));
+    assert_eq!(light.empty_block_light_mask, BitSet(vec![0b1101]));
+    assert_eq!(light.sky_light.len(), 3);
+    assert_eq!(light.sky_light[2], NibbleArray::filled(15));
+    assert_eq!(light.block_light[0].get(8, 1, 8), 14);
+}
@@ -0,0 +1,258 @@
+use arrow_protocol::{
+    nbt::{Compound, Tag},
+    play::{
+        metadata::GlobalPosition,
+        registry_codec::{
+            ChatDecoration, ChatTypes, CodecRegistry, DimensionTypeRef, IntProvider,
+            LegacyChatDisplay, LegacyChatType, LegacyNarration, RegistryCodec,
+        },
+        GameMode, LoginPlay, PreviousGameMode, Respawn,
+    },
+    types::{identifier::Identifier, position::Position, varint::VarInt, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+fn id(path: &str) -> Identifier {
+    Identifier::minecraft(path.to_owned()).unwrap()
+}
+
+fn login_play(codec: RegistryCodec, dimension_type: DimensionTypeRef) -> LoginPlay {
+    LoginPlay {
+        entity_id: 1,
+        is_hardcore: false,
+        game_mode: GameMode::Creative,
+        previous_game_mode: PreviousGameMode::None,
+        dimension_names: vec![id("overworld"), id("the_nether")],
+        registry_codec: codec,
+        dimension_type,
+        dimension_name: id("overworld"),
+        hashed_seed: -42,
+        max_players: VarInt(20),
+        view_distance: VarInt(10),
+        simulation_distance: VarInt(8),
+        reduced_debug_info: false,
+        enable_respawn_screen: true,
+        is_debug: false,
+        is_flat: true,
+        death_location: Some(GlobalPosition {
+            dimension: id("the_nether"),
+            position: Position { x: 1, y: 2, z: 3 },
+        }),
+    }
+}
+
+#[test]
+fn vanilla_codec() {
+    let codec = RegistryCodec::vanilla();
+
+    assert_eq!(codec.dimension_types.value.len(), 4);
+    assert_eq!(codec.biomes.value.len(), 63);
+    assert!(matches!(
+        &codec.chat_types,
+        Some(ChatTypes::Current(chat_types)) if chat_types.value.len() == 7
+    ));
+    assert_eq!(codec.biomes.id(&id("plains")), Some(1));
+
+    let overworld = codec.dimension_types.get(&id("overworld")).unwrap();
+
+    assert_eq!((overworld.min_y, overworld.height), (-64, 384));
+    assert_eq!(
+        overworld.monster_spawn_light_level,
+        Some(IntProvider::Uniform {
+            min_inclusive: 0,
+            max_inclusive: 7
+        })
+    );
+
+    let nether = codec.dimension_types.get(&id("the_nether")).unwrap();
+
+    assert_eq!(nether.fixed_time, Some(18000));
+    assert_eq!(
+        nether.monster_spawn_light_level,
+        Some(IntProvider::Constant(11))
+    );
+
+    let mut buf = Vec::new();
+    codec.encode(&mut buf).unwrap();
+
+    assert_eq!(RegistryCodec::decode(&mut buf.as_slice()).unwrap(), codec);
+
+    // Booleans are bytes, and the registries are lists of named entries with ids.
+    let compound = Compound::decode(&mut buf.as_slice()).unwrap();
+    let dimension_types = compound["minecraft:dimension_type"].as_compound().unwrap();
+    let entry = dimension_types["value"].as_list().unwrap()[2]
+        .as_compound()
+        .unwrap();
+
+    assert_eq!(
+        dimension_types["type"],
+        Tag::String("minecraft:dimension_type".into())
+    );
+    assert_eq!(entry["name"], Tag::String("minecraft:the_end".into()));
+    assert_eq!(entry["id"], Tag::Int(2));
+
+    let element = entry["element"].as_compound().unwrap();
+
+    assert_eq!(element["natural"], Tag::Byte(0));
+    assert_eq!(element["ambient_light"], Tag::Float(0.0));
+    assert_eq!(element["fixed_time"], Tag::Long(6000));
+    assert_eq!(
+        element["monster_spawn_light_level"].as_compound().unwrap()["type"],
+        Tag::String("minecraft:uniform".into())
+    );
+}
+
+#[test]
+fn custom_entries() {
+    let mut overworld = RegistryCodec::vanilla()
+        .dimension_types
+        .get(&id("overworld"))
+        .unwrap()
+        .clone();
+    overworld.height = 256;
+
+    let codec = RegistryCodec::vanilla()
+        .with_dimension_type(id("overworld"), overworld)
+        .with_dimension_type(
+            id("flat"),
+            RegistryCodec::vanilla().dimension_types.value[0]
+                .element
+                .clone(),
+        );
+
+    assert_eq!(codec.dimension_types.value.len(), 5);
+    assert_eq!(codec.dimension_types.id(&id("flat")), Some(4));
+    assert_eq!(
+        codec.dimension_types.get(&id("overworld")).unwrap().height,
+        256
+    );
+}
+
+#[test]
+fn login() {
+    let packet = login_play(
+        RegistryCodec::vanilla(),
+        DimensionTypeRef::Name(id("overworld")),
+    );
+
+    let data = packet.serialize(ProtocolVersion::V1_19_2).unwrap();
+    let decoded = LoginPlay::deserialize(ProtocolVersion::V1_19_2, &mut data.as_slice()).unwrap();
+
+    assert_eq!(&data[..7], [0, 0, 0, 1, 0, 1, 0xff]);
+    assert_eq!(decoded.registry_codec, packet.registry_codec);
+    assert_eq!(decoded.dimension_type, packet.dimension_type);
+    assert_eq!(decoded.death_location, packet.death_location);
+    assert_eq!(decoded.simulation_distance, VarInt(8));
+    assert!(decoded.is_flat);
+
+    // Before 1.19 the dimension type is sent in full, and there is no death location.
+    assert!(packet.serialize(ProtocolVersion::V1_18_2).is_err());
+
+    let mut codec = RegistryCodec::vanilla();
+    codec.chat_types = None;
+
+    let overworld = codec.dimension_types.value[0].element.clone();
+    let packet = login_play(codec, DimensionTypeRef::Element(Box::new(overworld)));
+
+    assert!(packet.serialize(ProtocolVersion::V1_19).is_err());
+
+    let data = packet.serialize(ProtocolVersion::V1_18_2).unwrap();
+    let decoded = LoginPlay::deserialize(ProtocolVersion::V1_18_2, &mut data.as_slice()).unwrap();
+
+    assert_eq!(decoded.registry_codec, packet.registry_codec);
+    assert_eq!(decoded.dimension_type, packet.dimension_type);
+    assert_eq!(decoded.death_location, None);
+}
+
+#[test]
+fn legacy_chat_types() {
+    let decoration = |key: &str| ChatDecoration {
+        translation_key: key.to_owned(),
+        parameters: vec!["sender".to_owned(), "content".to_owned()],
+        style: None,
+    };
+
+    let mut chat_types = CodecRegistry::new(id("chat_type"));
+    chat_types.insert(
+        id("chat"),
+        LegacyChatType {
+            chat: Some(LegacyChatDisplay {
+                decoration: Some(decoration("chat.type.text")),
+            }),
+            overlay: None,
+            narration: Some(LegacyNarration {
+                decoration: Some(decoration("chat.type.text.narrate")),
+                priority: "chat".to_owned(),
+            }),
+        },
+    );
+    chat_types.insert(
+        id("game_info"),
+        LegacyChatType {
+            chat: None,
+            overlay: Some(LegacyChatDisplay { decoration: None }),
+            narration: None,
+        },
+    );
+
+    let mut codec = RegistryCodec::vanilla();
+    codec.chat_types = Some(ChatTypes::Legacy(chat_types));
+
+    let packet = login_play(codec, DimensionTypeRef::Name(id("overworld")));
+
+    let data = packet.serialize(ProtocolVersion::V1_19).unwrap();
+    let decoded = LoginPlay::deserialize(ProtocolVersion::V1_19, &mut data.as_slice()).unwrap();
+
+    assert_eq!(decoded.registry_codec, packet.registry_codec);
+
+    // Each version only takes its own format of chat types.
+    assert!(packet.serialize(ProtocolVersion::V1_19_2).is_err());
+    assert!(LoginPlay::deserialize(ProtocolVersion::V1_19_2, &mut data.as_slice()).is_err());
+
+    let packet = login_play(
+        RegistryCodec::vanilla(),
+        DimensionTypeRef::Name(id("overworld")),
+    );
+
+    assert!(packet.serialize(ProtocolVersion::V1_19).is_err());
+
+    let mut buf = Vec::new();
+    packet.registry_codec.encode(&mut buf).unwrap();
+
+    let compound = Compound::decode(&mut buf.as_slice()).unwrap();
+    let chat = compound["minecraft:chat_type"].as_compound().unwrap()["value"]
+        .as_list()
+        .unwrap()[0]
+        .as_compound()
+        .unwrap()["element"]
+        .as_compound()
+        .unwrap();
+
+    assert!(chat["chat"]
+        .as_compound()
+        .unwrap()
+        .contains_key("translation_key"));
+}
+
+#[test]
+fn respawn() {
+    let packet = Respawn {
+        dimension_type: DimensionTypeRef::Name(id("the_end")),
+        dimension_name: id("the_end"),
+        hashed_seed: 7,
+        game_mode: GameMode::Survival,
+        previous_game_mode: PreviousGameMode::Spectator,
+        is_debug: false,
+        is_flat: false,
+        copy_metadata: true,
+        death_location: None,
+    };
+
+    let data = packet.serialize(ProtocolVersion::V1_19).unwrap();
+    let decoded = Respawn::deserialize(ProtocolVersion::V1_19, &mut data.as_slice()).unwrap();
+
+    assert_eq!(decoded.dimension_type, packet.dimension_type);
+    assert_eq!(decoded.previous_game_mode, PreviousGameMode::Spectator);
+    assert!(decoded.copy_metadata);
+    assert_eq!(data.last(), Some(&0));
+}
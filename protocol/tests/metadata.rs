@@ -0,0 +1,202 @@
+use arrow_protocol::{
+    chat::{Chat, Component, TextComponent},
+    play::{
+        entity_type::EntityType,
+        metadata::{
+            EntityFlags, EntityMetadata, Metadata, MetadataValue, Pose, Rotation, VillagerData,
+        },
+        particle::{Particle, ParticleData, PositionSource},
+        Play, SetEntityMetadata, SkinParts,
+    },
+    registry::{Block, BlockState, CatVariant, ParticleType, VillagerProfession, VillagerType},
+    types::{position::Position, varint::VarInt, Context, Decode, Encode},
+    version::ProtocolVersion,
+    Bound,
+};
+
+fn text(text: &str) -> Chat {
+    Chat::default().with_component(Component::String(TextComponent {
+        text: text.to_owned(),
+    }))
+}
+
+fn round_trip(metadata: &Metadata, version: ProtocolVersion) -> Vec<u8> {
+    let cx = Context::new(version);
+    let mut buf = Vec::new();
+    metadata.encode_with(&mut buf, &cx).unwrap();
+
+    assert_eq!(
+        &Metadata::decode_with(&mut buf.as_slice(), &cx).unwrap(),
+        metadata
+    );
+
+    buf
+}
+
+#[test]
+fn metadata_values() {
+    let mut metadata = Metadata::new();
+    metadata.set(0, EntityFlags::INVISIBLE);
+    metadata.set(2, Some(text("Dinnerbone")));
+
+    // Index, type and value of each entry, then the end marker.
+    let buf = round_trip(&metadata, ProtocolVersion::V1_19);
+
+    assert_eq!(&buf[..5], [0, 0, 0x20, 2, 5]);
+    assert_eq!(buf.last(), Some(&0xff));
+
+    let mut metadata = Metadata::new();
+    metadata.set(
+        1,
+        Rotation {
+            x: 0.0,
+            y: 90.0,
+            z: -45.0,
+        },
+    );
+    metadata.set(2, Some(Position { x: 1, y: -2, z: 3 }));
+    metadata.set(3, Option::<BlockState>::None);
+    metadata.set(4, Some(BlockState::from(Block::Stone)));
+    metadata.set(5, Some(4));
+    metadata.set(6, Pose::Sleeping);
+    metadata.set(
+        7,
+        VillagerData {
+            ty: VillagerType::Taiga,
+            profession: VillagerProfession::Librarian,
+            level: VarInt(2),
+        },
+    );
+    metadata.set(
+        8,
+        Particle {
+            ty: ParticleType::Dust,
+            data: ParticleData::Dust {
+                color: [1.0, 0.0, 0.5],
+                scale: 2.0,
+            },
+        },
+    );
+    metadata.set(9, CatVariant::Jellie);
+
+    round_trip(&metadata, ProtocolVersion::V1_19_2);
+
+    assert_eq!(metadata.get::<Pose>(6), Some(Pose::Sleeping));
+    assert_eq!(metadata.get::<f32>(6), None);
+    assert_eq!(metadata.get::<Option<i32>>(5), Some(Some(4)));
+
+    // Absent block states and optional VarInts are sent as 0, and present VarInts shifted by 1.
+    let mut buf = Vec::new();
+    MetadataValue::BlockState(None).encode(&mut buf).unwrap();
+    MetadataValue::OptionalVarInt(Some(4))
+        .encode(&mut buf)
+        .unwrap();
+
+    assert_eq!(buf, [13, 0, 17, 5]);
+}
+
+#[test]
+fn unsupported_types() {
+    let mut metadata = Metadata::new();
+    metadata.set(19, CatVariant::Black);
+
+    let mut buf = Vec::new();
+
+    assert!(metadata
+        .encode_with(&mut buf, &Context::new(ProtocolVersion::V1_18_2))
+        .is_err());
+    assert!(Metadata::decode_with(
+        &mut [0, 19, 0, 0xff].as_slice(),
+        &Context::new(ProtocolVersion::V1_18)
+    )
+    .is_err());
+    assert!(Metadata::decode_with(&mut [0, 23, 0, 0xff].as_slice(), &Context::default()).is_err());
+}
+
+#[test]
+fn particles() {
+    let particle = Particle {
+        ty: ParticleType::Vibration,
+        data: ParticleData::Vibration {
+            source: PositionSource::Entity {
+                entity_id: VarInt(7),
+                eye_height: 1.5,
+            },
+            ticks: VarInt(20),
+        },
+    };
+
+    let mut buf = Vec::new();
+    particle.encode(&mut buf).unwrap();
+
+    assert_eq!(Particle::decode(&mut buf.as_slice()).unwrap(), particle);
+    assert!(particle
+        .encode_with(&mut Vec::new(), &Context::new(ProtocolVersion::V1_18_2))
+        .is_err());
+
+    let particle = Particle::from(ParticleType::Flame);
+    let mut buf = Vec::new();
+    particle.encode(&mut buf).unwrap();
+
+    assert_eq!(buf, [ParticleType::Flame as u8]);
+    assert_eq!(Particle::decode(&mut buf.as_slice()).unwrap(), particle);
+}
+
+#[test]
+fn entity_metadata() {
+    let mut zombie = EntityMetadata::new(EntityType::Zombie);
+    zombie
+        .set_flags(EntityFlags::ON_FIRE | EntityFlags::GLOWING)
+        .set_custom_name(Some(text("Grumm")))
+        .set_pose(Pose::Crouching)
+        .set_health(5.0);
+
+    assert_eq!(
+        zombie.flags(),
+        Some(EntityFlags::ON_FIRE | EntityFlags::GLOWING)
+    );
+    assert_eq!(zombie.custom_name(), Some(text("Grumm")));
+    assert_eq!(zombie.pose(), Some(Pose::Crouching));
+    assert_eq!(zombie.health(), Some(5.0));
+    assert_eq!(zombie.air_ticks(), None);
+    // Zombies are not ageable mobs, so index 16 is not their age.
+    assert_eq!(zombie.is_baby(), None);
+
+    zombie.set_custom_name(None);
+
+    assert_eq!(zombie.custom_name(), None);
+
+    let mut player = EntityMetadata::new(EntityType::Player);
+    player.set_skin_parts(SkinParts::CAPE | SkinParts::HAT);
+
+    assert_eq!(player.metadata.0[&17], MetadataValue::Byte(0x41));
+    assert_eq!(player.skin_parts(), Some(SkinParts::CAPE | SkinParts::HAT));
+    assert_eq!(player.mob_flags(), None);
+
+    let packet = SetEntityMetadata {
+        entity_id: VarInt(3),
+        metadata: player.metadata.clone(),
+    };
+    let (id, data) = Play::from(packet)
+        .serialize(ProtocolVersion::V1_19)
+        .unwrap();
+    let decoded = Play::deserialize(
+        Bound::Clientbound,
+        ProtocolVersion::V1_19,
+        id,
+        &mut data.as_slice(),
+    )
+    .unwrap();
+
+    let Play::SetEntityMetadata(decoded) = decoded else {
+        panic!("decoded another packet");
+    };
+
+    assert_eq!(decoded.metadata, player.metadata);
+}
+
+#[test]
+#[should_panic]
+fn wrong_entity_class() {
+    EntityMetadata::new(EntityType::Arrow).set_health(1.0);
+}
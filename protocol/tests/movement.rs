@@ -0,0 +1,123 @@
+use arrow_protocol::{
+    play::{
+        movement::{position_delta, EntityMovement, EntityPosition},
+        Play, RemoveEntities, SetEntityVelocity,
+    },
+    types::{varint::VarInt, Angle},
+    version::ProtocolVersion,
+    Bound,
+};
+
+fn at(x: f64, y: f64, z: f64) -> EntityPosition {
+    EntityPosition {
+        x,
+        y,
+        z,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn angles() {
+    assert_eq!(Angle::from_degrees(90.0).to_degrees(), 90.0);
+    // Negative angles and angles past a full turn wrap around.
+    assert_eq!(Angle::from_degrees(-90.0), Angle::from_degrees(270.0));
+    assert_eq!(Angle::from_degrees(450.0), Angle::from_degrees(90.0));
+    assert_eq!(Angle::from_degrees(-0.5).to_degrees(), 358.59375);
+}
+
+#[test]
+fn deltas() {
+    assert_eq!(position_delta(0.0, 1.0), Some(4096));
+    assert_eq!(position_delta(-0.5, -1.0), Some(-2048));
+    assert_eq!(position_delta(0.0, 7.99), Some(32727));
+    assert_eq!(position_delta(0.0, 8.0), None);
+    // Both ends are rounded, so that moves add up to the actual position.
+    assert_eq!(position_delta(0.0001, 0.0002), Some(0));
+    assert_eq!(position_delta(0.0002, 0.0003), Some(1));
+}
+
+#[test]
+fn movement() {
+    let start = at(10.0, 64.0, -10.0);
+
+    assert!(EntityMovement::new(1, &start, &start).is_none());
+
+    let Some(EntityMovement::Position(packet)) =
+        EntityMovement::new(1, &start, &at(10.5, 63.0, -10.0))
+    else {
+        panic!("expected a relative move");
+    };
+
+    assert_eq!(
+        (packet.delta_x, packet.delta_y, packet.delta_z),
+        (2048, -4096, 0)
+    );
+    assert_eq!(packet.delta(), [0.5, -1.0, 0.0]);
+
+    let turned = EntityPosition {
+        yaw: -45.0,
+        ..start
+    };
+    let Some(EntityMovement::Rotation(packet)) = EntityMovement::new(1, &start, &turned) else {
+        panic!("expected a rotation");
+    };
+
+    assert_eq!(packet.yaw, Angle::from_degrees(315.0));
+
+    let landed = EntityPosition {
+        on_ground: true,
+        ..start
+    };
+
+    assert!(matches!(
+        EntityMovement::new(1, &start, &landed),
+        Some(EntityMovement::Position(_))
+    ));
+
+    let moved_and_turned = EntityPosition { x: 11.0, ..turned };
+
+    assert!(matches!(
+        EntityMovement::new(1, &start, &moved_and_turned),
+        Some(EntityMovement::PositionAndRotation(_))
+    ));
+
+    let Some(EntityMovement::Teleport(packet)) =
+        EntityMovement::new(2, &start, &at(10.0, 64.0, 0.0))
+    else {
+        panic!("expected a teleport");
+    };
+
+    assert_eq!((packet.x, packet.y, packet.z), (10.0, 64.0, 0.0));
+
+    let (id, data) = Play::from(EntityMovement::Teleport(packet))
+        .serialize(ProtocolVersion::V1_19_2)
+        .unwrap();
+
+    assert_eq!(id, 0x66);
+    assert!(Play::deserialize(
+        Bound::Clientbound,
+        ProtocolVersion::V1_19_2,
+        id,
+        &mut data.as_slice()
+    )
+    .is_ok());
+}
+
+#[test]
+fn velocity_and_removal() {
+    let packet = SetEntityVelocity::new(1, [0.25, 5.0, -0.1]);
+
+    assert_eq!(
+        (packet.velocity_x, packet.velocity_y, packet.velocity_z),
+        (2000, 31200, -800)
+    );
+
+    let data = RemoveEntities {
+        entity_ids: vec![VarInt(1), VarInt(300)],
+    }
+    .serialize(ProtocolVersion::V1_19)
+    .unwrap();
+
+    assert_eq!(data, [2, 1, 0xac, 0x02]);
+}
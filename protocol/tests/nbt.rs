@@ -0,0 +1,279 @@
+use arrow_protocol::{
+    error::{DeserializeError, NbtError, SerializeError, SnbtError},
+    nbt::{self, snbt, Compound, Nbt, Tag, MAX_DEPTH},
+    types::{
+        slot::{Slot, SlotData},
+        varint::VarInt,
+        Context, Decode, Encode,
+    },
+    version::ProtocolVersion,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn root_names() {
+    let mut compound = Compound::new();
+    compound.insert("id".to_string(), Tag::Short(1));
+
+    let mut named = Vec::new();
+    compound.write_named("root", &mut named).unwrap();
+
+    assert_eq!(named, b"\x0a\x00\x04root\x02\x00\x02id\x00\x01\x00");
+    assert_eq!(
+        Compound::read_named(&mut named.as_slice()).unwrap(),
+        ("root".to_string(), compound.clone())
+    );
+
+    let mut network = Vec::new();
+    compound
+        .encode_with(&mut network, &Context::new(ProtocolVersion::V1_19))
+        .unwrap();
+
+    assert_eq!(network, b"\x0a\x00\x00\x02\x00\x02id\x00\x01\x00");
+
+    let cx = Context::new(ProtocolVersion::V1_20_2);
+    let mut nameless = Vec::new();
+    compound.encode_with(&mut nameless, &cx).unwrap();
+
+    assert_eq!(nameless, b"\x0a\x02\x00\x02id\x00\x01\x00");
+    assert_eq!(
+        Compound::decode_with(&mut nameless.as_slice(), &cx).unwrap(),
+        compound
+    );
+    assert_eq!(
+        Tag::read_nameless(&mut b"\x08\x00\x02hi".as_slice()).unwrap(),
+        Tag::from("hi")
+    );
+}
+
+#[test]
+fn absent_root() {
+    let mut buf = Vec::new();
+    Nbt::default().encode(&mut buf).unwrap();
+
+    assert_eq!(buf, [0]);
+    assert_eq!(Nbt::decode(&mut buf.as_slice()).unwrap(), Nbt(None));
+    assert!(matches!(
+        Compound::decode(&mut buf.as_slice()),
+        Err(DeserializeError::NbtError(NbtError::UnexpectedEnd))
+    ));
+
+    let slot = Slot {
+        data: Some(SlotData {
+            id: VarInt(1),
+            count: 1,
+            nbt: Nbt::default(),
+        }),
+    };
+    let mut buf = Vec::new();
+    slot.encode(&mut buf).unwrap();
+
+    assert_eq!(buf, [1, 1, 1, 0]);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: String,
+    count: u8,
+    unbreakable: bool,
+    damage: Option<i32>,
+    #[serde(serialize_with = "nbt::long_array")]
+    heightmap: Vec<i64>,
+    #[serde(serialize_with = "nbt::int_array")]
+    uuid: [i32; 4],
+    enchantments: Vec<Enchantment>,
+    hide: HideFlags,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Enchantment {
+    id: String,
+    lvl: i16,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum HideFlags {
+    None,
+    Some { flags: i32 },
+}
+
+#[test]
+fn serde() {
+    let item = Item {
+        id: "minecraft:diamond_sword".to_string(),
+        count: 200,
+        unbreakable: true,
+        damage: None,
+        heightmap: vec![1, 2],
+        uuid: [1, 2, 3, 4],
+        enchantments: vec![Enchantment {
+            id: "minecraft:sharpness".to_string(),
+            lvl: 5,
+        }],
+        hide: HideFlags::Some { flags: 3 },
+    };
+
+    let compound = nbt::to_compound(&item).unwrap();
+
+    assert_eq!(compound["count"], Tag::Byte(-56));
+    assert_eq!(compound["unbreakable"], Tag::Byte(1));
+    assert!(!compound.contains_key("damage"));
+    assert_eq!(compound["heightmap"], Tag::LongArray(vec![1, 2]));
+    assert_eq!(compound["uuid"], Tag::IntArray(vec![1, 2, 3, 4]));
+    assert_eq!(
+        compound["enchantments"].as_list().unwrap()[0]
+            .as_compound()
+            .unwrap()["lvl"],
+        Tag::Short(5)
+    );
+
+    let mut buf = Vec::new();
+    compound.encode(&mut buf).unwrap();
+
+    let decoded = Compound::decode(&mut buf.as_slice()).unwrap();
+
+    assert_eq!(decoded, compound);
+    assert_eq!(nbt::from_compound::<Item>(decoded).unwrap(), item);
+    assert_eq!(nbt::to_tag(&HideFlags::None).unwrap(), Tag::from("None"));
+    assert_eq!(
+        nbt::from_tag::<Compound>(Tag::Compound(compound.clone())).unwrap()["id"],
+        compound["id"]
+    );
+    assert!(nbt::to_compound(&1).is_err());
+}
+
+#[test]
+fn limits() {
+    let mut nested = vec![0x09, 0x00, 0x00];
+
+    for _ in 0..=MAX_DEPTH {
+        nested.extend([0x09, 0x00, 0x00, 0x00, 0x01]);
+    }
+
+    nested.extend([0x00, 0x00, 0x00, 0x00, 0x00]);
+
+    assert!(matches!(
+        Tag::decode(&mut nested.as_slice()),
+        Err(DeserializeError::NbtError(NbtError::TooDeep(_)))
+    ));
+
+    // A huge length is rejected before anything is allocated.
+    assert!(matches!(
+        Tag::read_nameless(&mut b"\x0c\x7f\xff\xff\xff".as_slice()),
+        Err(DeserializeError::NbtError(NbtError::TooLarge(_)))
+    ));
+    assert!(matches!(
+        Tag::read_nameless(&mut b"\x09\x0a\x00\x01\x00\x00".as_slice()),
+        Err(DeserializeError::UnexpectedEof)
+    ));
+
+    let mut large = vec![0x07, 0x00, 0x40, 0x00, 0x00];
+    large.resize(large.len() + 0x400000, 0);
+
+    assert!(matches!(
+        Tag::read_nameless(&mut large.as_slice()),
+        Err(DeserializeError::NbtError(NbtError::TooLarge(_)))
+    ));
+
+    // Every element counts against the limit, even if its payload is a single byte.
+    let mut compounds = vec![0x09, 0x0a, 0x00, 0x18, 0x00, 0x00];
+    compounds.resize(compounds.len() + 0x180000, 0);
+
+    assert!(matches!(
+        Tag::read_nameless(&mut compounds.as_slice()),
+        Err(DeserializeError::NbtError(NbtError::TooLarge(_)))
+    ));
+    assert!(matches!(
+        Tag::read_nameless(&mut b"\x0d".as_slice()),
+        Err(DeserializeError::NbtError(NbtError::InvalidTag(13)))
+    ));
+
+    let mixed = Tag::List(vec![Tag::Int(1), Tag::from("a")]);
+
+    assert!(matches!(
+        mixed.write_nameless(&mut Vec::new()),
+        Err(SerializeError::NbtError(NbtError::MixedList))
+    ));
+}
+
+#[test]
+fn snbt() {
+    let s = r#"{display:{Name:'"x"',Lore:["a","b"]},Count:1b,Damage:-3s,Seed:5L,Speed:0.5f,Scale:1.0e20d,Health:20.0d,Bytes:[B;1B,-2B],Ints:[I;1,2],Longs:[L;3L],"a b":[],"":{}}"#;
+    let tag: Tag = s.parse().unwrap();
+    let compound = tag.as_compound().unwrap();
+
+    assert_eq!(
+        compound["display"].as_compound().unwrap()["Name"],
+        Tag::from("\"x\"")
+    );
+    assert_eq!(compound["Count"], Tag::Byte(1));
+    assert_eq!(compound["Damage"], Tag::Short(-3));
+    assert_eq!(compound["Seed"], Tag::Long(5));
+    assert_eq!(compound["Speed"], Tag::Float(0.5));
+    assert_eq!(compound["Scale"], Tag::Double(1e20));
+    assert_eq!(compound["Bytes"], Tag::ByteArray(vec![1, -2]));
+    assert_eq!(compound["Ints"], Tag::IntArray(vec![1, 2]));
+    assert_eq!(compound["Longs"], Tag::LongArray(vec![3]));
+    assert_eq!(compound["a b"], Tag::List(vec![]));
+    assert_eq!(tag.to_string(), s.replace("1.0e20d", "1e20d"));
+    assert_eq!(snbt::from_str(&snbt::to_string_pretty(&tag)).unwrap(), tag);
+}
+
+#[test]
+fn snbt_values() {
+    let parse = |s: &str| s.parse::<Tag>().unwrap();
+
+    assert_eq!(parse("1"), Tag::Int(1));
+    assert_eq!(parse("+1"), Tag::Int(1));
+    assert_eq!(parse("1.5"), Tag::Double(1.5));
+    assert_eq!(parse(".5"), Tag::Double(0.5));
+    assert_eq!(parse("2F"), Tag::Float(2.0));
+    assert_eq!(parse("2d"), Tag::Double(2.0));
+    assert_eq!(parse("true"), Tag::Byte(1));
+    assert_eq!(parse("false"), Tag::Byte(0));
+    // Numbers which are out of range or look unusual are strings, like in vanilla.
+    assert_eq!(parse("300b"), Tag::from("300b"));
+    assert_eq!(parse("01"), Tag::from("01"));
+    assert_eq!(parse("1e5"), Tag::from("1e5"));
+    assert_eq!(
+        parse("'minecraft:stone'").to_string(),
+        "\"minecraft:stone\""
+    );
+    assert_eq!(parse(r#"'it\'s'"#), Tag::from("it's"));
+    assert_eq!(
+        parse("[ 1 , 2 , ]"),
+        Tag::List(vec![Tag::Int(1), Tag::Int(2)])
+    );
+    assert_eq!(Tag::from("it's").to_string(), r#""it's""#);
+    assert_eq!(Tag::from(r#"a\"b'"#).to_string(), r#"'a\\"b\''"#);
+
+    let compound: Compound = "{ id : 'minecraft:stone' , Count : 1b }".parse().unwrap();
+
+    assert_eq!(compound.to_string(), r#"{id:"minecraft:stone",Count:1b}"#);
+    assert_eq!(
+        snbt::to_string_pretty(&Tag::Compound(compound)),
+        "{\n    id: \"minecraft:stone\",\n    Count: 1b\n}"
+    );
+}
+
+#[test]
+fn snbt_errors() {
+    let error = |s: &str| s.parse::<Tag>().unwrap_err();
+
+    assert_eq!(error(""), SnbtError("Expected value", 0));
+    assert_eq!(error("{a:1"), SnbtError("Expected '}'", 4));
+    assert_eq!(error("{:1}"), SnbtError("Expected key", 1));
+    assert_eq!(error("[1,a]"), SnbtError("Mixed list element types", 3));
+    assert_eq!(error("[I;1b]"), SnbtError("Invalid array element type", 3));
+    assert_eq!(error("'a"), SnbtError("Unclosed quoted string", 2));
+    assert_eq!(error(r#""\n""#), SnbtError("Invalid escape sequence", 3));
+    assert_eq!(error("1 2"), SnbtError("Trailing data", 2));
+    assert_eq!(
+        "[1]".parse::<Compound>().unwrap_err(),
+        SnbtError("Expected compound", 0)
+    );
+    assert_eq!(
+        error(&"[".repeat(MAX_DEPTH + 2)),
+        SnbtError("Too deeply nested", MAX_DEPTH + 1)
+    );
+}
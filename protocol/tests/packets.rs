@@ -1,9 +1,20 @@
 use aes::cipher::KeyIvInit;
 use arrow_protocol::{
-    chat::Chat, handshake::NextState, types::varint::VarInt, Bound, Decryptor, Encryptor,
-    PacketCompression, Protocol, State,
+    chat::Chat,
+    codec::Codec,
+    error::{DeserializeError, SerializeError},
+    handshake::NextState,
+    login::{
+        EncryptedVerifyToken, EncryptionResponse, EncryptionVerify, Login, LoginDisconnect,
+        SaltSignature, SharedSecret,
+    },
+    play::{ChatMessage, Play, SpawnExperienceOrb},
+    types::varint::VarInt,
+    version::ProtocolVersion,
+    Bound, Decryptor, Encryptor, PacketCompression, Protocol, State,
 };
-use bytes::{BufMut, Bytes, BytesMut};
+use bytes::{BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
 
 macro_rules! test_packet {
     ($module:ident :: $packet:ident { $($field:ident : $value:expr),* } = $bound:ident($state:ident) $(; $state_name:ident)?) => {
@@ -45,7 +56,7 @@ fn handshake() {
 fn login_disconnect() {
     test_packet! {
         login::LoginDisconnect {
-            reason: Chat::default().with_bold(true)
+            reason: Chat::default().with_bold(true).into()
         } = Clientbound(Login); LoginDisconnect
     }
 }
@@ -149,3 +160,158 @@ fn compression() {
         assert_eq!(packet.next_state, handshake.next_state);
     }
 }
+
+#[test]
+fn versioned_packet_ids() {
+    let chat = ChatMessage {
+        message: "hi".to_string(),
+        timestamp: 0,
+        salt: 0,
+        signature: Default::default(),
+        signed_preview: false,
+        last_seen_messages: vec![],
+        last_received_message: None,
+    };
+    let play = Play::from(chat);
+
+    let (id, data) = play.serialize(ProtocolVersion::V1_18).unwrap();
+
+    assert_eq!(id, 0x03);
+    assert_eq!(data, b"\x02hi");
+
+    let (id, data) = play.serialize(ProtocolVersion::V1_19).unwrap();
+
+    assert_eq!(id, 0x04);
+    assert_eq!(data.len(), 3 + 8 + 8 + 1 + 1);
+
+    let (id, data) = play.serialize(ProtocolVersion::V1_19_2).unwrap();
+
+    assert_eq!(id, 0x05);
+    assert_eq!(data.len(), 3 + 8 + 8 + 1 + 1 + 1 + 1);
+
+    let packet = Play::deserialize(
+        Bound::Serverbound,
+        ProtocolVersion::V1_18,
+        0x03,
+        &mut b"\x02hi".as_slice(),
+    )
+    .unwrap();
+
+    assert!(matches!(packet, Play::ChatMessage(chat) if chat.message == "hi"));
+    assert!(matches!(
+        Play::deserialize(
+            Bound::Serverbound,
+            ProtocolVersion::V1_19,
+            0x03,
+            &mut &data[..]
+        ),
+        Ok(Play::ChatCommand(_))
+    ));
+}
+
+#[test]
+fn unsupported_versions() {
+    let orb = Play::from(SpawnExperienceOrb {
+        entity_id: VarInt(1),
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        amount: 1,
+    });
+
+    assert_eq!(orb.packet_id(ProtocolVersion::V1_18), Some(0x01));
+    assert_eq!(orb.packet_id(ProtocolVersion::V1_21_5), None);
+    assert!(matches!(
+        orb.serialize(ProtocolVersion::V1_21_5),
+        Err(SerializeError::UnsupportedPacket("SpawnExperienceOrb", _))
+    ));
+    assert!(matches!(
+        Play::deserialize(
+            Bound::Clientbound,
+            ProtocolVersion(340),
+            0x01,
+            &mut [].as_slice()
+        ),
+        Err(DeserializeError::UnsupportedVersion(
+            State::Play,
+            ProtocolVersion(340)
+        ))
+    ));
+    // Clients newer than the reports can still be turned away.
+    let disconnect = Login::from(LoginDisconnect {
+        reason: Chat::default().into(),
+    });
+
+    assert!(!Play::supports(ProtocolVersion(773)));
+    assert_eq!(disconnect.serialize(ProtocolVersion(773)).unwrap().0, 0x00);
+}
+
+#[test]
+fn codec_version_from_handshake() {
+    let handshake = arrow_protocol::handshake::Handshake {
+        version: ProtocolVersion::V1_18_2.into(),
+        address: "localhost".to_string(),
+        port: 25565,
+        next_state: NextState::Login,
+    };
+
+    let mut client = Codec::new(Bound::Serverbound, State::Handshake);
+    let mut server = Codec::new(Bound::Serverbound, State::Handshake);
+    let mut bytes = BytesMut::new();
+
+    client
+        .encode(Protocol::Handshake(handshake.into()), &mut bytes)
+        .unwrap();
+
+    assert_eq!(client.version(), ProtocolVersion::V1_18_2);
+
+    server.decode(&mut bytes).unwrap().unwrap();
+
+    assert_eq!(server.version(), ProtocolVersion::V1_18_2);
+}
+
+#[test]
+fn encryption_response_versions() {
+    let response = EncryptionResponse {
+        shared_secret: SharedSecret {
+            encrypted_secret: vec![1].into(),
+        },
+        verify: EncryptionVerify::Token(EncryptedVerifyToken {
+            encrypted_token: vec![2].into(),
+        }),
+    };
+
+    assert_eq!(
+        response.serialize(ProtocolVersion::V1_18_2).unwrap(),
+        [0x01, 0x01, 0x01, 0x02]
+    );
+    assert_eq!(
+        response.serialize(ProtocolVersion::V1_19).unwrap(),
+        [0x01, 0x01, 0x01, 0x01, 0x02]
+    );
+
+    let signed = EncryptionResponse {
+        verify: EncryptionVerify::Signature(SaltSignature {
+            salt: 0,
+            signature: vec![].into(),
+        }),
+        ..response
+    };
+
+    assert!(signed.serialize(ProtocolVersion::V1_19).is_ok());
+    assert!(matches!(
+        signed.serialize(ProtocolVersion::V1_18_2),
+        Err(SerializeError::UnsupportedValue(
+            _,
+            ProtocolVersion::V1_18_2
+        ))
+    ));
+
+    let decoded =
+        EncryptionResponse::deserialize(ProtocolVersion::V1_18_2, &mut [1, 1, 1, 2].as_slice())
+            .unwrap();
+
+    assert!(
+        matches!(decoded.verify, EncryptionVerify::Token(token) if token.encrypted_token[..] == [2])
+    );
+}
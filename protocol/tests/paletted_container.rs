@@ -0,0 +1,183 @@
+use arrow_protocol::{
+    error::DeserializeError,
+    types::{varint::VarInt, BitStorage, Encode, Palette, PaletteKind, PalettedContainer},
+};
+
+#[test]
+fn bit_storage() {
+    let mut storage = BitStorage::new(5, 13);
+
+    assert_eq!(storage.data(), [0, 0]);
+    assert_eq!(storage.set(12, 31), 0);
+    assert_eq!(storage.set(11, 1), 0);
+    assert_eq!(storage.set(11, 2), 1);
+    // 12 entries fit in a long, and the 4 bits left over are unused.
+    assert_eq!(storage.data(), [2 << 55, 31]);
+    assert_eq!(storage.get(12), 31);
+
+    let resized = storage.resize(7);
+
+    assert_eq!(resized.data().len(), 2);
+    assert_eq!(
+        resized.iter().collect::<Vec<_>>(),
+        storage.iter().collect::<Vec<_>>()
+    );
+    assert!(BitStorage::from_data(4, 16, vec![0; 2]).is_err());
+    assert_eq!(BitStorage::new(0, 4096).get(4095), 0);
+}
+
+#[test]
+#[should_panic = "value does not fit"]
+fn bit_storage_overflow() {
+    BitStorage::new(4, 16).set(0, 16);
+}
+
+#[test]
+fn palette_growth() {
+    let mut container = PalettedContainer::single(PaletteKind::BlockStates, 0);
+
+    assert_eq!(container.set(1, 2, 3, 1), 0);
+    assert_eq!(container.palette(), &Palette::Indirect(vec![0, 1]));
+    assert_eq!(container.bits(), 4);
+
+    for i in 2..=16 {
+        container.set(i as usize % 16, 0, 0, i);
+    }
+
+    assert_eq!(container.bits(), 5);
+    assert_eq!(container.get(1, 2, 3), 1);
+
+    for i in 0..16 {
+        container.set(i, 5, 0, 100 + i as i32);
+    }
+
+    // More than 256 values do not fit in an indirect palette.
+    for i in 0..256 {
+        container.set(i % 16, 6 + i / 16 % 10, i / 160, 1000 + i as i32);
+    }
+
+    assert_eq!(container.palette(), &Palette::Direct);
+    assert_eq!(container.bits(), 15);
+    assert_eq!(container.get(15, 5, 0), 115);
+    assert_eq!(container.get(1, 2, 3), 1);
+
+    let values = container.values();
+
+    for (i, value) in values.iter().enumerate() {
+        if *value != 0 {
+            container.set(i % 16, i / 256, i / 16 % 16, 0);
+        }
+    }
+
+    assert_eq!(
+        container,
+        PalettedContainer::single(PaletteKind::BlockStates, 0)
+    );
+}
+
+#[test]
+fn palette_shrinks() {
+    let mut container = PalettedContainer::single(PaletteKind::Biomes(63), 7);
+
+    for x in 0..3 {
+        container.set(x, 0, 0, x as i32 + 1);
+    }
+
+    assert_eq!(container.palette(), &Palette::Indirect(vec![7, 1, 2, 3]));
+
+    // A value which is no longer used gives its slot to the next one.
+    container.set(0, 0, 0, 7);
+    container.set(3, 0, 0, 4);
+
+    assert_eq!(container.palette(), &Palette::Indirect(vec![7, 4, 2, 3]));
+    assert_eq!(container.bits(), 2);
+
+    container.set(1, 0, 0, 7);
+    container.set(2, 0, 0, 7);
+
+    assert_eq!(container.palette(), &Palette::Indirect(vec![7, 4]));
+    assert_eq!(container.bits(), 1);
+    assert_eq!(container.get(3, 0, 0), 4);
+
+    container.set(3, 0, 0, 7);
+
+    assert_eq!(
+        container,
+        PalettedContainer::single(PaletteKind::Biomes(63), 7)
+    );
+}
+
+#[test]
+fn direct_palettes() {
+    let blocks: Vec<_> = (0..4096).collect();
+    let container = PalettedContainer::from_values(PaletteKind::BlockStates, &blocks);
+
+    assert_eq!(container.bits(), 15);
+    assert_eq!(container.palette(), &Palette::Direct);
+    assert_eq!(container.storage().data().len(), 1024);
+    assert_eq!(container.values(), blocks);
+
+    let biomes: Vec<_> = (0..64).map(|i| i % 9).collect();
+    let container = PalettedContainer::from_values(PaletteKind::Biomes(63), &biomes);
+
+    assert_eq!(container.bits(), 6);
+    assert_eq!(container.palette(), &Palette::Direct);
+    assert_eq!(container.values(), biomes);
+    assert_eq!(
+        PalettedContainer::single(PaletteKind::Biomes(63), 4).values(),
+        [4; 64]
+    );
+
+    // A bigger biome registry needs more bits for its ids.
+    let container = PalettedContainer::from_values(PaletteKind::Biomes(100), &biomes);
+
+    assert_eq!(container.bits(), 7);
+    assert_eq!(container.palette(), &Palette::Direct);
+    assert_eq!(container.values(), biomes);
+}
+
+#[test]
+fn exact_round_trip() {
+    // An unused palette entry, as vanilla leaves behind after blocks change, is kept.
+    let mut buf = vec![4];
+    vec![VarInt(0), VarInt(9), VarInt(1)]
+        .encode(&mut buf)
+        .unwrap();
+
+    let mut data = vec![0i64; 256];
+    data[0] = 2;
+    data.encode(&mut buf).unwrap();
+
+    let container =
+        PalettedContainer::decode_kind(PaletteKind::BlockStates, &mut buf.as_slice()).unwrap();
+
+    assert_eq!(container.get(0, 0, 0), 1);
+    assert_eq!(container.get(1, 0, 0), 0);
+
+    let mut encoded = Vec::new();
+    container.encode(&mut encoded).unwrap();
+
+    assert_eq!(encoded, buf);
+}
+
+#[test]
+fn invalid_containers() {
+    // 4 bits per entry need 256 longs, whatever the bits on the wire say.
+    let mut buf = vec![2];
+    vec![VarInt(0), VarInt(1)].encode(&mut buf).unwrap();
+    vec![0i64; 128].encode(&mut buf).unwrap();
+
+    assert!(matches!(
+        PalettedContainer::decode_kind(PaletteKind::BlockStates, &mut buf.as_slice()),
+        Err(DeserializeError::InvalidLength(128))
+    ));
+
+    let mut buf = vec![1];
+    vec![VarInt(7)].encode(&mut buf).unwrap();
+    vec![-1i64].encode(&mut buf).unwrap();
+
+    assert!(matches!(
+        PalettedContainer::decode_kind(PaletteKind::Biomes(63), &mut buf.as_slice()),
+        Err(DeserializeError::InvalidPaletteIndex(1))
+    ));
+}
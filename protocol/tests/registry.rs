@@ -0,0 +1,230 @@
+use arrow_protocol::{
+    error::{BlockStateError, UnknownEntryError},
+    registry::{
+        Block, BlockState, Enchantment, EntityCategory, EntityType, Item, Menu, MobEffect,
+        ParticleType, Registry, SoundEvent,
+    },
+    types::{identifier::Identifier, Context, Decode, Encode},
+    version::ProtocolVersion,
+};
+
+#[test]
+fn lookups() {
+    assert_eq!(Item::KEY, "minecraft:item");
+    assert_eq!(Item::DiamondSword.id(), 755);
+    assert_eq!(Item::from_id(755), Some(Item::DiamondSword));
+    assert_eq!(Item::from_id(-1), None);
+    assert_eq!(Item::from_id(Item::ALL.len() as i32), None);
+    assert_eq!(Item::DiamondSword.path(), "diamond_sword");
+    assert_eq!(Item::from_path("diamond_sword"), Some(Item::DiamondSword));
+
+    let identifier = Identifier::minecraft("oak_stairs").unwrap();
+
+    assert_eq!(Block::OakStairs.identifier(), identifier);
+    assert_eq!(Block::from_identifier(&identifier), Some(Block::OakStairs));
+    assert_eq!(
+        Block::from_identifier(&"custom:oak_stairs".parse().unwrap()),
+        None
+    );
+
+    assert!(Item::ALL
+        .iter()
+        .enumerate()
+        .all(|(id, item)| item.id() == id as i32));
+}
+
+#[test]
+fn names() {
+    assert_eq!(SoundEvent::EntityPigAmbient.id(), 845);
+    assert_eq!(
+        SoundEvent::EntityPigAmbient.to_string(),
+        "minecraft:entity.pig.ambient"
+    );
+    assert_eq!(
+        "entity.pig.ambient".parse::<SoundEvent>(),
+        Ok(SoundEvent::EntityPigAmbient)
+    );
+    assert_eq!("minecraft:flame".parse(), Ok(ParticleType::Flame));
+    assert_eq!("minecraft:crafting".parse(), Ok(Menu::Crafting));
+    assert_eq!(Menu::Generic9x3.to_string(), "minecraft:generic_9x3");
+    assert_eq!(MobEffect::Speed.id(), 0);
+    assert_eq!(Enchantment::Sharpness.id(), 13);
+
+    assert_eq!(
+        "minecraft:unknown".parse::<Item>(),
+        Err(UnknownEntryError(
+            "minecraft:item",
+            "minecraft:unknown".to_string()
+        ))
+    );
+    assert!("custom:stone".parse::<Item>().is_err());
+    assert!("Not An Identifier".parse::<Item>().is_err());
+}
+
+#[test]
+fn encoding() {
+    let mut buf = Vec::new();
+    Enchantment::Sharpness.encode(&mut buf).unwrap();
+
+    assert_eq!(buf, [13]);
+    assert_eq!(
+        Enchantment::decode(&mut buf.as_slice()).unwrap(),
+        Enchantment::Sharpness
+    );
+    assert!(MobEffect::decode(&mut [0x7f].as_slice()).is_err());
+}
+
+#[test]
+fn versions() {
+    const V1_18: Context = Context {
+        version: ProtocolVersion::V1_18,
+    };
+
+    assert_eq!(EntityType::Zombie.id(), 112);
+    assert_eq!(EntityType::Zombie.id_in(ProtocolVersion::V1_18), Some(107));
+    assert_eq!(EntityType::Zombie.id_in(ProtocolVersion::V1_19), Some(112));
+    assert_eq!(EntityType::Zombie.id_in(ProtocolVersion::V1_19_3), None);
+    assert_eq!(EntityType::Frog.id_in(ProtocolVersion::V1_18), None);
+    assert_eq!(
+        EntityType::from_id_in(107, ProtocolVersion::V1_18),
+        Some(EntityType::Zombie)
+    );
+
+    let mut buf = Vec::new();
+    EntityType::Zombie.encode_with(&mut buf, &V1_18).unwrap();
+
+    assert_eq!(buf, [107]);
+    assert_eq!(
+        EntityType::decode_with(&mut buf.as_slice(), &V1_18).unwrap(),
+        EntityType::Zombie
+    );
+    assert!(EntityType::Frog.encode_with(&mut buf, &V1_18).is_err());
+    assert!(EntityType::Zombie
+        .encode_with(
+            &mut buf,
+            &Context {
+                version: ProtocolVersion::V1_19_3
+            }
+        )
+        .is_err());
+
+    let log: BlockState = "oak_log[axis=x]".parse().unwrap();
+
+    assert_eq!(log.id(), 117);
+    assert_eq!(log.id_in(ProtocolVersion::V1_18), Some(76));
+    assert_eq!(log.id_in(ProtocolVersion::V1_18_2), Some(76));
+    assert_eq!(
+        BlockState::from_id_in(76, ProtocolVersion::V1_18),
+        Some(log)
+    );
+
+    let mut buf = Vec::new();
+    log.encode_with(&mut buf, &V1_18).unwrap();
+
+    assert_eq!(
+        BlockState::decode_with(&mut buf.as_slice(), &V1_18).unwrap(),
+        log
+    );
+
+    // Leaves can only be waterlogged since 1.19.
+    let leaves: BlockState = "oak_leaves[distance=1,persistent=true,waterlogged=true]"
+        .parse()
+        .unwrap();
+    let old = BlockState::from_id_in(
+        leaves.id_in(ProtocolVersion::V1_18).unwrap(),
+        ProtocolVersion::V1_18,
+    )
+    .unwrap();
+
+    assert_eq!(old, leaves.with_property("waterlogged", "false").unwrap());
+    assert_eq!(
+        BlockState::from(Block::Air).id_in(ProtocolVersion::V1_18),
+        Some(0)
+    );
+}
+
+#[test]
+fn entity_info() {
+    assert_eq!(EntityType::Pig.id(), 67);
+    assert_eq!(EntityType::Pig.width(), 0.9);
+    assert_eq!(EntityType::Pig.height(), 0.9);
+    assert_eq!(EntityType::Pig.category(), EntityCategory::Passive);
+    assert_eq!(EntityType::Zombie.height(), 1.95);
+    assert_eq!(EntityType::Zombie.category(), EntityCategory::Hostile);
+    assert_eq!(EntityType::Arrow.category(), EntityCategory::Projectile);
+    assert_eq!(EntityType::Painting.category(), EntityCategory::Immobile);
+}
+
+#[test]
+fn block_states() {
+    let stairs = BlockState::from(Block::OakStairs);
+
+    assert_eq!(stairs.id(), 2219);
+    assert!(stairs.is_default());
+    assert_eq!(
+        stairs.properties().collect::<Vec<_>>(),
+        [
+            ("facing", "north"),
+            ("half", "bottom"),
+            ("shape", "straight"),
+            ("waterlogged", "false"),
+        ]
+    );
+    assert_eq!(
+        stairs.to_string(),
+        "minecraft:oak_stairs[facing=north,half=bottom,shape=straight,waterlogged=false]"
+    );
+
+    let state: BlockState = "minecraft:oak_stairs[facing=east,half=top]"
+        .parse()
+        .unwrap();
+
+    assert_eq!(state.block(), Block::OakStairs);
+    assert_eq!(state.property("facing"), Some("east"));
+    assert_eq!(state.property("half"), Some("top"));
+    assert_eq!(state.property("shape"), Some("straight"));
+    assert_eq!(state.property("waterlogged"), Some("false"));
+    assert_eq!(BlockState::from_id(state.id()), Some(state));
+    assert_eq!(
+        state
+            .with_property("facing", "north")
+            .unwrap()
+            .with_property("half", "bottom")
+            .unwrap(),
+        stairs
+    );
+
+    for id in Block::OakStairs.states() {
+        let state = BlockState::from_id(id).unwrap();
+
+        assert_eq!(state.to_string().parse(), Ok(state));
+    }
+
+    assert_eq!(BlockState::default().to_string(), "minecraft:air");
+    assert_eq!("stone".parse(), Ok(BlockState::from(Block::Stone)));
+    assert_eq!(BlockState::from_id(-1), None);
+}
+
+#[test]
+fn block_state_errors() {
+    assert!(matches!(
+        "minecraft:oak_stairs[facing=up]".parse::<BlockState>(),
+        Err(BlockStateError::InvalidValue(name, value)) if name == "facing" && value == "up"
+    ));
+    assert!(matches!(
+        "minecraft:stone[snowy=true]".parse::<BlockState>(),
+        Err(BlockStateError::UnknownProperty(..))
+    ));
+    assert!(matches!(
+        "minecraft:oak_stairs[facing=north".parse::<BlockState>(),
+        Err(BlockStateError::Invalid(_))
+    ));
+    assert!(matches!(
+        "minecraft:oak_stairs[facing]".parse::<BlockState>(),
+        Err(BlockStateError::Invalid(_))
+    ));
+    assert!(matches!(
+        "minecraft:unknown".parse::<BlockState>(),
+        Err(BlockStateError::UnknownBlock(_))
+    ));
+}
@@ -0,0 +1,197 @@
+use arrow_protocol::{
+    error::{DeserializeError, SerializeError},
+    login::{LoginPluginResponse, LoginSuccess, LoginSuccessProperty},
+    schema::{DynamicPacket, FieldType, Schema, Value},
+    status::PingRequest,
+    types::varint::VarInt,
+    version::ProtocolVersion,
+    Bound, State,
+};
+use uuid::Uuid;
+
+const SCHEMA: &str = r#"{
+    "version": "1.19",
+    "states": {
+        "status": {
+            "serverbound": [
+                { "id": 1, "name": "PingRequest", "fields": [{ "name": "payload", "type": "i64" }] }
+            ]
+        },
+        "login": {
+            "serverbound": [
+                {
+                    "id": 2,
+                    "name": "LoginPluginResponse",
+                    "fields": [
+                        { "name": "message_id", "type": "varint" },
+                        { "name": "data", "type": { "option": "byte_array" } }
+                    ]
+                }
+            ],
+            "clientbound": [
+                {
+                    "id": 2,
+                    "name": "LoginSuccess",
+                    "fields": [
+                        { "name": "uuid", "type": "uuid" },
+                        { "name": "username", "type": "string" },
+                        {
+                            "name": "properties",
+                            "type": {
+                                "array": {
+                                    "container": [
+                                        { "name": "name", "type": "string" },
+                                        { "name": "value", "type": "string" },
+                                        { "name": "signature", "type": { "option": "string" } }
+                                    ]
+                                }
+                            }
+                        }
+                    ]
+                }
+            ]
+        }
+    }
+}"#;
+
+#[test]
+fn load() {
+    let schema = Schema::from_json(SCHEMA).unwrap();
+
+    assert_eq!(schema.version, ProtocolVersion::V1_19);
+    assert_eq!(schema.packets(Bound::Clientbound, State::Login).len(), 1);
+    assert!(schema.packets(Bound::Clientbound, State::Play).is_empty());
+    assert_eq!(
+        schema
+            .packet(Bound::Serverbound, State::Login, 2)
+            .unwrap()
+            .fields[1]
+            .ty,
+        FieldType::Option(Box::new(FieldType::ByteArray))
+    );
+    assert!(Schema::from_json(r#"{ "version": "0.0.1" }"#).is_err());
+}
+
+#[test]
+fn matches_compiled_packets() {
+    let schema = Schema::from_json(SCHEMA).unwrap();
+    let version = schema.version;
+
+    let ping = PingRequest { payload: 42 }.serialize(version).unwrap();
+    let packet = schema
+        .decode(
+            Bound::Serverbound,
+            State::Status,
+            0x01,
+            &mut ping.as_slice(),
+        )
+        .unwrap();
+
+    assert_eq!(packet.name, "PingRequest");
+    assert_eq!(packet.value.get("payload"), Some(&Value::Int(42)));
+    assert_eq!(
+        schema
+            .encode(Bound::Serverbound, State::Status, &packet)
+            .unwrap(),
+        (0x01, ping)
+    );
+
+    let response = LoginPluginResponse {
+        message_id: VarInt(7),
+        data: Some(vec![1, 2, 3].into()),
+    }
+    .serialize(version)
+    .unwrap();
+    let packet = schema
+        .decode(
+            Bound::Serverbound,
+            State::Login,
+            0x02,
+            &mut response.as_slice(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .encode(Bound::Serverbound, State::Login, &packet)
+            .unwrap(),
+        (0x02, response)
+    );
+
+    let success = LoginSuccess {
+        uuid: Uuid::from_u128(1),
+        username: "arrow".to_string(),
+        properties: vec![LoginSuccessProperty {
+            name: "textures".to_string(),
+            value: "...".to_string(),
+            signature: None,
+        }],
+        strict_error_handling: false,
+    }
+    .serialize(version)
+    .unwrap();
+    let packet = schema
+        .decode(
+            Bound::Clientbound,
+            State::Login,
+            0x02,
+            &mut success.as_slice(),
+        )
+        .unwrap();
+
+    let Some(Value::List(properties)) = packet.value.get("properties") else {
+        panic!("properties are not a list");
+    };
+
+    assert_eq!(
+        properties[0].get("name").and_then(Value::as_str),
+        Some("textures")
+    );
+    assert_eq!(properties[0].get("signature"), Some(&Value::Option(None)));
+    assert_eq!(
+        schema
+            .encode(Bound::Clientbound, State::Login, &packet)
+            .unwrap(),
+        (0x02, success)
+    );
+}
+
+#[test]
+fn errors() {
+    let schema = Schema::from_json(SCHEMA).unwrap();
+
+    assert!(matches!(
+        schema.decode(Bound::Clientbound, State::Status, 0x01, &mut [].as_slice()),
+        Err(DeserializeError::UnknownPacketId(
+            Bound::Clientbound,
+            State::Status,
+            0x01
+        ))
+    ));
+
+    let packet = |value| DynamicPacket {
+        name: "PingRequest".to_string(),
+        value,
+    };
+
+    assert!(matches!(
+        schema.encode(
+            Bound::Serverbound,
+            State::Status,
+            &packet(Value::Struct(vec![("payload".to_string(), "42".into())]))
+        ),
+        Err(SerializeError::TypeMismatch("i64"))
+    ));
+    assert!(matches!(
+        schema.encode(Bound::Serverbound, State::Status, &packet(Value::Struct(vec![]))),
+        Err(SerializeError::MissingField(field)) if field == "payload"
+    ));
+    assert!(matches!(
+        schema.encode(
+            Bound::Clientbound,
+            State::Status,
+            &packet(Value::Struct(vec![]))
+        ),
+        Err(SerializeError::UnknownPacket(_))
+    ));
+}
@@ -0,0 +1,66 @@
+use arrow_protocol::{
+    play::{
+        entity_type::EntityType,
+        spawn::{velocity_to_fixed, ObjectData},
+        Face, SpawnEntity,
+    },
+    registry::{Block, BlockState},
+    types::varint::VarInt,
+    version::ProtocolVersion,
+};
+use uuid::Uuid;
+
+#[test]
+fn object_data() {
+    let sand = BlockState::from(Block::Sand);
+    let mut packet = SpawnEntity::new(1, Uuid::nil(), EntityType::FallingBlock, 0.5, 64.0, 0.5);
+    packet.set_object_data(ObjectData::BlockState(sand));
+
+    assert_eq!(packet.data, VarInt(sand.id()));
+
+    let data = packet.serialize(ProtocolVersion::V1_19).unwrap();
+    let decoded = SpawnEntity::deserialize(ProtocolVersion::V1_19, &mut data.as_slice()).unwrap();
+
+    assert_eq!(decoded.object_data().unwrap(), ObjectData::BlockState(sand));
+
+    let frame = SpawnEntity::new(2, Uuid::nil(), EntityType::GlowItemFrame, 0.0, 0.0, 0.0);
+
+    assert_eq!(
+        frame.object_data().unwrap(),
+        ObjectData::Direction(Face::Bottom)
+    );
+
+    let mut arrow = SpawnEntity::new(3, Uuid::nil(), EntityType::Arrow, 0.0, 0.0, 0.0);
+
+    assert_eq!(arrow.object_data().unwrap(), ObjectData::Owner(None));
+
+    arrow.set_object_data(ObjectData::Owner(Some(1)));
+
+    assert_eq!(arrow.data, VarInt(1));
+    assert_eq!(
+        ObjectData::from_raw(EntityType::Pig, 5).unwrap(),
+        ObjectData::None
+    );
+    assert!(ObjectData::from_raw(EntityType::Painting, 6).is_err());
+}
+
+#[test]
+#[should_panic]
+fn wrong_object_data() {
+    SpawnEntity::new(1, Uuid::nil(), EntityType::Pig, 0.0, 0.0, 0.0)
+        .set_object_data(ObjectData::Direction(Face::North));
+}
+
+#[test]
+fn velocity() {
+    let mut packet = SpawnEntity::new(1, Uuid::nil(), EntityType::Snowball, 0.0, 0.0, 0.0);
+    packet.set_velocity([0.5, -1.25, 10.0]);
+
+    // Velocity is clamped to 3.9 blocks per tick.
+    assert_eq!(
+        (packet.velocity_x, packet.velocity_y, packet.velocity_z),
+        (4000, -10000, 31200)
+    );
+    assert_eq!(packet.velocity(), [0.5, -1.25, 3.9]);
+    assert_eq!(velocity_to_fixed(-100.0), -31200);
+}
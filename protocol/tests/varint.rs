@@ -1,6 +1,6 @@
-use bytes::BytesMut;
+use bytes::{Buf, BytesMut};
 
-use arrow_protocol::types::{varint::*, Serialize};
+use arrow_protocol::types::{varint::*, Decode, Encode, Serialize};
 
 macro_rules! test {
     ($ty:ident; $($val:literal == [$($b:literal),*]),*) => {
@@ -56,3 +56,21 @@ fn varlong() {
         -0x8000000000000000 == [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]
     }
 }
+
+#[test]
+fn generic_buffers() {
+    let mut vec = Vec::new();
+
+    VarInt(0x63dd).encode(&mut vec).unwrap();
+    VarLong(-0x01).encode(&mut vec).unwrap();
+
+    let mut slice = vec.as_slice();
+
+    assert_eq!(VarInt::decode(&mut slice).unwrap(), VarInt(0x63dd));
+    assert_eq!(VarLong::decode(&mut slice).unwrap(), VarLong(-0x01));
+    assert!(slice.is_empty());
+
+    let mut chain = [0xdd, 0xc7].chain([0x01].as_slice());
+
+    assert_eq!(VarInt::decode(&mut chain).unwrap(), VarInt(0x63dd));
+}
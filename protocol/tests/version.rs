@@ -0,0 +1,77 @@
+use arrow_protocol::{
+    error::UnknownVersionError,
+    version::{ProtocolVersion, VersionInfo},
+};
+
+#[test]
+fn lookup() {
+    assert_eq!(
+        ProtocolVersion::from_name("1.19"),
+        Some(ProtocolVersion(759))
+    );
+    assert_eq!(ProtocolVersion(759).name(), Some("1.19"));
+    assert_eq!(ProtocolVersion(760).name(), Some("1.19.2"));
+    assert_eq!(
+        ProtocolVersion(760)
+            .versions()
+            .map(|info| info.name)
+            .collect::<Vec<_>>(),
+        ["1.19.2", "1.19.1"]
+    );
+    assert_eq!(ProtocolVersion(47).name(), Some("1.8.9"));
+    assert_eq!(
+        ProtocolVersion::from_name("22w42a"),
+        Some(ProtocolVersion(0x40000068))
+    );
+    assert_eq!(
+        VersionInfo::by_data_version(3105).map(|info| info.name),
+        Some("1.19")
+    );
+    assert!(!ProtocolVersion(12345).is_known());
+    assert_eq!(
+        ProtocolVersion::from_name("23w51b"),
+        Some(ProtocolVersion(0x400000aa))
+    );
+    assert_eq!(ProtocolVersion::from_name("25w31a"), None);
+    assert_eq!(
+        ProtocolVersion::newest_snapshot().name(),
+        Some("1.21.8-rc1")
+    );
+    assert_eq!(
+        VersionInfo::all()
+            .iter()
+            .filter(|info| info.name == "1.9.1-pre3")
+            .count(),
+        1
+    );
+    assert!(ProtocolVersion::latest() >= ProtocolVersion::V1_19_3);
+}
+
+#[test]
+fn parse() {
+    assert_eq!("1.18.2".parse(), Ok(ProtocolVersion::V1_18_2));
+    assert_eq!("759".parse(), Ok(ProtocolVersion::V1_19));
+    assert_eq!(
+        "1.19.5".parse::<ProtocolVersion>(),
+        Err(UnknownVersionError("1.19.5".to_string()))
+    );
+}
+
+#[test]
+fn ordering() {
+    let snapshot = ProtocolVersion::from_name("22w42a").unwrap();
+    let pre_release = ProtocolVersion::from_name("1.19.1-pre1").unwrap();
+
+    assert!(snapshot.is_snapshot());
+    assert!(snapshot > ProtocolVersion::V1_19_2);
+    assert!(snapshot < ProtocolVersion::V1_19_3);
+    assert!(pre_release > ProtocolVersion::V1_19);
+    assert!(pre_release < ProtocolVersion::V1_19_1);
+    assert!(pre_release < snapshot);
+    assert!(ProtocolVersion(0x40000fff) > ProtocolVersion::latest());
+    assert!(!ProtocolVersion(0x40000fff).is_known());
+    assert!(ProtocolVersion::newest_snapshot() >= snapshot);
+    assert!(ProtocolVersion::from_name("24w03b").unwrap() > ProtocolVersion::V1_20_3);
+    assert!(ProtocolVersion::from_name("24w03b").unwrap() < ProtocolVersion::V1_20_5);
+    assert!(ProtocolVersion::V1_18 < ProtocolVersion::V1_19);
+}